@@ -0,0 +1,38 @@
+//! A minimal shell reading statements from standard input.
+//!
+//! ```text
+//! cargo run --example shell --features shell -- [database]
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use sqlite_ll::shell::Shell;
+use sqlite_ll::Connection;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args().nth(1);
+    let c = Connection::open(path.as_deref().unwrap_or(":memory:"))?;
+    let mut shell = Shell::new(&c);
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        write!(stdout, "{}", shell.prompt())?;
+        stdout.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        match shell.input(&line) {
+            Ok(output) => write!(stdout, "{output}")?,
+            Err(error) => writeln!(stdout, "Error: {error}")?,
+        }
+    }
+
+    writeln!(stdout)?;
+    Ok(())
+}
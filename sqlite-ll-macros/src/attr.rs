@@ -0,0 +1,89 @@
+/// Attributes on a field, specified through `#[sqlite(..)]`.
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    /// Use the column or parameter with the given name.
+    pub(crate) rename: Option<syn::LitStr>,
+    /// Use the column or parameter with the given index.
+    pub(crate) index: Option<syn::LitInt>,
+}
+
+impl FieldAttr {
+    /// Parse field attributes.
+    pub(crate) fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut attr = Self::default();
+
+        for a in attrs {
+            if !a.path().is_ident("sqlite") {
+                continue;
+            }
+
+            a.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    attr.rename = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("index") {
+                    attr.index = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                Err(meta.error("unsupported attribute"))
+            })?;
+        }
+
+        if let (Some(rename), Some(..)) = (&attr.rename, &attr.index) {
+            return Err(syn::Error::new(
+                rename.span(),
+                "`rename` and `index` can't be used together",
+            ));
+        }
+
+        Ok(attr)
+    }
+}
+
+/// Storage used for a fieldless enum.
+#[derive(Default, Clone, Copy)]
+pub(crate) enum Repr {
+    /// Store the discriminant of the variant as an integer.
+    #[default]
+    Integer,
+    /// Store the name of the variant as text.
+    Text,
+}
+
+/// Attributes on a type, specified through `#[sqlite(..)]`.
+#[derive(Default)]
+pub(crate) struct ContainerAttr {
+    pub(crate) repr: Repr,
+}
+
+impl ContainerAttr {
+    /// Parse container attributes.
+    pub(crate) fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut attr = Self::default();
+
+        for a in attrs {
+            if !a.path().is_ident("sqlite") {
+                continue;
+            }
+
+            a.parse_nested_meta(|meta| {
+                if meta.path.is_ident("integer") {
+                    attr.repr = Repr::Integer;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("text") {
+                    attr.repr = Repr::Text;
+                    return Ok(());
+                }
+
+                Err(meta.error("unsupported attribute"))
+            })?;
+        }
+
+        Ok(attr)
+    }
+}
@@ -0,0 +1,146 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::attr::{ContainerAttr, FieldAttr, Repr};
+
+/// A parsed fieldless enum.
+struct Enum<'a> {
+    input: &'a syn::DeriveInput,
+    repr: Repr,
+    variants: Vec<(&'a syn::Ident, String)>,
+}
+
+impl<'a> Enum<'a> {
+    fn parse(input: &'a syn::DeriveInput, derive: &str) -> syn::Result<Self> {
+        let data = match &input.data {
+            syn::Data::Enum(data) => data,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    format!("`{derive}` can only be derived for fieldless enums"),
+                ))
+            }
+        };
+
+        let attr = ContainerAttr::parse(&input.attrs)?;
+        let mut variants = Vec::new();
+
+        for variant in &data.variants {
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!("`{derive}` can only be derived for fieldless enums"),
+                ));
+            }
+
+            let field = FieldAttr::parse(&variant.attrs)?;
+
+            if let Some(index) = &field.index {
+                return Err(syn::Error::new(
+                    index.span(),
+                    "`index` is not supported on variants",
+                ));
+            }
+
+            let name = match &field.rename {
+                Some(rename) => rename.value(),
+                None => variant.ident.unraw().to_string(),
+            };
+
+            variants.push((&variant.ident, name));
+        }
+
+        Ok(Self {
+            input,
+            repr: attr.repr,
+            variants,
+        })
+    }
+}
+
+pub(crate) fn expand_bindable(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let e = Enum::parse(input, "Bindable")?;
+    let ident = &e.input.ident;
+    let (impl_generics, ty_generics, where_clause) = e.input.generics.split_for_impl();
+
+    let arms = e.variants.iter().map(|(variant, name)| match e.repr {
+        Repr::Integer => quote!(#ident::#variant => (#ident::#variant as i64).bind(statement, i),),
+        Repr::Text => quote!(#ident::#variant => #name.bind(statement, i),),
+    });
+
+    let arms = arms.collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::sqlite_ll::Bindable for #ident #ty_generics #where_clause {
+            #[inline]
+            fn bind(self, statement: &mut ::sqlite_ll::Statement, i: usize) -> ::sqlite_ll::Result<()> {
+                ::sqlite_ll::Bindable::bind(&self, statement, i)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::sqlite_ll::Bindable for &#ident #ty_generics #where_clause {
+            #[inline]
+            fn bind(self, statement: &mut ::sqlite_ll::Statement, i: usize) -> ::sqlite_ll::Result<()> {
+                use ::sqlite_ll::Bindable as _;
+
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn expand_readable(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let e = Enum::parse(input, "Readable")?;
+    let ident = &e.input.ident;
+    let (impl_generics, ty_generics, where_clause) = e.input.generics.split_for_impl();
+
+    let body = match e.repr {
+        Repr::Integer => {
+            let checks = e.variants.iter().map(|(variant, _)| {
+                quote! {
+                    if value == #ident::#variant as i64 {
+                        return ::core::result::Result::Ok(#ident::#variant);
+                    }
+                }
+            });
+
+            let message = format!("unknown value `{{}}` for `{ident}`");
+
+            quote! {
+                let value = statement.read::<i64>(i)?;
+                #(#checks)*
+                ::core::result::Result::Err(::sqlite_ll::Error::custom(::core::format_args!(#message, value)))
+            }
+        }
+        Repr::Text => {
+            let arms = e.variants.iter().map(
+                |(variant, name)| quote!(#name => ::core::result::Result::Ok(#ident::#variant),),
+            );
+
+            let message = format!("unknown value `{{}}` for `{ident}`");
+
+            quote! {
+                let value = statement.read::<::std::string::String>(i)?;
+
+                match value.as_str() {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(::sqlite_ll::Error::custom(::core::format_args!(#message, value))),
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::sqlite_ll::Readable for #ident #ty_generics #where_clause {
+            fn read(statement: &::sqlite_ll::Statement, i: usize) -> ::sqlite_ll::Result<Self> {
+                #body
+            }
+        }
+    })
+}
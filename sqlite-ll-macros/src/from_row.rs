@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::attr::FieldAttr;
+
+pub(crate) fn expand(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        syn::Data::Struct(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`FromRow` can only be derived for structs",
+            ))
+        }
+    };
+
+    let row = quote!(row);
+
+    let construct = match &data.fields {
+        syn::Fields::Named(fields) => {
+            let mut entries = Vec::new();
+
+            for field in &fields.named {
+                let attr = FieldAttr::parse(&field.attrs)?;
+                let ident = field.ident.as_ref().expect("named field");
+                let read = read(&row, &attr, ident.unraw().to_string(), None);
+                entries.push(quote!(#ident: #read));
+            }
+
+            quote!(Self { #(#entries,)* })
+        }
+        syn::Fields::Unnamed(fields) => {
+            let mut entries = Vec::new();
+
+            for (index, field) in fields.unnamed.iter().enumerate() {
+                let attr = FieldAttr::parse(&field.attrs)?;
+                entries.push(read(&row, &attr, index.to_string(), Some(index)));
+            }
+
+            quote!(Self(#(#entries,)*))
+        }
+        syn::Fields::Unit => quote!(Self),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::sqlite_ll::FromRow for #ident #ty_generics #where_clause {
+            #[inline]
+            fn from_row(#row: &::sqlite_ll::Row<'_>) -> ::sqlite_ll::Result<Self> {
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+/// Generate the expression reading a single field.
+///
+/// Fields are read by position if they have an explicit index or if they are
+/// unnamed, otherwise they are read by name.
+fn read(row: &TokenStream, attr: &FieldAttr, name: String, index: Option<usize>) -> TokenStream {
+    if let Some(index) = &attr.index {
+        return quote!(#row.get(#index)?);
+    }
+
+    if let Some(rename) = &attr.rename {
+        return quote!(#row.get_by_name(#rename)?);
+    }
+
+    match index {
+        Some(index) => quote!(#row.get(#index)?),
+        None => quote!(#row.get_by_name(#name)?),
+    }
+}
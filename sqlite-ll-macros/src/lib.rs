@@ -0,0 +1,65 @@
+//! Derive macros for [sqlite-ll].
+//!
+//! These are re-exported from the main crate when the `derive` feature is
+//! enabled, and should not be used directly.
+//!
+//! [sqlite-ll]: https://docs.rs/sqlite-ll
+
+mod attr;
+mod enums;
+mod from_row;
+mod params;
+
+use proc_macro::TokenStream;
+
+/// Derive `FromRow` for a struct.
+///
+/// See the documentation of `sqlite_ll::FromRow`.
+#[proc_macro_derive(FromRow, attributes(sqlite))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match from_row::expand(&input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derive `Params` for a struct.
+///
+/// See the documentation of `sqlite_ll::Params`.
+#[proc_macro_derive(Params, attributes(sqlite))]
+pub fn derive_params(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match params::expand(&input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derive `Bindable` for a fieldless enum.
+///
+/// See the documentation of `sqlite_ll::Bindable`.
+#[proc_macro_derive(Bindable, attributes(sqlite))]
+pub fn derive_bindable(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match enums::expand_bindable(&input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derive `Readable` for a fieldless enum.
+///
+/// See the documentation of `sqlite_ll::Readable`.
+#[proc_macro_derive(Readable, attributes(sqlite))]
+pub fn derive_readable(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match enums::expand_readable(&input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,101 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::attr::FieldAttr;
+
+pub(crate) fn expand(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        syn::Data::Struct(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`Params` can only be derived for structs",
+            ))
+        }
+    };
+
+    let statement = quote!(statement);
+    let mut binds = Vec::new();
+
+    match &data.fields {
+        syn::Fields::Named(fields) => {
+            for field in &fields.named {
+                let attr = FieldAttr::parse(&field.attrs)?;
+                check_index(&attr)?;
+                let ident = field.ident.as_ref().expect("named field");
+                let name = format!(":{}", ident.unraw());
+                binds.push(bind(&statement, &attr, quote!(self.#ident), name, None));
+            }
+        }
+        syn::Fields::Unnamed(fields) => {
+            for (index, field) in fields.unnamed.iter().enumerate() {
+                let attr = FieldAttr::parse(&field.attrs)?;
+                check_index(&attr)?;
+                let member = syn::Index::from(index);
+                binds.push(bind(
+                    &statement,
+                    &attr,
+                    quote!(self.#member),
+                    String::new(),
+                    Some(index + 1),
+                ));
+            }
+        }
+        syn::Fields::Unit => {}
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::sqlite_ll::Params for #ident #ty_generics #where_clause {
+            #[inline]
+            fn bind_params(self, #statement: &mut ::sqlite_ll::Statement) -> ::sqlite_ll::Result<()> {
+                #(#binds)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+/// Check that an explicit parameter index is valid, since parameters are
+/// indexed from 1.
+fn check_index(attr: &FieldAttr) -> syn::Result<()> {
+    if let Some(index) = &attr.index {
+        if index.base10_parse::<usize>()? == 0 {
+            return Err(syn::Error::new(
+                index.span(),
+                "parameter indexes start at 1",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate the statement binding a single field.
+///
+/// Fields are bound by index if they have an explicit index or if they are
+/// unnamed, otherwise they are bound by name.
+fn bind(
+    statement: &TokenStream,
+    attr: &FieldAttr,
+    value: TokenStream,
+    name: String,
+    index: Option<usize>,
+) -> TokenStream {
+    if let Some(index) = &attr.index {
+        return quote!(#statement.bind(#index, #value)?;);
+    }
+
+    if let Some(rename) = &attr.rename {
+        return quote!(#statement.bind_by_name(#rename, #value)?;);
+    }
+
+    match index {
+        Some(index) => quote!(#statement.bind(#index, #value)?;),
+        None => quote!(#statement.bind_by_name(#name, #value)?;),
+    }
+}
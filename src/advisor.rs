@@ -0,0 +1,278 @@
+//! An index advisor for a workload of statements.
+//!
+//! The advisor inspects the query plans of a set of statements, reports the
+//! tables which are scanned in full, and suggests indexes which would let the
+//! planner avoid those scans or temporary sorting b-trees. This is similar to
+//! the `.expert` command of the SQLite shell.
+//!
+//! Candidate indexes are found by copying the schema of the database into an
+//! empty in-memory database and greedily trying indexes over the columns of
+//! every table, keeping those which improve the plan. The database being
+//! analyzed is never modified.
+//!
+//! Since the copy is empty, the planner uses its default estimates rather than
+//! the statistics gathered by `ANALYZE`, so suggestions should be verified
+//! against real data. Virtual tables are only supported if their module is
+//! available on every connection.
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::advisor;
+//!
+//! let c = sqlite_ll::Connection::open(":memory:")?;
+//! c.execute("CREATE TABLE users (name TEXT, age INTEGER, city TEXT)")?;
+//!
+//! let report = advisor::analyze(&c, [
+//!     "SELECT name FROM users WHERE age = ?",
+//!     "SELECT name FROM users WHERE age = ? AND city = ?",
+//! ])?;
+//!
+//! assert_eq!(report.statements[0].full_scans, ["users"]);
+//! assert_eq!(report.indexes, [
+//!     "CREATE INDEX \"users_age\" ON \"users\" (\"age\")",
+//!     "CREATE INDEX \"users_age_city\" ON \"users\" (\"age\", \"city\")",
+//! ]);
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+
+use std::cmp::Reverse;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::statement::State;
+use crate::utils;
+
+/// The maximum number of columns in a suggested index.
+const MAX_COLUMNS: usize = 4;
+
+/// The prefix used for the names of indexes created while searching.
+const CANDIDATE: &str = "advisor_candidate_";
+
+/// The result of analyzing a workload with [`analyze`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Report {
+    /// The analysis of each statement, in the order they were given.
+    pub statements: Vec<StatementReport>,
+    /// The indexes suggested for the whole workload, without duplicates.
+    pub indexes: Vec<String>,
+}
+
+/// The analysis of a single statement.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StatementReport {
+    /// The analyzed statement.
+    pub sql: String,
+    /// The details of each step of the current query plan.
+    pub plan: Vec<String>,
+    /// The tables which are scanned in full by the current plan, either
+    /// directly or through an index. Tables are named by their alias if the
+    /// statement uses one.
+    pub full_scans: Vec<String>,
+    /// The `CREATE INDEX` statements suggested for this statement.
+    pub indexes: Vec<String>,
+}
+
+/// Analyze the plans of the given statements against the schema of the
+/// connection and suggest indexes.
+///
+/// See the [module level documentation][self] for more.
+pub fn analyze<I>(c: &Connection, statements: I) -> Result<Report>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let scratch = Connection::open(":memory:")?;
+
+    let mut schema = c.prepare(
+        "SELECT sql FROM main.sqlite_schema \
+         WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' AND type IN ('table', 'index', 'view') \
+         ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'index' THEN 1 ELSE 2 END, rowid",
+    )?;
+
+    while let State::Row = schema.step()? {
+        scratch.execute(schema.read::<String>(0)?)?;
+    }
+
+    let mut tables = Vec::new();
+
+    let mut stmt = scratch.prepare(
+        "SELECT name FROM sqlite_schema WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+
+    while let State::Row = stmt.step()? {
+        let table = stmt.read::<String>(0)?;
+        let mut info = scratch.prepare("SELECT name FROM pragma_table_info(?)")?;
+        info.bind(1, table.as_str())?;
+
+        let columns = info
+            .iter::<(String,)>()
+            .map(|row| Ok(row?.0))
+            .collect::<Result<Vec<_>>>()?;
+
+        tables.push((table, columns));
+    }
+
+    let mut report = Report::default();
+
+    for sql in statements {
+        let statement = analyze_statement(&scratch, &tables, sql.as_ref())?;
+
+        for index in &statement.indexes {
+            if !report.indexes.contains(index) {
+                report.indexes.push(index.clone());
+            }
+        }
+
+        report.statements.push(statement);
+    }
+
+    Ok(report)
+}
+
+fn analyze_statement(
+    scratch: &Connection,
+    tables: &[(String, Vec<String>)],
+    sql: &str,
+) -> Result<StatementReport> {
+    let plan = query_plan(scratch, sql)?;
+    let full_scans = plan
+        .iter()
+        .filter_map(|detail| full_scan(detail))
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+
+    let mut current = cost(&plan);
+    let mut indexes = Vec::new();
+    let mut created = 0;
+
+    if current.0 > 0 || current.1 > 0 {
+        for (table, columns) in tables {
+            let mut chosen = Vec::<&str>::new();
+
+            while chosen.len() < MAX_COLUMNS {
+                let mut best = None;
+
+                for column in columns {
+                    if chosen.contains(&column.as_str()) {
+                        continue;
+                    }
+
+                    let mut candidate = chosen.clone();
+                    candidate.push(column);
+
+                    let name = format!("{CANDIDATE}{created}");
+                    scratch.execute(create_index(&name, table, &candidate))?;
+                    let cost = cost(&query_plan(scratch, sql)?);
+                    scratch.execute(format!("DROP INDEX \"{name}\""))?;
+
+                    if cost < best.as_ref().map_or(current, |(cost, _)| *cost) {
+                        best = Some((cost, column.as_str()));
+                    }
+                }
+
+                match best {
+                    Some((cost, column)) => {
+                        current = cost;
+                        chosen.push(column);
+                    }
+                    None => break,
+                }
+            }
+
+            if chosen.is_empty() {
+                continue;
+            }
+
+            // Keep the index while looking at other tables, since the best
+            // index for them might depend on it.
+            let name = format!("{CANDIDATE}{created}");
+            scratch.execute(create_index(&name, table, &chosen))?;
+            created += 1;
+
+            let name = format!("{table}_{}", chosen.join("_"));
+            indexes.push(create_index(&name, table, &chosen));
+        }
+    }
+
+    for n in 0..created {
+        scratch.execute(format!("DROP INDEX \"{CANDIDATE}{n}\""))?;
+    }
+
+    Ok(StatementReport {
+        sql: sql.to_owned(),
+        plan,
+        full_scans,
+        indexes,
+    })
+}
+
+/// Get the details of the query plan for a statement.
+fn query_plan(c: &Connection, sql: &str) -> Result<Vec<String>> {
+    let mut stmt = c.prepare(format!("EXPLAIN QUERY PLAN {sql}"))?;
+    let mut plan = Vec::new();
+
+    while let State::Row = stmt.step()? {
+        plan.push(stmt.read::<String>(3)?);
+    }
+
+    Ok(plan)
+}
+
+/// Get the name of the table scanned by a step of a query plan, if it scans
+/// a table.
+fn full_scan(detail: &str) -> Option<&str> {
+    let rest = detail.strip_prefix("SCAN ")?;
+    // Versions of SQLite before 3.36 include the word `TABLE`.
+    let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+
+    if rest == "CONSTANT ROW" || rest.starts_with('(') {
+        return None;
+    }
+
+    rest.split(' ').next()
+}
+
+/// Compute the cost of a query plan, where lower is better.
+///
+/// Full scans are the most expensive, followed by temporary b-trees used for
+/// sorting or grouping. Among plans which are otherwise equal, the one which
+/// uses more constraints to search indexes is preferred.
+fn cost(plan: &[String]) -> (usize, usize, Reverse<usize>) {
+    let mut scans = 0;
+    let mut temporary = 0;
+    let mut constraints = 0;
+
+    for detail in plan {
+        if full_scan(detail).is_some() {
+            scans += 1;
+        } else if detail.starts_with("USE TEMP B-TREE") {
+            temporary += 1;
+        } else if detail.starts_with("SEARCH ") {
+            constraints += detail.matches('?').count();
+        }
+    }
+
+    (scans, temporary, Reverse(constraints))
+}
+
+fn create_index(name: &str, table: &str, columns: &[&str]) -> String {
+    let mut sql = String::from("CREATE INDEX ");
+    utils::push_identifier(&mut sql, name);
+    sql.push_str(" ON ");
+    utils::push_identifier(&mut sql, table);
+    sql.push_str(" (");
+
+    for (n, column) in columns.iter().enumerate() {
+        if n > 0 {
+            sql.push_str(", ");
+        }
+
+        utils::push_identifier(&mut sql, column);
+    }
+
+    sql.push(')');
+    sql
+}
@@ -0,0 +1,615 @@
+use std::future::{self as std_future, Future};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::thread;
+
+use sqlite3_sys as ffi;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::connection::{self, Connection, OpenOptions};
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::row::FromRow;
+use crate::statement::{Readable, State, Statement};
+
+/// A job sent to the worker thread.
+type Job = Box<dyn FnOnce(&mut Connection) + Send>;
+
+/// The receiving end of the result of a job.
+type Receiver<T> = oneshot::Receiver<thread::Result<Result<T>>>;
+
+/// An asynchronous connection which runs all operations on a dedicated worker
+/// thread.
+///
+/// Operations are sent to the worker as messages and their results are
+/// awaited, so that async services can use SQLite without blocking the
+/// workers of their runtime. This only depends on the synchronization
+/// primitives of [tokio], so it can be used with any executor.
+///
+/// The connection can be cheaply cloned, and all clones share the same worker
+/// thread which executes operations in the order they were submitted. The
+/// worker and the underlying connection are shut down once the last clone and
+/// the last statement prepared through it have been dropped.
+///
+/// If an operation panics, the panic is propagated to the task awaiting it.
+///
+/// [tokio]: https://docs.rs/tokio
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::AsyncConnection;
+///
+/// # #[tokio::main(flavor = "current_thread")] async fn main() -> sqlite_ll::Result<()> {
+/// let c = AsyncConnection::open(":memory:").await?;
+///
+/// c.execute("CREATE TABLE users (name TEXT, age INTEGER)").await?;
+/// c.execute("INSERT INTO users VALUES ('Alice', 42), ('Bob', 69)").await?;
+///
+/// let mut stmt = c.prepare("SELECT name, age FROM users WHERE age > ?").await?;
+/// stmt.bind_all((50,)).await?;
+///
+/// let mut users = Vec::new();
+///
+/// while let Some(user) = stmt.next::<(String, i64)>().await? {
+///     users.push(user);
+/// }
+///
+/// assert_eq!(users, [(String::from("Bob"), 69)]);
+/// # Ok(()) }
+/// ```
+#[derive(Clone)]
+pub struct AsyncConnection {
+    tx: mpsc::UnboundedSender<Job>,
+    handle: Handle,
+}
+
+impl AsyncConnection {
+    /// Move an already opened connection to a new worker thread.
+    pub fn new(connection: Connection) -> Self {
+        let handle = Handle::new(&connection);
+        let (tx, rx) = mpsc::unbounded_channel();
+        thread::spawn(move || worker(connection, rx));
+
+        Self { tx, handle }
+    }
+
+    /// Open a read-write connection to a new or existing database on a new
+    /// worker thread.
+    ///
+    /// See [`Connection::open`].
+    pub async fn open<T>(path: T) -> Result<Self>
+    where
+        T: AsRef<Path>,
+    {
+        Self::open_with(OpenOptions::new().set_create().set_read_write(), path).await
+    }
+
+    /// Open a connection using the given options on a new worker thread.
+    ///
+    /// The connection is opened by the worker, so that the runtime isn't
+    /// blocked while doing so.
+    pub async fn open_with<T>(options: OpenOptions, path: T) -> Result<Self>
+    where
+        T: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+        let (tx, rx) = oneshot::channel();
+
+        thread::spawn(move || {
+            let connection = match options.open(path) {
+                Ok(connection) => connection,
+                Err(error) => {
+                    let _ = tx.send(Err(error));
+                    return;
+                }
+            };
+
+            let handle = Handle::new(&connection);
+            let (jobs_tx, jobs_rx) = mpsc::unbounded_channel();
+
+            if tx
+                .send(Ok(Self {
+                    tx: jobs_tx,
+                    handle,
+                }))
+                .is_ok()
+            {
+                worker(connection, jobs_rx);
+            }
+        });
+
+        match rx.await {
+            Ok(result) => result,
+            Err(..) => Err(stopped()),
+        }
+    }
+
+    /// Run the given closure with the connection on the worker thread and
+    /// return its result.
+    ///
+    /// This is the building block of all other operations, and can be used to
+    /// perform anything which is supported by [`Connection`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::AsyncConnection;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() -> sqlite_ll::Result<()> {
+    /// let c = AsyncConnection::open(":memory:").await?;
+    ///
+    /// let count = c.call(|c| {
+    ///     c.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1), (2)")?;
+    ///     Ok(c.change_count())
+    /// }).await?;
+    ///
+    /// assert_eq!(count, 2);
+    /// # Ok(()) }
+    /// ```
+    pub async fn call<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let rx = self.submit(f)?;
+        receive(rx.await)
+    }
+
+    /// Run the given closure with the connection on the worker thread, and
+    /// interrupt it if `cancel` completes first.
+    ///
+    /// Cancellation is performed using `sqlite3_interrupt`, which causes any
+    /// statement being executed by the closure to fail with
+    /// [`Code::INTERRUPT`]. If the closure hasn't started running yet it is
+    /// skipped and the same error is returned. Note that the closure is still
+    /// waited for after cancellation, since it might hold on to resources
+    /// which are in use.
+    ///
+    /// Any future can be used to signal cancellation, such as the one returned
+    /// by `CancellationToken::cancelled` in [tokio-util].
+    ///
+    /// [`Code::INTERRUPT`]: crate::Code::INTERRUPT
+    /// [tokio-util]: https://docs.rs/tokio-util
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{AsyncConnection, Code};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() -> sqlite_ll::Result<()> {
+    /// let c = AsyncConnection::open(":memory:").await?;
+    ///
+    /// let e = c.call_until(std::future::ready(()), |c| {
+    ///     c.query_value::<i64, _>("WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) SELECT MAX(i) FROM n", ())
+    /// }).await.unwrap_err();
+    ///
+    /// assert_eq!(e.code(), Code::INTERRUPT);
+    /// # Ok(()) }
+    /// ```
+    pub async fn call_until<C, F, T>(&self, cancel: C, f: F) -> Result<T>
+    where
+        C: Future<Output = ()>,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.run_until(cancel, move |c, skipped| match skipped {
+            true => Err(interrupted()),
+            false => f(c),
+        })
+        .await
+    }
+
+    /// Run a job which can be cancelled, where the closure is told whether it
+    /// was skipped due to having been cancelled before it started.
+    async fn run_until<C, F, T>(&self, cancel: C, f: F) -> Result<T>
+    where
+        C: Future<Output = ()>,
+        F: FnOnce(&mut Connection, bool) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let status = Arc::new(Mutex::new(Status::Pending));
+
+        let mut rx = self.submit({
+            let status = status.clone();
+
+            move |c| {
+                {
+                    let mut status = status.lock().unwrap_or_else(|e| e.into_inner());
+
+                    if *status == Status::Cancelled {
+                        drop(status);
+                        return f(c, true);
+                    }
+
+                    *status = Status::Running;
+                }
+
+                let result = f(c, false);
+                *status.lock().unwrap_or_else(|e| e.into_inner()) = Status::Done;
+                result
+            }
+        })?;
+
+        let mut cancel = Box::pin(cancel);
+
+        let result = std_future::poll_fn(|cx| {
+            if let Poll::Ready(result) = Pin::new(&mut rx).poll(cx) {
+                return Poll::Ready(Some(result));
+            }
+
+            match cancel.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await;
+
+        let result = match result {
+            Some(result) => result,
+            None => {
+                {
+                    let mut status = status.lock().unwrap_or_else(|e| e.into_inner());
+
+                    match *status {
+                        Status::Pending => {
+                            *status = Status::Cancelled;
+                        }
+                        Status::Running => {
+                            // SAFETY: The status lock ensures that the closure
+                            // is still running, so the connection is alive.
+                            unsafe { ffi::sqlite3_interrupt(self.handle.0.as_ptr()) };
+                        }
+                        Status::Cancelled | Status::Done => {}
+                    }
+                }
+
+                rx.await
+            }
+        };
+
+        receive(result)
+    }
+
+    /// Execute a statement without processing the resulting rows if any,
+    /// interrupting it if `cancel` completes first.
+    ///
+    /// See [`AsyncConnection::call_until`].
+    pub async fn execute_until<C, T>(&self, cancel: C, statement: T) -> Result<()>
+    where
+        C: Future<Output = ()>,
+        T: AsRef<str>,
+    {
+        let statement = statement.as_ref().to_owned();
+        self.call_until(cancel, move |c| c.execute(statement)).await
+    }
+
+    /// Execute a statement without processing the resulting rows if any.
+    ///
+    /// See [`Connection::execute`].
+    pub async fn execute<T>(&self, statement: T) -> Result<()>
+    where
+        T: AsRef<str>,
+    {
+        let statement = statement.as_ref().to_owned();
+        self.call(move |c| c.execute(statement)).await
+    }
+
+    /// Create a prepared statement which is stepped on the worker thread.
+    ///
+    /// See [`Connection::prepare`].
+    pub async fn prepare<T>(&self, statement: T) -> Result<AsyncStatement>
+    where
+        T: AsRef<str>,
+    {
+        let statement = statement.as_ref().to_owned();
+        let statement = self.call(move |c| c.prepare(statement)).await?;
+
+        Ok(AsyncStatement {
+            connection: self.clone(),
+            statement: Some(statement),
+        })
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row.
+    ///
+    /// See [`Connection::query_row`].
+    pub async fn query_row<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: FromRow + Send + 'static,
+        P: Params + Send + 'static,
+    {
+        let statement = statement.as_ref().to_owned();
+        self.call(move |c| c.query_row(statement, params)).await
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row, and read its first column.
+    ///
+    /// See [`Connection::query_value`].
+    pub async fn query_value<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: Readable + Send + 'static,
+        P: Params + Send + 'static,
+    {
+        let statement = statement.as_ref().to_owned();
+        self.call(move |c| c.query_value(statement, params)).await
+    }
+
+    /// Run the given closure inside of a transaction on the worker thread.
+    ///
+    /// The transaction is committed if the closure returns `Ok`, and rolled
+    /// back if it returns an error or panics. Since the whole closure runs on
+    /// the worker, no other operations are interleaved with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::AsyncConnection;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() -> sqlite_ll::Result<()> {
+    /// let c = AsyncConnection::open(":memory:").await?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER)").await?;
+    ///
+    /// let result = c.transaction(|c| {
+    ///     c.execute("INSERT INTO numbers VALUES (1)")?;
+    ///     c.execute("INSERT INTO missing VALUES (2)")
+    /// }).await;
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ()).await?, 0);
+    /// # Ok(()) }
+    /// ```
+    pub async fn transaction<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.call(move |c| {
+            c.execute("BEGIN")?;
+
+            let result = match panic::catch_unwind(AssertUnwindSafe(|| f(c))) {
+                Ok(result) => result,
+                Err(panic) => {
+                    // The panic is propagated even if rolling back fails.
+                    let _ = rollback(c);
+                    panic::resume_unwind(panic);
+                }
+            };
+
+            connection::commit_or_rollback(c.as_raw(), result)
+        })
+        .await
+    }
+
+    /// Submit a job to the worker thread.
+    fn submit<F, T>(&self, f: F) -> Result<Receiver<T>>
+    where
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+
+        let job: Job = Box::new(move |connection| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(connection)));
+            let _ = tx.send(result);
+        });
+
+        if self.tx.send(job).is_err() {
+            return Err(stopped());
+        }
+
+        Ok(rx)
+    }
+}
+
+/// A prepared statement which is stepped on the worker thread of an
+/// [`AsyncConnection`].
+///
+/// The statement is moved to the worker for the duration of each operation.
+/// If the future of an operation is dropped before it completes, the
+/// statement is lost and all subsequent operations on it result in an error.
+///
+/// The statement is finalized on the worker thread when it's dropped.
+pub struct AsyncStatement {
+    connection: AsyncConnection,
+    statement: Option<Statement>,
+}
+
+impl AsyncStatement {
+    /// Run the given closure with the statement on the worker thread and return
+    /// its result.
+    pub async fn call<F, T>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Statement) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut statement = match self.statement.take() {
+            Some(statement) => statement,
+            None => return Err(lost()),
+        };
+
+        let (statement, result) = self
+            .connection
+            .call(move |_| {
+                let result = f(&mut statement);
+                Ok((statement, result))
+            })
+            .await?;
+
+        self.statement = Some(statement);
+        result
+    }
+
+    /// Run the given closure with the statement on the worker thread, and
+    /// interrupt it if `cancel` completes first.
+    ///
+    /// See [`AsyncConnection::call_until`].
+    pub async fn call_until<C, F, T>(&mut self, cancel: C, f: F) -> Result<T>
+    where
+        C: Future<Output = ()>,
+        F: FnOnce(&mut Statement) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut statement = match self.statement.take() {
+            Some(statement) => statement,
+            None => return Err(lost()),
+        };
+
+        let (tx, rx) = oneshot::channel();
+
+        let result = self
+            .connection
+            .run_until(cancel, move |_, skipped| {
+                let result = match skipped {
+                    true => Err(interrupted()),
+                    false => f(&mut statement),
+                };
+
+                let _ = tx.send(statement);
+                result
+            })
+            .await;
+
+        // The statement is only lost if the closure panicked.
+        if let Ok(statement) = rx.await {
+            self.statement = Some(statement);
+        }
+
+        result
+    }
+
+    /// Bind parameters to the statement.
+    ///
+    /// See [`Statement::bind_all`].
+    pub async fn bind_all<P>(&mut self, params: P) -> Result<()>
+    where
+        P: Params + Send + 'static,
+    {
+        self.call(move |s| s.bind_all(params)).await
+    }
+
+    /// Advance the statement to the next row.
+    ///
+    /// See [`Statement::step`].
+    pub async fn step(&mut self) -> Result<State> {
+        self.call(Statement::step).await
+    }
+
+    /// Advance the statement and read the next row, or return `None` if the
+    /// statement is done.
+    pub async fn next<T>(&mut self) -> Result<Option<T>>
+    where
+        T: FromRow + Send + 'static,
+    {
+        self.call(|s| match s.next_row()? {
+            Some(row) => Ok(Some(T::from_row(&row)?)),
+            None => Ok(None),
+        })
+        .await
+    }
+
+    /// Read a value from a column of the current row.
+    ///
+    /// See [`Statement::read`].
+    pub async fn read<T>(&mut self, i: usize) -> Result<T>
+    where
+        T: Readable + Send + 'static,
+    {
+        self.call(move |s| s.read(i)).await
+    }
+
+    /// Reset the statement so that it can be executed again.
+    ///
+    /// See [`Statement::reset`].
+    pub async fn reset(&mut self) -> Result<()> {
+        self.call(Statement::reset).await
+    }
+}
+
+impl Drop for AsyncStatement {
+    fn drop(&mut self) {
+        // The statement is finalized on the worker, since the connection
+        // might not be protected by a mutex and could be in use by it.
+        if let Some(statement) = self.statement.take() {
+            let _ = self.connection.submit(move |_| {
+                drop(statement);
+                Ok(())
+            });
+        }
+    }
+}
+
+/// The status of a job which can be cancelled.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pending,
+    Running,
+    Cancelled,
+    Done,
+}
+
+/// The database handle of the worker, used for interrupting it.
+#[derive(Clone, Copy)]
+struct Handle(NonNull<ffi::sqlite3>);
+
+impl Handle {
+    fn new(connection: &Connection) -> Self {
+        // SAFETY: Connection handles are never null.
+        Self(unsafe { NonNull::new_unchecked(connection.as_raw()) })
+    }
+}
+
+// SAFETY: The handle is only used with `sqlite3_interrupt`, which is thread
+// safe, and the connection is kept alive by the worker for as long as any
+// sender which is stored alongside the handle exists.
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+/// Run jobs against the connection until all senders have been dropped.
+fn worker(mut connection: Connection, mut rx: mpsc::UnboundedReceiver<Job>) {
+    while let Some(job) = rx.blocking_recv() {
+        job(&mut connection);
+    }
+}
+
+/// Roll back the current transaction unless SQLite already did so.
+fn rollback(c: &Connection) -> Result<()> {
+    if !c.is_autocommit() {
+        c.execute("ROLLBACK")?;
+    }
+
+    Ok(())
+}
+
+/// Unpack the result of a job, propagating panics.
+fn receive<T>(result: Result<thread::Result<Result<T>>, oneshot::error::RecvError>) -> Result<T> {
+    match result {
+        Ok(Ok(result)) => result,
+        Ok(Err(panic)) => panic::resume_unwind(panic),
+        Err(..) => Err(stopped()),
+    }
+}
+
+fn interrupted() -> Error {
+    Error::from_code(ffi::SQLITE_INTERRUPT)
+}
+
+fn stopped() -> Error {
+    Error::new(
+        ffi::SQLITE_MISUSE,
+        Some("connection worker has stopped".into()),
+    )
+}
+
+fn lost() -> Error {
+    Error::new(
+        ffi::SQLITE_MISUSE,
+        Some("statement was lost since an operation on it was cancelled".into()),
+    )
+}
@@ -0,0 +1,184 @@
+use std::time::{Duration, Instant};
+
+use crate::connection::{self, Connection};
+use crate::error::Result;
+use crate::statement::State;
+use crate::value::Value;
+
+/// The default number of queued statements which triggers a flush.
+const DEFAULT_MAX_COUNT: usize = 1000;
+
+/// The default age of the oldest queued statement which triggers a flush.
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// A writer which queues statements and executes them in batches inside of a
+/// single transaction.
+///
+/// Committing a transaction is expensive, so writing many small changes which
+/// each commit on their own is slow. Queued statements are instead flushed
+/// once [`max_count`] of them have been queued, or once the oldest one has
+/// been queued for longer than [`max_delay`]. The delay is only checked when
+/// a statement is queued, so [`flush`] should be called periodically if
+/// statements are queued irregularly.
+///
+/// Any remaining statements are flushed when the writer is dropped, in which
+/// case errors are ignored. Call [`flush`] before dropping the writer to
+/// observe them.
+///
+/// Like [`Statement::execute_batch`], if the connection is already inside of
+/// a transaction when flushing, the statements are executed as part of it.
+///
+/// [`max_count`]: BatchedWriter::max_count
+/// [`max_delay`]: BatchedWriter::max_delay
+/// [`flush`]: BatchedWriter::flush
+/// [`Statement::execute_batch`]: crate::Statement::execute_batch
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{BatchedWriter, Value};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE events (kind TEXT, value INTEGER)")?;
+///
+/// let mut writer = BatchedWriter::new(&c).max_count(100);
+///
+/// for n in 0..250 {
+///     writer.push("INSERT INTO events VALUES (?, ?)", &[Value::Text("tick".into()), Value::Integer(n)])?;
+/// }
+///
+/// assert_eq!(writer.len(), 50);
+/// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM events", ())?, 200);
+///
+/// writer.flush()?;
+/// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM events", ())?, 250);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub struct BatchedWriter<'a> {
+    connection: &'a Connection,
+    queue: Vec<(Box<str>, Vec<Value>)>,
+    max_count: usize,
+    max_delay: Duration,
+    /// When the oldest statement in the queue was queued.
+    oldest: Option<Instant>,
+}
+
+impl<'a> BatchedWriter<'a> {
+    /// Construct a writer which flushes to the given connection once 1000
+    /// statements have been queued, or once the oldest one has been queued
+    /// for a second.
+    pub fn new(connection: &'a Connection) -> Self {
+        Self {
+            connection,
+            queue: Vec::new(),
+            max_count: DEFAULT_MAX_COUNT,
+            max_delay: DEFAULT_MAX_DELAY,
+            oldest: None,
+        }
+    }
+
+    /// Set the number of queued statements which triggers a flush.
+    pub fn max_count(mut self, max_count: usize) -> Self {
+        self.max_count = max_count.max(1);
+        self
+    }
+
+    /// Set how long the oldest queued statement may wait before a flush is
+    /// triggered.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// The number of queued statements.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Test if no statements are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Queue a statement to be executed with the given values bound to its
+    /// parameters, flushing the queue if a threshold has been reached.
+    ///
+    /// Errors are those of [`BatchedWriter::flush`].
+    pub fn push(&mut self, statement: impl AsRef<str>, values: &[Value]) -> Result<()> {
+        let now = Instant::now();
+        let oldest = *self.oldest.get_or_insert(now);
+        self.queue
+            .push((statement.as_ref().into(), values.to_vec()));
+
+        if self.queue.len() >= self.max_count || now.duration_since(oldest) >= self.max_delay {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Execute all queued statements inside of a single transaction.
+    ///
+    /// Statements are prepared through [`Connection::prepare_cached`]. If a
+    /// statement fails the transaction is rolled back, and the queue is
+    /// discarded so that a failing statement isn't retried indefinitely.
+    ///
+    /// If the transaction can't be committed, such as with [`Code::BUSY`]
+    /// when another connection is reading from the database, it's rolled back
+    /// and the queue is kept so that flushing can be retried.
+    ///
+    /// [`Code::BUSY`]: crate::Code::BUSY
+    pub fn flush(&mut self) -> Result<()> {
+        if self.queue.is_empty() {
+            return Ok(());
+        }
+
+        let c = self.connection;
+        let owned = c.is_autocommit();
+
+        if owned {
+            c.execute("BEGIN")?;
+        }
+
+        let result = (|| {
+            for (statement, values) in &self.queue {
+                let mut statement = c.prepare_cached(statement)?;
+                statement.bind_values(values)?;
+                while let State::Row = statement.step()? {}
+            }
+
+            Ok(())
+        })();
+
+        if let Err(error) = result {
+            self.clear();
+
+            // SQLite might already have rolled back the transaction depending
+            // on the error.
+            if owned && !c.is_autocommit() {
+                c.execute("ROLLBACK")?;
+            }
+
+            return Err(error);
+        }
+
+        if owned {
+            connection::commit_or_rollback(c.as_raw(), Ok(()))?;
+        }
+
+        self.clear();
+        Ok(())
+    }
+
+    /// Discard all queued statements.
+    fn clear(&mut self) {
+        self.queue.clear();
+        self.oldest = None;
+    }
+}
+
+impl Drop for BatchedWriter<'_> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
@@ -0,0 +1,94 @@
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+
+use crate::connection::Connection;
+use crate::statement::Statement;
+
+/// A prepared statement which borrows the connection it was prepared from.
+///
+/// This dereferences to [`Statement`], but cannot outlive the borrow of the
+/// connection. This ensures that the connection stays open for as long as
+/// the statement is in use, which is useful for statements that are only
+/// used within a single scope.
+///
+/// See [`Connection::prepare_borrowed`].
+pub struct BorrowedStatement<'a> {
+    statement: Statement,
+    _marker: PhantomData<&'a Connection>,
+}
+
+impl<'a> BorrowedStatement<'a> {
+    #[inline]
+    pub(crate) fn new(statement: Statement) -> Self {
+        Self {
+            statement,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Release the borrow of the connection, returning the underlying
+    /// statement.
+    #[inline]
+    pub fn into_inner(self) -> Statement {
+        self.statement
+    }
+}
+
+impl Deref for BorrowedStatement<'_> {
+    type Target = Statement;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.statement
+    }
+}
+
+impl DerefMut for BorrowedStatement<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.statement
+    }
+}
+
+/// A connection adopted from a raw database handle which is owned by someone
+/// else.
+///
+/// This dereferences to [`Connection`], but never closes the handle. When
+/// the guard is dropped, including while unwinding or when returning early
+/// with `?`, ownership of the handle is released as with
+/// [`Connection::into_raw`].
+///
+/// See [`Connection::from_raw_borrowed`].
+pub struct BorrowedConnection<'a> {
+    connection: ManuallyDrop<Connection>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> BorrowedConnection<'a> {
+    #[inline]
+    pub(crate) fn new(connection: Connection) -> Self {
+        Self {
+            connection: ManuallyDrop::new(connection),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Deref for BorrowedConnection<'_> {
+    type Target = Connection;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+impl Drop for BorrowedConnection<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The connection is never used again after being taken.
+        let connection = unsafe { ManuallyDrop::take(&mut self.connection) };
+        connection.into_raw();
+    }
+}
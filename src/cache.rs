@@ -0,0 +1,125 @@
+use core::cell::RefCell;
+use core::ops::{Deref, DerefMut};
+use std::collections::VecDeque;
+
+use crate::statement::Statement;
+
+/// The default number of statements retained by the statement cache.
+pub(crate) const DEFAULT_CAPACITY: usize = 16;
+
+/// A least-recently-used cache of prepared statements keyed by their SQL.
+pub(crate) struct StatementCache {
+    capacity: usize,
+    /// Cached entries, with the most recently used entry at the back.
+    entries: VecDeque<(Box<str>, Statement)>,
+}
+
+impl StatementCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Remove the statement associated with the given SQL from the cache.
+    pub(crate) fn take(&mut self, sql: &str) -> Option<(Box<str>, Statement)> {
+        let index = self.entries.iter().position(|(key, _)| **key == *sql)?;
+        self.entries.remove(index)
+    }
+
+    /// Insert a statement as the most recently used entry, evicting the least
+    /// recently used entries in excess of the capacity.
+    pub(crate) fn insert(&mut self, sql: Box<str>, statement: Statement) {
+        self.entries.push_back((sql, statement));
+        self.evict();
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// A prepared statement borrowed from the statement cache of a connection.
+///
+/// The statement is reset, has its bindings and any budget of operations
+/// cleared and is returned to the cache when this guard is dropped.
+///
+/// See [`Connection::prepare_cached`].
+///
+/// [`Connection::prepare_cached`]: crate::Connection::prepare_cached
+pub struct CachedStatement<'a> {
+    cache: &'a RefCell<StatementCache>,
+    entry: Option<(Box<str>, Statement)>,
+}
+
+impl<'a> CachedStatement<'a> {
+    #[inline]
+    pub(crate) fn new(
+        cache: &'a RefCell<StatementCache>,
+        sql: Box<str>,
+        statement: Statement,
+    ) -> Self {
+        Self {
+            cache,
+            entry: Some((sql, statement)),
+        }
+    }
+
+    /// Finalize the statement instead of returning it to the cache.
+    pub(crate) fn discard(mut self) {
+        self.entry = None;
+    }
+}
+
+impl Deref for CachedStatement<'_> {
+    type Target = Statement;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.entry {
+            Some((_, statement)) => statement,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl DerefMut for CachedStatement<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.entry {
+            Some((_, statement)) => statement,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl Drop for CachedStatement<'_> {
+    fn drop(&mut self) {
+        let (sql, mut statement) = match self.entry.take() {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        if statement.reset().is_err() || statement.clear_bindings().is_err() {
+            return;
+        }
+
+        statement.remove_op_budget();
+
+        if let Ok(mut cache) = self.cache.try_borrow_mut() {
+            cache.insert(sql, statement);
+        }
+    }
+}
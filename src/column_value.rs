@@ -0,0 +1,239 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use libc::c_int;
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Statement};
+use crate::value::{Type, Value};
+
+/// A protected value of a column in the current row of a [`Statement`].
+///
+/// This wraps the `sqlite3_value` returned by `sqlite3_column_value`, which
+/// can be bound to another statement as-is through [`Bindable`] without
+/// converting it through a Rust type first. It can't outlive the borrow of the
+/// statement it was read from, since SQLite frees it when the statement is
+/// stepped or reset. Use [`ColumnValue::dup`] to get a copy which can be kept
+/// around.
+///
+/// See [`Statement::column_value`].
+pub struct ColumnValue<'stmt> {
+    raw: NonNull<ffi::sqlite3_value>,
+    _marker: PhantomData<&'stmt Statement>,
+}
+
+impl<'stmt> ColumnValue<'stmt> {
+    /// Construct a new column value.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be a protected value which lives for `'stmt`.
+    pub(crate) unsafe fn new(raw: NonNull<ffi::sqlite3_value>) -> Self {
+        Self {
+            raw,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the type of the value.
+    #[inline]
+    pub fn value_type(&self) -> Type {
+        value_type(self.raw)
+    }
+
+    /// Convert into a dynamic [`Value`].
+    #[inline]
+    pub fn to_value(&self) -> Result<Value> {
+        to_value(self.raw)
+    }
+
+    /// Copy the value using `sqlite3_value_dup`, so that it can be kept after
+    /// the statement it was read from has been stepped or reset.
+    pub fn dup(&self) -> Result<OwnedValue> {
+        OwnedValue::dup(self.raw)
+    }
+
+    /// Access the underlying protected `sqlite3_value`.
+    ///
+    /// The pointer is valid for as long as this value is.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut ffi::sqlite3_value {
+        self.raw.as_ptr()
+    }
+}
+
+/// An owned copy of an `sqlite3_value`, created through `sqlite3_value_dup`
+/// and freed with `sqlite3_value_free` when dropped.
+///
+/// This can be bound to any statement through [`Bindable`].
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{State, Type, Value};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE copies (data)")?;
+///
+/// let mut select = c.prepare("SELECT X'0102'")?;
+/// assert_eq!(select.step()?, State::Row);
+/// let value = select.column_value(0).dup()?;
+/// select.reset()?;
+///
+/// assert_eq!(value.value_type(), Type::Blob);
+///
+/// let mut insert = c.prepare("INSERT INTO copies VALUES (?)")?;
+/// insert.bind(1, &value)?;
+/// assert_eq!(insert.step()?, State::Done);
+///
+/// let mut select = c.prepare("SELECT data FROM copies")?;
+/// assert_eq!(select.step()?, State::Row);
+/// assert_eq!(select.read::<Value>(0)?, Value::Blob(vec![1, 2]));
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub struct OwnedValue {
+    raw: NonNull<ffi::sqlite3_value>,
+}
+
+// SAFETY: Values created through `sqlite3_value_dup` aren't associated with
+// any connection, so they can be moved across threads.
+unsafe impl Send for OwnedValue {}
+
+impl OwnedValue {
+    fn dup(raw: NonNull<ffi::sqlite3_value>) -> Result<Self> {
+        let raw = unsafe { ffi::sqlite3_value_dup(raw.as_ptr()) };
+
+        match NonNull::new(raw) {
+            Some(raw) => Ok(Self { raw }),
+            None => Err(Error::from_code(ffi::SQLITE_NOMEM)),
+        }
+    }
+
+    /// Return the type of the value.
+    #[inline]
+    pub fn value_type(&self) -> Type {
+        value_type(self.raw)
+    }
+
+    /// Convert into a dynamic [`Value`].
+    #[inline]
+    pub fn to_value(&self) -> Result<Value> {
+        to_value(self.raw)
+    }
+
+    /// Copy the value using `sqlite3_value_dup`.
+    pub fn try_clone(&self) -> Result<Self> {
+        Self::dup(self.raw)
+    }
+
+    /// Access the underlying `sqlite3_value`.
+    ///
+    /// The pointer is valid for as long as this value is.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut ffi::sqlite3_value {
+        self.raw.as_ptr()
+    }
+}
+
+impl Drop for OwnedValue {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_value_free(self.raw.as_ptr()) };
+    }
+}
+
+impl Bindable for &ColumnValue<'_> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        bind_value(statement, i, self.raw)
+    }
+}
+
+impl Bindable for ColumnValue<'_> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        bind_value(statement, i, self.raw)
+    }
+}
+
+impl Bindable for &OwnedValue {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        bind_value(statement, i, self.raw)
+    }
+}
+
+impl Bindable for OwnedValue {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        bind_value(statement, i, self.raw)
+    }
+}
+
+fn bind_value(
+    statement: &mut Statement,
+    i: usize,
+    value: NonNull<ffi::sqlite3_value>,
+) -> Result<()> {
+    debug_assert!(i > 0, "the indexing starts from 1");
+
+    unsafe {
+        let raw = statement.as_raw();
+
+        sqlite3_try! {
+            ffi::sqlite3_db_handle(raw),
+            ffi::sqlite3_bind_value(raw, i as c_int, value.as_ptr())
+        };
+    }
+
+    Ok(())
+}
+
+fn value_type(raw: NonNull<ffi::sqlite3_value>) -> Type {
+    match unsafe { ffi::sqlite3_value_type(raw.as_ptr()) } {
+        ffi::SQLITE_BLOB => Type::Blob,
+        ffi::SQLITE_FLOAT => Type::Float,
+        ffi::SQLITE_INTEGER => Type::Integer,
+        ffi::SQLITE_TEXT => Type::Text,
+        ffi::SQLITE_NULL => Type::Null,
+        _ => unreachable!(),
+    }
+}
+
+fn to_value(raw: NonNull<ffi::sqlite3_value>) -> Result<Value> {
+    let raw = raw.as_ptr();
+
+    unsafe {
+        Ok(match value_type(NonNull::new_unchecked(raw)) {
+            Type::Blob => {
+                let pointer = ffi::sqlite3_value_blob(raw);
+                let len = ffi::sqlite3_value_bytes(raw) as usize;
+
+                if pointer.is_null() {
+                    Value::Blob(Vec::new())
+                } else {
+                    Value::Blob(std::slice::from_raw_parts(pointer as *const u8, len).to_vec())
+                }
+            }
+            Type::Float => Value::Float(ffi::sqlite3_value_double(raw)),
+            Type::Integer => Value::Integer(ffi::sqlite3_value_int64(raw)),
+            Type::Text => {
+                let pointer = ffi::sqlite3_value_text(raw);
+                let len = ffi::sqlite3_value_bytes(raw) as usize;
+
+                if pointer.is_null() {
+                    Value::Text(String::new())
+                } else {
+                    let bytes = std::slice::from_raw_parts(pointer, len);
+
+                    match std::str::from_utf8(bytes) {
+                        Ok(text) => Value::Text(text.to_owned()),
+                        Err(..) => return Err(Error::from_code(ffi::SQLITE_MISUSE)),
+                    }
+                }
+            }
+            Type::Null => Value::Null,
+        })
+    }
+}
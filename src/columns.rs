@@ -0,0 +1,162 @@
+use core::slice;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A snapshot of the column metadata of a statement, returned by
+/// [`Statement::columns`].
+///
+/// The snapshot is built once and can be cheaply cloned and reused across
+/// rows and resets, so that mapping rows doesn't require looking up column
+/// names through SQLite every time. Strings are interned, so declared types
+/// which are shared by multiple columns are only stored once.
+///
+/// [`Statement::columns`]: crate::Statement::columns
+///
+/// # Examples
+///
+/// ```
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, email TEXT, age INTEGER)")?;
+///
+/// let stmt = c.prepare("SELECT name, email, age + 1 AS next FROM users")?;
+/// let columns = stmt.columns()?;
+///
+/// assert_eq!(columns.len(), 3);
+/// assert_eq!(columns.names().collect::<Vec<_>>(), ["name", "email", "next"]);
+/// assert_eq!(columns.index("email"), Some(1));
+///
+/// let column = &columns[1];
+/// assert_eq!(column.name(), "email");
+/// assert_eq!(column.decltype(), Some("TEXT"));
+/// assert_eq!(columns[2].decltype(), None);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Columns {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    columns: Vec<Column>,
+    index: HashMap<Arc<str>, usize>,
+}
+
+impl Columns {
+    /// Build a snapshot from an iterator of names and declared types.
+    pub(crate) fn new<'a, I>(iter: I) -> Self
+    where
+        I: ExactSizeIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        let mut columns = Vec::with_capacity(iter.len());
+        let mut index = HashMap::with_capacity(iter.len());
+        let mut interned = HashMap::<&str, Arc<str>>::new();
+
+        for (i, (name, decltype)) in iter.enumerate() {
+            let name = Arc::<str>::from(name);
+            index.entry(name.clone()).or_insert(i);
+
+            let decltype = decltype.map(|decltype| {
+                interned
+                    .entry(decltype)
+                    .or_insert_with(|| Arc::from(decltype))
+                    .clone()
+            });
+
+            columns.push(Column { name, decltype });
+        }
+
+        Self {
+            inner: Arc::new(Inner { columns, index }),
+        }
+    }
+
+    /// Return the number of columns.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.columns.len()
+    }
+
+    /// Test if there are no columns.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.columns.is_empty()
+    }
+
+    /// Get the column at the given index.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&Column> {
+        self.inner.columns.get(i)
+    }
+
+    /// Return the index of the column with the given name if it exists.
+    ///
+    /// If multiple columns have the same name, the index of the first one is
+    /// returned.
+    #[inline]
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.inner.index.get(name).copied()
+    }
+
+    /// Iterate over the columns.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Column> {
+        self.inner.columns.iter()
+    }
+
+    /// Iterate over the names of the columns.
+    #[inline]
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.iter().map(Column::name)
+    }
+}
+
+impl core::ops::Index<usize> for Columns {
+    type Output = Column;
+
+    #[inline]
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.inner.columns[i]
+    }
+}
+
+impl<'a> IntoIterator for &'a Columns {
+    type Item = &'a Column;
+    type IntoIter = slice::Iter<'a, Column>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for Columns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// The metadata of a single column in [`Columns`].
+#[derive(Debug, Clone)]
+pub struct Column {
+    name: Arc<str>,
+    decltype: Option<Arc<str>>,
+}
+
+impl Column {
+    /// The name of the column.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The declared type of the column, or `None` if it is an expression.
+    ///
+    /// See [`Statement::column_decltype`].
+    ///
+    /// [`Statement::column_decltype`]: crate::Statement::column_decltype
+    #[inline]
+    pub fn decltype(&self) -> Option<&str> {
+        self.decltype.as_deref()
+    }
+}
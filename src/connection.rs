@@ -1,18 +1,37 @@
-use std::mem::MaybeUninit;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::mem::{ManuallyDrop, MaybeUninit};
 use std::path::Path;
 use std::ptr;
 use std::ptr::NonNull;
 
-use crate::error::{Error, Result};
-use crate::statement::Statement;
+use crate::borrowed::{BorrowedConnection, BorrowedStatement};
+use crate::cache::{CachedStatement, StatementCache};
+use crate::db_mutex::DbMutexGuard;
+use crate::error::{Error, ErrorKind, Result};
+use crate::function;
+use crate::like;
+use crate::observer::{self, Event, Observer, ObserverCell};
+use crate::params::Params;
+use crate::row::FromRow;
+use crate::statement::{Readable, State, Statement};
+use crate::sys;
 use crate::utils;
-use libc::{c_char, c_int, c_void};
+use crate::value::{Type, Value};
+use libc::{c_int, c_void};
 use sqlite3_sys as ffi;
 
+/// A callback rewriting SQL before it is prepared.
+type SqlInterceptor = dyn FnMut(&str) -> Cow<'_, str> + Send;
+
 /// A SQLite database connection.
 pub struct Connection {
     raw: NonNull<ffi::sqlite3>,
     busy_callback: Option<Box<dyn FnMut(usize) -> bool>>,
+    sql_interceptor: RefCell<Option<Box<SqlInterceptor>>>,
+    observer: Option<Box<ObserverCell>>,
+    cache: RefCell<StatementCache>,
 }
 
 /// Connection is `Send`.
@@ -27,26 +46,49 @@ impl Connection {
         OpenOptions::new().set_create().set_read_write().open(path)
     }
 
+    /// Open a read-write connection to a named in-memory database which is
+    /// shared by all connections in the process opened with the same name.
+    ///
+    /// This opens the URI `file:<name>?mode=memory&cache=shared`, and the
+    /// database is deleted once the last connection to it has been closed. See
+    /// [`OpenOptions::open_shared_memory`] to customize the options used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Connection;
+    ///
+    /// let a = Connection::open_shared_memory("open_shared_memory_example")?;
+    /// let b = Connection::open_shared_memory("open_shared_memory_example")?;
+    ///
+    /// a.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice')")?;
+    /// let name = b.query_value::<String, _>("SELECT name FROM users", ())?;
+    /// assert_eq!(name, "Alice");
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn open_shared_memory<T>(name: T) -> Result<Connection>
+    where
+        T: AsRef<str>,
+    {
+        OpenOptions::new()
+            .set_create()
+            .set_read_write()
+            .open_shared_memory(name)
+    }
+
     /// Execute a statement without processing the resulting rows if any.
+    ///
+    /// The statement may consist of multiple statements separated by
+    /// semicolons, which are executed in order.
     #[inline]
     pub fn execute<T>(&self, statement: T) -> Result<()>
     where
         T: AsRef<str>,
     {
-        unsafe {
-            sqlite3_try! {
-                self.raw.as_ptr(),
-                ffi::sqlite3_exec(
-                    self.raw.as_ptr(),
-                    utils::string_to_cstring(statement.as_ref())?.as_ptr(),
-                    None,
-                    ptr::null_mut(),
-                    ptr::null_mut(),
-                )
-            };
-        }
-
-        Ok(())
+        self.observe(execute(
+            self.raw.as_ptr(),
+            &self.intercept(statement.as_ref()),
+        ))
     }
 
     /// Execute a statement and process the resulting rows as plain text.
@@ -60,17 +102,82 @@ impl Connection {
         F: FnMut(&[(&str, Option<&str>)]) -> bool,
         T: AsRef<str>,
     {
-        unsafe {
-            sqlite3_try! {
-                self.raw.as_ptr(),
-                ffi::sqlite3_exec(
-                    self.raw.as_ptr(),
-                    utils::string_to_cstring(statement.as_ref())?.as_ptr(),
-                    Some(process_callback::<F>),
-                    &mut callback as *mut F as *mut _,
-                    ptr::null_mut(),
-                )
-            };
+        self.try_iterate(statement, |pairs| Ok::<_, Error>(callback(pairs)))
+    }
+
+    /// Execute a statement and process the resulting rows as plain text with a
+    /// fallible callback.
+    ///
+    /// This behaves like [`iterate`], except that the callback returns a
+    /// `Result`. If the callback returns an error, no more rows will be
+    /// processed and the error is returned from this function.
+    ///
+    /// [`iterate`]: Connection::iterate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::ParseIntError;
+    ///
+    /// #[derive(Debug)]
+    /// enum Error {
+    ///     Sqlite(sqlite_ll::Error),
+    ///     Parse(ParseIntError),
+    /// }
+    ///
+    /// impl From<sqlite_ll::Error> for Error {
+    ///     fn from(error: sqlite_ll::Error) -> Self {
+    ///         Error::Sqlite(error)
+    ///     }
+    /// }
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (age TEXT); INSERT INTO users VALUES ('42'), ('old');")?;
+    ///
+    /// let mut ages = Vec::new();
+    ///
+    /// let result = c.try_iterate("SELECT age FROM users", |pairs| {
+    ///     let age = pairs[0].1.unwrap_or_default();
+    ///     ages.push(age.parse::<u32>().map_err(Error::Parse)?);
+    ///     Ok::<_, Error>(true)
+    /// });
+    ///
+    /// assert!(matches!(result, Err(Error::Parse(..))));
+    /// assert_eq!(ages, [42]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn try_iterate<T, F, E>(&self, statement: T, mut callback: F) -> Result<(), E>
+    where
+        F: FnMut(&[(&str, Option<&str>)]) -> Result<bool, E>,
+        E: From<Error>,
+        T: AsRef<str>,
+    {
+        let statement = self.intercept(statement.as_ref());
+        let mut rest = statement.as_ref();
+
+        while let (Some(mut statement), tail) = Statement::prepare_next(self.raw.as_ptr(), rest)? {
+            let columns = (0..statement.column_count())
+                .map(|i| Ok(statement.column_name(i)?.to_owned()))
+                .collect::<Result<Vec<_>>>()?;
+
+            while let State::Row = statement.step()? {
+                let mut pairs = Vec::with_capacity(columns.len());
+
+                for (i, column) in columns.iter().enumerate() {
+                    let value = match statement.column_type(i) {
+                        Type::Null => None,
+                        _ => Some(statement.column_text(i)?),
+                    };
+
+                    pairs.push((column.as_str(), value));
+                }
+
+                if !callback(&pairs)? {
+                    return Ok(());
+                }
+            }
+
+            rest = tail;
         }
 
         Ok(())
@@ -79,13 +186,429 @@ impl Connection {
     /// Create a prepared statement.
     ///
     /// The database connection will be kept open for the lifetime of this
-    /// statement.
+    /// statement, so the statement may outlive the connection it was
+    /// prepared from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT 42")?;
+    /// drop(c);
+    ///
+    /// assert_eq!(stmt.step()?, State::Row);
+    /// assert_eq!(stmt.read::<i64>(0)?, 42);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
     #[inline]
     pub fn prepare<T>(&self, statement: T) -> Result<Statement>
     where
         T: AsRef<str>,
     {
-        Statement::new(self.raw.as_ptr(), statement)
+        self.observe(self.prepare_unobserved(statement.as_ref()))
+    }
+
+    /// Prepare a statement without reporting errors to the observer.
+    fn prepare_unobserved(&self, statement: &str) -> Result<Statement> {
+        Statement::new(self.raw.as_ptr(), self.intercept(statement))
+    }
+
+    /// Create a prepared statement which borrows the connection.
+    ///
+    /// This behaves like [`prepare`], except that the returned statement
+    /// cannot outlive the borrow of the connection. Use it for statements
+    /// which are only used within the same scope as the connection.
+    ///
+    /// [`prepare`]: Connection::prepare
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare_borrowed("SELECT 42")?;
+    ///
+    /// assert_eq!(stmt.step()?, State::Row);
+    /// assert_eq!(stmt.read::<i64>(0)?, 42);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    ///
+    /// The statement cannot be used after the connection has been dropped:
+    ///
+    /// ```compile_fail
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare_borrowed("SELECT 42")?;
+    /// drop(c);
+    /// stmt.step()?;
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn prepare_borrowed<T>(&self, statement: T) -> Result<BorrowedStatement<'_>>
+    where
+        T: AsRef<str>,
+    {
+        Ok(BorrowedStatement::new(self.prepare(statement)?))
+    }
+
+    /// Prepare a statement through the statement cache of the connection.
+    ///
+    /// If a statement with the same SQL has previously been prepared through
+    /// this function and is still cached, it is reused instead of being
+    /// prepared again. Otherwise the statement is prepared with the hint that
+    /// it will be retained and reused many times.
+    ///
+    /// The returned guard dereferences to [`Statement`]. When it is dropped,
+    /// the statement is reset, has its bindings and any budget set through
+    /// [`Statement::set_op_budget`] cleared, and is returned to the cache. The cache retains up to 16 statements by default, evicting
+    /// the least recently used ones, see [`set_statement_cache_capacity`].
+    ///
+    /// [`set_statement_cache_capacity`]: Connection::set_statement_cache_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+    ///
+    /// for (name, age) in [("Alice", 42), ("Bob", 69)] {
+    ///     let mut stmt = c.prepare_cached("INSERT INTO users VALUES (?, ?)")?;
+    ///     stmt.execute_batch([&[Value::Text(name.into()), Value::Integer(age)]])?;
+    /// }
+    ///
+    /// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?, 2);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn prepare_cached<T>(&self, statement: T) -> Result<CachedStatement<'_>>
+    where
+        T: AsRef<str>,
+    {
+        let sql = statement.as_ref();
+
+        let cached = self.cache.borrow_mut().take(sql);
+
+        let (sql, statement) = match cached {
+            Some(entry) => entry,
+            None => (sql.into(), self.observe(self.prepare_persistent(sql))?),
+        };
+
+        Ok(CachedStatement::new(&self.cache, sql, statement))
+    }
+
+    /// Prepare a statement with the hint that it will be retained and reused
+    /// many times.
+    pub(crate) fn prepare_persistent(&self, statement: &str) -> Result<Statement> {
+        Statement::new_with_flags(
+            self.raw.as_ptr(),
+            &self.intercept(statement),
+            sys::SQLITE_PREPARE_PERSISTENT,
+        )
+    }
+
+    /// Report an error to the observer if one is set.
+    pub(crate) fn observe<T>(&self, result: Result<T>) -> Result<T> {
+        if let (Err(error), Some(observer)) = (&result, &self.observer) {
+            observer::emit(observer, Event::Error(error));
+        }
+
+        result
+    }
+
+    /// Apply the SQL interceptor if one is set.
+    fn intercept<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        match &mut *self.sql_interceptor.borrow_mut() {
+            Some(interceptor) => interceptor(sql),
+            None => Cow::Borrowed(sql),
+        }
+    }
+
+    /// Set the maximum number of statements retained by the statement cache
+    /// used by [`prepare_cached`], evicting the least recently used
+    /// statements in excess of it.
+    ///
+    /// Setting the capacity to zero disables caching.
+    ///
+    /// [`prepare_cached`]: Connection::prepare_cached
+    pub fn set_statement_cache_capacity(&self, capacity: usize) {
+        self.cache.borrow_mut().set_capacity(capacity);
+    }
+
+    /// Finalize all statements retained by the statement cache used by
+    /// [`prepare_cached`].
+    ///
+    /// [`prepare_cached`]: Connection::prepare_cached
+    pub fn clear_statement_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row, and decode that row into `T` through [`FromRow`].
+    ///
+    /// An error of kind [`ErrorKind::NoRows`] is returned if the statement
+    /// doesn't produce any rows, and an error of kind
+    /// [`ErrorKind::TooManyRows`] if it produces more than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{ErrorKind, Value};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let (name, age) = c.query_row::<(String, i64), _>("SELECT name, age FROM users WHERE age > ?", &[Value::Integer(50)])?;
+    /// assert_eq!((name.as_str(), age), ("Bob", 69));
+    ///
+    /// let e = c.query_row::<(String,), _>("SELECT name FROM users WHERE age > 100", ()).unwrap_err();
+    /// assert_eq!(e.kind(), ErrorKind::NoRows);
+    ///
+    /// let e = c.query_row::<(String,), _>("SELECT name FROM users", ()).unwrap_err();
+    /// assert_eq!(e.kind(), ErrorKind::TooManyRows);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn query_row<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        self.observe((|| {
+            let mut statement = self.prepare_unobserved(statement.as_ref())?;
+            query_one(&mut statement, params)
+        })())
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row, and read its first column as `T`.
+    ///
+    /// This returns the same errors as [`Connection::query_row`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let count = c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?;
+    /// assert_eq!(count, 2);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn query_value<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: Readable,
+        P: Params,
+    {
+        let (value,) = self.query_row::<(T,), P>(statement, params)?;
+        Ok(value)
+    }
+
+    /// Insert rows of values into the given columns of a table, using
+    /// multi-row `INSERT INTO table (columns) VALUES (?, ?), (?, ?), ..`
+    /// statements.
+    ///
+    /// Each row must contain exactly one value per column. Rows are inserted
+    /// in chunks which are as large as the `SQLITE_LIMIT_VARIABLE_NUMBER`
+    /// limit of the connection permits, and if the connection isn't already
+    /// inside of a transaction all chunks are inserted in a single
+    /// transaction.
+    ///
+    /// Returns the number of rows inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+    ///
+    /// let rows = [
+    ///     [Value::Text(String::from("Alice")), Value::Integer(42)],
+    ///     [Value::Text(String::from("Bob")), Value::Integer(69)],
+    /// ];
+    ///
+    /// assert_eq!(c.insert_rows("users", &["name", "age"], &rows)?, 2);
+    /// assert_eq!(c.query_value::<i64, _>("SELECT SUM(age) FROM users", ())?, 111);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn insert_rows<R>(&self, table: &str, columns: &[&str], rows: &[R]) -> Result<usize>
+    where
+        R: AsRef<[Value]>,
+    {
+        if columns.is_empty() {
+            return Err(Error::custom("at least one column must be specified"));
+        }
+
+        for (n, row) in rows.iter().enumerate() {
+            let len = row.as_ref().len();
+
+            if len != columns.len() {
+                return Err(Error::custom(format_args!(
+                    "row {n} has {len} values but {} columns were specified",
+                    columns.len()
+                )));
+            }
+        }
+
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let limit =
+            unsafe { ffi::sqlite3_limit(self.raw.as_ptr(), ffi::SQLITE_LIMIT_VARIABLE_NUMBER, -1) };
+        let per_chunk = limit.max(0) as usize / columns.len();
+
+        if per_chunk == 0 {
+            return Err(Error::custom(format_args!(
+                "{} columns exceed the limit of {limit} variables per statement",
+                columns.len()
+            )));
+        }
+
+        let mut prefix = String::from("INSERT INTO ");
+        utils::push_identifier(&mut prefix, table);
+        prefix.push_str(" (");
+
+        for (n, column) in columns.iter().enumerate() {
+            if n > 0 {
+                prefix.push_str(", ");
+            }
+
+            utils::push_identifier(&mut prefix, column);
+        }
+
+        prefix.push_str(") VALUES ");
+
+        let build = |count: usize| {
+            let mut sql = prefix.clone();
+
+            for n in 0..count {
+                if n > 0 {
+                    sql.push_str(", ");
+                }
+
+                sql.push('(');
+
+                for c in 0..columns.len() {
+                    if c > 0 {
+                        sql.push_str(", ");
+                    }
+
+                    sql.push('?');
+                }
+
+                sql.push(')');
+            }
+
+            self.prepare(sql)
+        };
+
+        let owned = self.is_autocommit();
+
+        if owned {
+            self.execute("BEGIN")?;
+        }
+
+        let result = (|| {
+            let mut full = None;
+
+            for chunk in rows.chunks(per_chunk) {
+                let mut partial;
+
+                let statement = if chunk.len() == per_chunk {
+                    match &mut full {
+                        Some(statement) => statement,
+                        full => full.insert(build(per_chunk)?),
+                    }
+                } else {
+                    partial = build(chunk.len())?;
+                    &mut partial
+                };
+
+                statement.reset()?;
+
+                for (n, value) in chunk.iter().flat_map(|row| row.as_ref()).enumerate() {
+                    statement.bind(n + 1, value)?;
+                }
+
+                while let State::Row = statement.step()? {}
+            }
+
+            Ok(())
+        })();
+
+        if owned {
+            commit_or_rollback(self.raw.as_ptr(), result)?;
+        } else {
+            result?;
+        }
+
+        Ok(rows.len())
+    }
+
+    /// Return the SQL of every statement which is currently prepared against
+    /// the connection and hasn't been finalized.
+    ///
+    /// This includes statements retained by the statement cache used by
+    /// [`prepare_cached`]. It's useful for tracking down leaked statements
+    /// which keep the database open.
+    ///
+    /// [`prepare_cached`]: Connection::prepare_cached
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let stmt = c.prepare("SELECT 1")?;
+    /// assert_eq!(c.statements(), ["SELECT 1"]);
+    /// drop(stmt);
+    /// assert!(c.statements().is_empty());
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+
+        unsafe {
+            let mut stmt = ffi::sqlite3_next_stmt(self.raw.as_ptr(), ptr::null_mut());
+
+            while !stmt.is_null() {
+                let sql = ffi::sqlite3_sql(stmt);
+
+                if !sql.is_null() {
+                    statements.push(CStr::from_ptr(sql).to_string_lossy().into_owned());
+                }
+
+                stmt = ffi::sqlite3_next_stmt(self.raw.as_ptr(), stmt);
+            }
+        }
+
+        statements
+    }
+
+    /// Finalize every statement which is currently prepared against the
+    /// connection, including the ones retained by the statement cache.
+    ///
+    /// # Safety
+    ///
+    /// Any [`Statement`] prepared from this connection which is still alive
+    /// refers to a finalized statement after this has been called. The caller
+    /// must ensure that no such statement is used or dropped afterwards, for
+    /// example by leaking them with [`std::mem::forget`].
+    pub unsafe fn finalize_all(&self) {
+        self.cache.borrow_mut().clear();
+
+        loop {
+            let stmt = ffi::sqlite3_next_stmt(self.raw.as_ptr(), ptr::null_mut());
+
+            if stmt.is_null() {
+                break;
+            }
+
+            ffi::sqlite3_finalize(stmt);
+        }
     }
 
     /// Return the number of rows inserted, updated, or deleted by the most
@@ -102,6 +625,330 @@ impl Connection {
         unsafe { ffi::sqlite3_total_changes(self.raw.as_ptr()) as usize }
     }
 
+    /// Test if the connection is in autocommit mode, which is the case unless
+    /// a transaction has been started with `BEGIN` and not yet ended.
+    ///
+    /// This can be used to verify that no transaction was left open before
+    /// reusing a connection, such as when returning it to a pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// assert!(c.is_autocommit());
+    ///
+    /// c.execute("BEGIN")?;
+    /// assert!(!c.is_autocommit());
+    ///
+    /// c.execute("COMMIT")?;
+    /// assert!(c.is_autocommit());
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn is_autocommit(&self) -> bool {
+        unsafe { ffi::sqlite3_get_autocommit(self.raw.as_ptr()) != 0 }
+    }
+
+    /// Test if an interrupt is pending on the connection, using
+    /// `sqlite3_is_interrupted`.
+    ///
+    /// An interrupt is pending if `sqlite3_interrupt` has been called, such as
+    /// through a raw handle on another thread, and remains so until no
+    /// statements are running. This allows long-running processing of rows in
+    /// Rust, which is interleaved with stepping a statement, to stop early
+    /// instead of only finding out once the next step fails with
+    /// [`Code::INTERRUPT`].
+    ///
+    /// This requires the `is_interrupted` feature and SQLite 3.41.0 or later.
+    /// Enabling the feature with an older library causes linking to fail.
+    ///
+    /// [`Code::INTERRUPT`]: crate::Code::INTERRUPT
+    #[cfg(feature = "is_interrupted")]
+    #[inline]
+    pub fn is_interrupted(&self) -> bool {
+        unsafe { sys::sqlite3_is_interrupted(self.raw.as_ptr()) != 0 }
+    }
+
+    /// Acquire the recursive mutex of the connection using
+    /// `sqlite3_db_mutex`, or return `None` if the connection doesn't have one
+    /// because it isn't in the serialized threading mode.
+    ///
+    /// While the returned guard is held, no other thread can use the
+    /// connection or any statements prepared from it. This is useful when
+    /// statements prepared from the same connection are used from multiple
+    /// threads, and a compound operation must not be interleaved with them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::OpenOptions;
+    ///
+    /// let c = OpenOptions::new().set_create().set_read_write().set_full_mutex().open(":memory:")?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER)")?;
+    ///
+    /// let guard = c.db_mutex().expect("connection is serialized");
+    /// c.execute("INSERT INTO numbers VALUES (1), (2)")?;
+    /// assert_eq!(c.change_count(), 2);
+    /// drop(guard);
+    ///
+    /// let c = OpenOptions::new().set_create().set_read_write().set_no_mutex().open(":memory:")?;
+    /// assert!(c.db_mutex().is_none());
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn db_mutex(&self) -> Option<DbMutexGuard<'_>> {
+        let mutex = NonNull::new(unsafe { ffi::sqlite3_db_mutex(self.raw.as_ptr()) })?;
+        // SAFETY: The mutex is owned by the connection, which outlives the
+        // guard.
+        Some(unsafe { DbMutexGuard::enter(mutex) })
+    }
+
+    /// Access the raw database handle.
+    ///
+    /// This can be used to call SQLite functions which this crate doesn't
+    /// wrap. The handle must not be closed, and any callbacks installed by
+    /// this crate such as the busy handler should be left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::ffi;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1);")?;
+    ///
+    /// let rowid = unsafe { ffi::sqlite3_last_insert_rowid(c.as_raw()) };
+    /// assert_eq!(rowid, 1);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_raw(&self) -> *mut ffi::sqlite3 {
+        self.raw.as_ptr()
+    }
+
+    /// Release ownership of the raw database handle without closing it.
+    ///
+    /// Cached statements are finalized and the callbacks installed through
+    /// this connection, such as the busy handler and observer, are removed
+    /// since they are owned by it. Other statements prepared through the
+    /// connection remain valid. The caller is responsible for eventually
+    /// closing the handle with `sqlite3_close`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{ffi, Connection};
+    ///
+    /// let c = Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER)")?;
+    ///
+    /// let raw = c.into_raw();
+    /// let c = unsafe { Connection::from_raw(raw) };
+    /// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?, 0);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn into_raw(mut self) -> *mut ffi::sqlite3 {
+        self.clear_statement_cache();
+
+        if self.busy_callback.is_some() {
+            _ = self.remove_busy_handler();
+        }
+
+        self.remove_observer();
+
+        let raw = self.raw.as_ptr();
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: Ownership of the handle is released, so the remaining
+        // fields are dropped without running the destructor of the
+        // connection.
+        unsafe {
+            ptr::drop_in_place(&mut this.busy_callback);
+            ptr::drop_in_place(&mut this.cache);
+            ptr::drop_in_place(&mut this.sql_interceptor);
+            ptr::drop_in_place(&mut this.observer);
+        }
+
+        raw
+    }
+
+    /// Construct a connection from a raw database handle, taking ownership of
+    /// it.
+    ///
+    /// This allows a handle opened by other code, such as a host application
+    /// handing a handle to a plugin, to be used through this crate. The handle
+    /// is closed when the connection is dropped, so if it is still owned by
+    /// someone else ownership should be released with
+    /// [`Connection::into_raw`] once done.
+    ///
+    /// Note that dropping the connection or calling
+    /// [`Connection::remove_busy_handler`] removes any busy handler installed
+    /// on the handle, including ones installed by other code.
+    ///
+    /// # Safety
+    ///
+    /// The handle must be a valid, non-null database handle which is not
+    /// closed while the connection is in use. Unless ownership is released
+    /// through [`Connection::into_raw`], nothing else may close it.
+    #[inline]
+    pub unsafe fn from_raw(raw: *mut ffi::sqlite3) -> Connection {
+        Connection {
+            raw: NonNull::new_unchecked(raw),
+            busy_callback: None,
+            sql_interceptor: RefCell::new(None),
+            observer: None,
+            cache: RefCell::new(StatementCache::new()),
+        }
+    }
+
+    /// Construct a connection from a raw database handle which is owned by
+    /// someone else, without taking ownership of it.
+    ///
+    /// The returned guard dereferences to [`Connection`] and releases the
+    /// handle again when dropped instead of closing it, even if an error is
+    /// returned or a panic unwinds while it's in use. Like
+    /// [`Connection::into_raw`], this removes any busy handler installed on
+    /// the handle if one was installed through the guard.
+    ///
+    /// # Safety
+    ///
+    /// The handle must be a valid, non-null database handle which stays open
+    /// for the lifetime `'a` and isn't used by anything else while the guard
+    /// is in use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Connection;
+    ///
+    /// let owner = Connection::open(":memory:")?;
+    /// owner.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1);")?;
+    ///
+    /// {
+    ///     let c = unsafe { Connection::from_raw_borrowed(owner.as_raw()) };
+    ///     assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?, 1);
+    ///     assert!(c.query_value::<i64, _>("SELECT COUNT(*) FROM missing", ()).is_err());
+    /// }
+    ///
+    /// assert_eq!(owner.query_value::<i64, _>("SELECT n FROM numbers", ())?, 1);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub unsafe fn from_raw_borrowed<'a>(raw: *mut ffi::sqlite3) -> BorrowedConnection<'a> {
+        BorrowedConnection::new(Connection::from_raw(raw))
+    }
+
+    /// Set a callback which rewrites SQL before it is prepared or executed.
+    ///
+    /// The interceptor is applied to all SQL passed to this connection, such
+    /// as through [`prepare`], [`prepare_cached`] and [`execute`], which allows
+    /// testing frameworks to substitute table names and instrumentation to
+    /// inject comments without changing every call site. Any previously set
+    /// interceptor is replaced, and the statement cache is cleared since its
+    /// statements might have been rewritten differently.
+    ///
+    /// The interceptor must not use the connection it is installed on.
+    ///
+    /// [`prepare`]: Connection::prepare
+    /// [`prepare_cached`]: Connection::prepare_cached
+    /// [`execute`]: Connection::execute
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// let mut c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE test_users (name TEXT)")?;
+    ///
+    /// c.set_sql_interceptor(|sql| match sql.contains("{users}") {
+    ///     true => Cow::Owned(sql.replace("{users}", "test_users")),
+    ///     false => Cow::Borrowed(sql),
+    /// });
+    ///
+    /// c.execute("INSERT INTO {users} VALUES ('Alice')")?;
+    /// let name = c.query_value::<String, _>("SELECT name FROM {users}", ())?;
+    /// assert_eq!(name, "Alice");
+    ///
+    /// c.remove_sql_interceptor();
+    /// assert!(c.execute("SELECT name FROM {users}").is_err());
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn set_sql_interceptor<F>(&mut self, interceptor: F)
+    where
+        F: FnMut(&str) -> Cow<'_, str> + Send + 'static,
+    {
+        *self.sql_interceptor.get_mut() = Some(Box::new(interceptor));
+        self.clear_statement_cache();
+    }
+
+    /// Remove the callback rewriting SQL.
+    pub fn remove_sql_interceptor(&mut self) {
+        if self.sql_interceptor.get_mut().take().is_some() {
+            self.clear_statement_cache();
+        }
+    }
+
+    /// Set an observer receiving lifecycle events for the connection, such as
+    /// transactions being committed and errors occurring.
+    ///
+    /// Any previously set observer is replaced. See [`Observer`] for more.
+    pub fn set_observer<O>(&mut self, observer: O)
+    where
+        O: Observer + 'static,
+    {
+        self.remove_observer();
+        let observer = Box::new(ObserverCell::new(Box::new(observer)));
+        // SAFETY: The hooks are removed before the observer is dropped.
+        unsafe { observer::install(self.raw.as_ptr(), &observer) };
+        self.observer = Some(observer);
+    }
+
+    /// Remove the observer receiving lifecycle events.
+    pub fn remove_observer(&mut self) {
+        if self.observer.is_some() {
+            unsafe { observer::uninstall(self.raw.as_ptr()) };
+            self.observer = None;
+        }
+    }
+
+    /// Notify the observer that the connection is closing and remove it.
+    fn close_observer(&mut self) {
+        if let Some(observer) = &self.observer {
+            observer::emit(observer, Event::Close);
+        }
+
+        self.remove_observer();
+    }
+
+    /// Override the `LIKE` operator with an implementation which compares
+    /// characters using Unicode case folding.
+    ///
+    /// The built-in `LIKE` operator only ignores the case of ASCII characters
+    /// unless SQLite is compiled with the ICU extension. This registers a
+    /// `like(pattern, text)` function implemented in Rust which ignores case
+    /// for all characters with Unicode case mappings.
+    ///
+    /// Characters are compared individually, so `_` always matches exactly
+    /// one character. Note that overriding `LIKE` prevents SQLite from using
+    /// indexes to optimize it, that the `case_sensitive_like` pragma no longer
+    /// has an effect, and that `LIKE` with an `ESCAPE` clause still uses the
+    /// built-in implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// assert!(!c.query_value::<bool, _>("SELECT 'ÅSA' LIKE 'åsa'", ())?);
+    ///
+    /// c.register_unicode_like()?;
+    /// assert!(c.query_value::<bool, _>("SELECT 'ÅSA' LIKE 'åsa'", ())?);
+    /// assert!(c.query_value::<bool, _>("SELECT 'ΣΊΣΥΦΟΣ' LIKE 'σίσυ%ς'", ())?);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn register_unicode_like(&self) -> Result<()> {
+        unsafe { function::create_scalar::<2>(self.raw.as_ptr(), b"like\0", like::like_function) }
+    }
+
     /// Set a callback for handling busy events.
     ///
     /// The callback is triggered when the database cannot perform an operation
@@ -167,6 +1014,76 @@ impl Connection {
         self.busy_callback = None;
         Ok(())
     }
+
+    /// Close the connection, reporting whether the database was actually
+    /// closed.
+    ///
+    /// Dropping a connection defers closing the database until every
+    /// statement prepared from it has been finalized. This instead uses
+    /// `sqlite3_close`, which fails with [`Code::BUSY`] if any statements are
+    /// still alive, in which case the connection is handed back together with
+    /// the error. Statements retained by the statement cache are finalized
+    /// before closing.
+    ///
+    /// This is useful when the database must be known to be closed, such as
+    /// before deleting or reopening it.
+    ///
+    /// [`Code::BUSY`]: crate::Code::BUSY
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Code;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let stmt = c.prepare("SELECT 1")?;
+    ///
+    /// let (c, e) = c.close().unwrap_err();
+    /// assert_eq!(e.code(), Code::BUSY);
+    ///
+    /// drop(stmt);
+    /// c.close().map_err(|(_, e)| e)?;
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn close(self) -> Result<(), (Connection, Error)> {
+        self.clear_statement_cache();
+
+        let result = unsafe {
+            (|| {
+                sqlite3_try!(self.raw.as_ptr(), ffi::sqlite3_close(self.raw.as_ptr()));
+                Ok(())
+            })()
+        };
+
+        if let Err(error) = result {
+            if let Some(observer) = &self.observer {
+                observer::emit(observer, Event::Error(&error));
+            }
+
+            return Err((self, error));
+        }
+
+        // The handle is closed, so the hooks are gone and only the event is
+        // emitted.
+        if let Some(observer) = &self.observer {
+            observer::emit(observer, Event::Close);
+        }
+
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: The database handle has been closed, so the remaining
+        // fields are dropped without running the destructor of the
+        // connection.
+        unsafe {
+            ptr::drop_in_place(&mut this.busy_callback);
+            ptr::drop_in_place(&mut this.cache);
+            ptr::drop_in_place(&mut this.sql_interceptor);
+            ptr::drop_in_place(&mut this.observer);
+        }
+
+        Ok(())
+    }
 }
 
 impl Drop for Connection {
@@ -174,6 +1091,7 @@ impl Drop for Connection {
     #[allow(unused_must_use)]
     fn drop(&mut self) {
         self.remove_busy_handler();
+        self.close_observer();
         // Will close the connection unconditionally. The database will stay
         // alive until all associated prepared statements have been closed since
         // we're using v2.
@@ -224,10 +1142,77 @@ impl OpenOptions {
                 }
             }
 
-            Ok(Connection {
-                raw: NonNull::new_unchecked(raw),
-                busy_callback: None,
-            })
+            Ok(Connection::from_raw(raw))
+        }
+    }
+
+    /// Open a connection to a named in-memory database which is shared by all
+    /// connections in the process opened with the same name.
+    ///
+    /// This opens the URI `file:<name>?mode=memory&cache=shared` with the
+    /// current flags, where `name` is percent-encoded as needed. The database
+    /// is deleted once the last connection to it has been closed.
+    ///
+    /// Names containing NUL bytes result in an error with [`Code::MISUSE`].
+    ///
+    /// [`Code::MISUSE`]: crate::Code::MISUSE
+    pub fn open_shared_memory<T>(&self, name: T) -> Result<Connection>
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+
+        if name.contains('\0') {
+            return Err(Error::from_code(ffi::SQLITE_MISUSE));
+        }
+
+        let mut uri = String::with_capacity(name.len() + 32);
+        uri.push_str("file:");
+
+        for b in name.bytes() {
+            match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    uri.push(b as char);
+                }
+                b => {
+                    uri.push_str(&format!("%{b:02X}"));
+                }
+            }
+        }
+
+        uri.push_str("?mode=memory&cache=shared");
+
+        let mut options = *self;
+        options.raw |=
+            ffi::SQLITE_OPEN_URI | ffi::SQLITE_OPEN_MEMORY | ffi::SQLITE_OPEN_SHAREDCACHE;
+        options.open(uri)
+    }
+
+    /// Open a database connection with current flags and the given observer
+    /// installed.
+    ///
+    /// The observer receives [`Event::Open`] if the connection was opened, or
+    /// [`Event::Error`] if opening it failed. See [`Connection::set_observer`].
+    ///
+    /// [`Event::Open`]: crate::Event::Open
+    /// [`Event::Error`]: crate::Event::Error
+    pub fn open_observed<T, O>(&self, path: T, mut observer: O) -> Result<Connection>
+    where
+        T: AsRef<Path>,
+        O: Observer + 'static,
+    {
+        let path = path.as_ref();
+
+        match self.open(path) {
+            Ok(mut connection) => {
+                observer.event(Event::Open { path });
+                connection.set_observer(observer);
+                Ok(connection)
+            }
+            Err(error) => {
+                observer.event(Event::Error(&error));
+                Err(error)
+            }
         }
     }
 
@@ -266,56 +1251,75 @@ impl OpenOptions {
     }
 }
 
-extern "C" fn busy_callback<F>(callback: *mut c_void, attempts: c_int) -> c_int
-where
-    F: FnMut(usize) -> bool,
-{
-    unsafe {
-        if (*(callback as *mut F))(attempts as usize) {
-            1
-        } else {
-            0
-        }
+/// Execute all statements in `statement` against the given handle.
+pub(crate) fn execute(handle: *mut ffi::sqlite3, statement: &str) -> Result<()> {
+    let mut rest = statement;
+
+    while let (Some(mut statement), tail) = Statement::prepare_next(handle, rest)? {
+        while let State::Row = statement.step()? {}
+        rest = tail;
     }
+
+    Ok(())
 }
 
-// TODO: remove unwraps.
-extern "C" fn process_callback<F>(
-    callback: *mut c_void,
-    count: c_int,
-    values: *mut *mut c_char,
-    columns: *mut *mut c_char,
-) -> c_int
+/// Bind parameters to a prepared statement and decode the single row it's
+/// expected to produce, as in [`Connection::query_row`].
+pub(crate) fn query_one<T, P>(statement: &mut Statement, params: P) -> Result<T>
 where
-    F: FnMut(&[(&str, Option<&str>)]) -> bool,
+    T: FromRow,
+    P: Params,
 {
-    unsafe {
-        let mut pairs = Vec::with_capacity(count as usize);
-
-        for i in 0..(count as isize) {
-            let column = {
-                let pointer = *columns.offset(i);
-                debug_assert!(!pointer.is_null());
-                utils::cstr_to_str(pointer).unwrap()
-            };
+    params.bind_params(statement)?;
 
-            let value = {
-                let pointer = *values.offset(i);
+    let value = match statement.next_row()? {
+        Some(row) => T::from_row(&row)?,
+        None => return Err(Error::from_kind(ErrorKind::NoRows)),
+    };
 
-                if pointer.is_null() {
-                    None
-                } else {
-                    Some(utils::cstr_to_str(pointer).unwrap())
-                }
-            };
+    if let State::Row = statement.step()? {
+        return Err(Error::from_kind(ErrorKind::TooManyRows));
+    }
+
+    Ok(value)
+}
 
-            pairs.push((column, value));
+/// Commit a transaction which was started by the caller if the operation
+/// performed inside of it succeeded, or roll it back otherwise.
+///
+/// If committing fails, such as with [`Code::BUSY`] when another connection
+/// is reading from the database, SQLite keeps the transaction open. It's then
+/// rolled back so that it doesn't linger and capture later statements.
+///
+/// [`Code::BUSY`]: crate::Code::BUSY
+pub(crate) fn commit_or_rollback<T>(handle: *mut ffi::sqlite3, result: Result<T>) -> Result<T> {
+    let result = match result {
+        Ok(value) => execute(handle, "COMMIT").map(|()| value),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = result {
+        // SQLite might already have rolled back the transaction depending on
+        // the error.
+        if unsafe { ffi::sqlite3_get_autocommit(handle) == 0 } {
+            execute(handle, "ROLLBACK")?;
         }
 
-        if (*(callback as *mut F))(&pairs) {
-            0
-        } else {
+        return Err(error);
+    }
+
+    result
+}
+
+extern "C" fn busy_callback<F>(callback: *mut c_void, attempts: c_int) -> c_int
+where
+    F: FnMut(usize) -> bool,
+{
+    utils::catch_unwind(0, || unsafe {
+        if (*(callback as *mut F))(attempts as usize) {
             1
+        } else {
+            0
         }
-    }
+    })
 }
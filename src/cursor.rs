@@ -0,0 +1,46 @@
+use crate::error::Result;
+use crate::iter::Iter;
+use crate::row::{FromRow, Row};
+use crate::statement::Statement;
+use crate::value::Value;
+
+/// A cursor over the rows produced by a statement which has been reset and
+/// bound to a full set of parameters.
+///
+/// See [`Statement::cursor`].
+pub struct Cursor<'a> {
+    statement: &'a mut Statement,
+}
+
+impl<'a> Cursor<'a> {
+    #[inline]
+    pub(crate) fn new(statement: &'a mut Statement) -> Self {
+        Self { statement }
+    }
+
+    /// Step to the next row, returning it if one is available.
+    #[inline]
+    pub fn next_row(&mut self) -> Result<Option<Row<'_>>> {
+        self.statement.next_row()
+    }
+
+    /// Convert the cursor into an iterator where each row is decoded into `T`
+    /// through [`FromRow`].
+    #[inline]
+    pub fn iter<T>(self) -> Iter<'a, T>
+    where
+        T: FromRow,
+    {
+        Iter::new(self.statement)
+    }
+}
+
+impl<'a> IntoIterator for Cursor<'a> {
+    type Item = Result<Vec<Value>>;
+    type IntoIter = Iter<'a, Vec<Value>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.statement)
+    }
+}
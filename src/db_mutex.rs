@@ -0,0 +1,47 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use sqlite3_sys as ffi;
+
+/// A guard holding the recursive mutex of a database connection, returned by
+/// [`Connection::db_mutex`] and [`SyncConnection::lock`].
+///
+/// While the guard is held, no other thread can use the connection or any
+/// statement prepared from it, which allows compound operations to be
+/// performed without other threads interleaving with them. Since the mutex is
+/// recursive, the thread holding the guard can keep using the connection as
+/// usual.
+///
+/// The mutex is released when the guard is dropped. The guard can't be sent to
+/// other threads, since the mutex has to be released by the thread which
+/// entered it.
+///
+/// [`Connection::db_mutex`]: crate::Connection::db_mutex
+/// [`SyncConnection::lock`]: crate::SyncConnection::lock
+pub struct DbMutexGuard<'a> {
+    mutex: NonNull<ffi::sqlite3_mutex>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl DbMutexGuard<'_> {
+    /// Enter the given mutex.
+    ///
+    /// # Safety
+    ///
+    /// The mutex must be valid for the lifetime of the guard.
+    pub(crate) unsafe fn enter(mutex: NonNull<ffi::sqlite3_mutex>) -> Self {
+        ffi::sqlite3_mutex_enter(mutex.as_ptr());
+
+        Self {
+            mutex,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Drop for DbMutexGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_mutex_leave(self.mutex.as_ptr()) };
+    }
+}
@@ -0,0 +1,449 @@
+use core::fmt;
+
+use serde::de::{self, IntoDeserializer};
+
+use crate::error::{Error, Result};
+use crate::statement::Statement;
+use crate::value::{Type, Value};
+
+impl de::Error for Error {
+    #[inline]
+    fn custom<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::custom(message)
+    }
+}
+
+/// Deserializer for the current row of a statement.
+///
+/// Structs and maps are deserialized using column names as keys, while
+/// sequences and tuples are deserialized by position.
+pub(crate) struct RowDeserializer<'de> {
+    statement: &'de Statement,
+}
+
+impl<'de> RowDeserializer<'de> {
+    #[inline]
+    pub(crate) fn new(statement: &'de Statement) -> Self {
+        Self { statement }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(Columns {
+            statement: self.statement,
+            index: 0,
+            count: self.statement.column_count(),
+        })
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Columns {
+            statement: self.statement,
+            index: 0,
+            count: self.statement.column_count(),
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct enum identifier
+        ignored_any
+    }
+}
+
+/// Access to the columns of a row, either as a map or a sequence.
+struct Columns<'de> {
+    statement: &'de Statement,
+    index: usize,
+    count: usize,
+}
+
+impl<'de> de::MapAccess<'de> for Columns<'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.index == self.count {
+            return Ok(None);
+        }
+
+        let name = self.statement.column_name(self.index)?;
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(name))
+            .map(Some)
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let index = self.index;
+        self.index += 1;
+
+        seed.deserialize(ColumnDeserializer {
+            statement: self.statement,
+            index,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.count - self.index)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Columns<'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.index == self.count {
+            return Ok(None);
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        seed.deserialize(ColumnDeserializer {
+            statement: self.statement,
+            index,
+        })
+        .map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.count - self.index)
+    }
+}
+
+/// Deserializer for a single column.
+struct ColumnDeserializer<'de> {
+    statement: &'de Statement,
+    index: usize,
+}
+
+impl<'de> de::Deserializer<'de> for ColumnDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.statement.column_type(self.index) {
+            Type::Null => visitor.visit_unit(),
+            Type::Integer => visitor.visit_i64(self.statement.read(self.index)?),
+            Type::Float => visitor.visit_f64(self.statement.read(self.index)?),
+            Type::Text => visitor.visit_borrowed_str(self.statement.column_text(self.index)?),
+            Type::Blob => visitor.visit_borrowed_bytes(self.statement.column_blob(self.index)),
+        }
+    }
+
+    #[inline]
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.statement.column_type(self.index) {
+            Type::Integer => visitor.visit_bool(self.statement.read::<i64>(self.index)? != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.statement.column_type(self.index) {
+            Type::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.statement.column_type(self.index) {
+            Type::Text | Type::Blob => {
+                visitor.visit_borrowed_bytes(self.statement.column_blob(self.index))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.statement.column_type(self.index) {
+            Type::Text => {
+                let variant = self.statement.column_text(self.index)?;
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    /// Deserialize from any self-describing value. Sequences of bytes are
+    /// deserialized as blobs, since that is how many formats represent them.
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an SQLite value")
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, value: bool) -> core::result::Result<Value, E> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, value: i64) -> core::result::Result<Value, E> {
+        Ok(Value::Integer(value))
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, value: u64) -> core::result::Result<Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Integer(value)),
+            Err(..) => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+        }
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, value: f64) -> core::result::Result<Value, E> {
+        Ok(Value::Float(value))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> core::result::Result<Value, E> {
+        Ok(Value::Text(value.to_owned()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, value: String) -> core::result::Result<Value, E> {
+        Ok(Value::Text(value))
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, value: &[u8]) -> core::result::Result<Value, E> {
+        Ok(Value::Blob(value.to_vec()))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> core::result::Result<Value, E> {
+        Ok(Value::Blob(value))
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> core::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> core::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> core::result::Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut blob = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(b) = seq.next_element::<u8>()? {
+            blob.push(b);
+        }
+
+        Ok(Value::Blob(blob))
+    }
+}
+
+const TYPES: &[&str] = &["Blob", "Float", "Integer", "Text", "Null"];
+
+impl<'de> de::Deserialize<'de> for Type {
+    /// Deserialize from a unit variant.
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("Type", TYPES, TypeVisitor)
+    }
+}
+
+struct TypeVisitor;
+
+impl<'de> de::Visitor<'de> for TypeVisitor {
+    type Value = Type;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an SQLite type")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> core::result::Result<Type, E>
+    where
+        E: de::Error,
+    {
+        Ok(match value {
+            0 => Type::Blob,
+            1 => Type::Float,
+            2 => Type::Integer,
+            3 => Type::Text,
+            4 => Type::Null,
+            _ => return Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+        })
+    }
+
+    fn visit_str<E>(self, value: &str) -> core::result::Result<Type, E>
+    where
+        E: de::Error,
+    {
+        Ok(match value {
+            "Blob" => Type::Blob,
+            "Float" => Type::Float,
+            "Integer" => Type::Integer,
+            "Text" => Type::Text,
+            "Null" => Type::Null,
+            _ => return Err(E::unknown_variant(value, TYPES)),
+        })
+    }
+
+    fn visit_enum<A>(self, data: A) -> core::result::Result<Type, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use serde::de::VariantAccess;
+
+        let (ty, variant) = data.variant_seed(TypeSeed)?;
+        variant.unit_variant()?;
+        Ok(ty)
+    }
+}
+
+struct TypeSeed;
+
+impl<'de> de::DeserializeSeed<'de> for TypeSeed {
+    type Value = Type;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> core::result::Result<Type, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(TypeVisitor)
+    }
+}
@@ -0,0 +1,258 @@
+use std::time::{Duration, SystemTime};
+
+use crate::duration::{float_seconds, from_epoch};
+use crate::error::Result;
+use crate::statement::{Readable, Statement};
+use crate::value::Type;
+
+/// The number of days between the start of the julian calendar used by SQLite
+/// and the unix epoch.
+const JULIAN_EPOCH: f64 = 2440587.5;
+
+/// The type affinity of a column.
+///
+/// This is determined from the declared type of a column, using the rules
+/// SQLite itself uses as described in [Datatypes In SQLite].
+///
+/// [Datatypes In SQLite]: https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Affinity {
+    /// The declared type contains `INT`.
+    Integer,
+    /// The declared type contains `CHAR`, `CLOB` or `TEXT`.
+    Text,
+    /// The declared type contains `BLOB`, or no type is declared.
+    Blob,
+    /// The declared type contains `REAL`, `FLOA` or `DOUB`.
+    Real,
+    /// Any other declared type.
+    Numeric,
+}
+
+impl Affinity {
+    /// Determine the affinity of a declared type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Affinity;
+    ///
+    /// assert_eq!(Affinity::from_decltype("BIGINT"), Affinity::Integer);
+    /// assert_eq!(Affinity::from_decltype("VARCHAR(255)"), Affinity::Text);
+    /// assert_eq!(Affinity::from_decltype("DOUBLE PRECISION"), Affinity::Real);
+    /// assert_eq!(Affinity::from_decltype("DATETIME"), Affinity::Numeric);
+    /// assert_eq!(Affinity::from_decltype(""), Affinity::Blob);
+    /// ```
+    pub fn from_decltype(decltype: &str) -> Self {
+        let decltype = decltype.to_ascii_uppercase();
+
+        if decltype.contains("INT") {
+            Affinity::Integer
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|s| decltype.contains(s))
+        {
+            Affinity::Text
+        } else if decltype.contains("BLOB") || decltype.trim().is_empty() {
+            Affinity::Blob
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|s| decltype.contains(s))
+        {
+            Affinity::Real
+        } else {
+            Affinity::Numeric
+        }
+    }
+}
+
+/// A dynamic value which has been read according to the declared type of its
+/// column.
+///
+/// See [`Statement::read_declared`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeclaredValue {
+    Blob(Vec<u8>),
+    Boolean(bool),
+    Float(f64),
+    Integer(i64),
+    Text(String),
+    Timestamp(SystemTime),
+    Null,
+}
+
+/// Read a column according to its declared type.
+pub(crate) fn read(statement: &Statement, i: usize) -> Result<DeclaredValue> {
+    let ty = statement.column_type(i);
+
+    if let Type::Null = ty {
+        return Ok(DeclaredValue::Null);
+    }
+
+    // The name of the declared type without any size arguments, like in
+    // `DATETIME(6)`.
+    let name = statement
+        .column_decltype(i)?
+        .and_then(|decltype| decltype.split('(').next())
+        .map(|name| name.trim().to_ascii_uppercase());
+
+    match (name.as_deref(), ty) {
+        (Some("BOOL" | "BOOLEAN"), Type::Integer) => {
+            return Ok(DeclaredValue::Boolean(bool::read(statement, i)?));
+        }
+        (Some("DATE" | "DATETIME" | "TIMESTAMP"), _) => {
+            if let Some(time) = timestamp(statement, i, ty)? {
+                return Ok(DeclaredValue::Timestamp(time));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(match ty {
+        Type::Blob => DeclaredValue::Blob(statement.read_blob(i)?.to_vec()),
+        Type::Float => DeclaredValue::Float(f64::read(statement, i)?),
+        Type::Integer => DeclaredValue::Integer(i64::read(statement, i)?),
+        Type::Text => DeclaredValue::Text(statement.read_str(i)?.to_owned()),
+        Type::Null => DeclaredValue::Null,
+    })
+}
+
+/// Read a point in time in any of the formats understood by the date and time
+/// functions of SQLite, returning `None` if the column isn't one.
+fn timestamp(statement: &Statement, i: usize, ty: Type) -> Result<Option<SystemTime>> {
+    let seconds = match ty {
+        Type::Integer => {
+            let seconds = i64::read(statement, i)?;
+            let duration = Duration::from_secs(seconds.unsigned_abs());
+            return Ok(Some(from_epoch(seconds >= 0, duration)?));
+        }
+        Type::Float => (f64::read(statement, i)? - JULIAN_EPOCH) * 86400.0,
+        Type::Text => match parse(statement.read_str(i)?) {
+            Some(seconds) => seconds,
+            None => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    let duration = float_seconds(seconds.abs())?;
+    Ok(Some(from_epoch(seconds >= 0.0, duration)?))
+}
+
+/// Parse a `YYYY-MM-DD` date optionally followed by a `HH:MM[:SS[.SSS]]` time
+/// and a timezone, into seconds since the unix epoch.
+fn parse(text: &str) -> Option<f64> {
+    let mut p = Parser(text.trim().as_bytes());
+
+    let year = p.number(4)?;
+    p.expect(b'-')?;
+    let month = p.number(2)?;
+    p.expect(b'-')?;
+    let day = p.number(2)?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day) as f64 * 86400.0;
+
+    if p.eat(b' ') || p.eat(b'T') {
+        let hour = p.number(2)?;
+        p.expect(b':')?;
+        let minute = p.number(2)?;
+        let mut second = 0.0;
+
+        if p.eat(b':') {
+            second = p.number(2)? as f64;
+
+            if p.eat(b'.') {
+                let start = p.0;
+
+                while let [b'0'..=b'9', rest @ ..] = p.0 {
+                    p.0 = rest;
+                }
+
+                let digits = &start[..start.len() - p.0.len()];
+                let fraction = std::str::from_utf8(digits).ok()?;
+                second += format!("0.{fraction}").parse::<f64>().ok()?;
+            }
+        }
+
+        if hour > 24 || minute > 59 || second >= 60.0 {
+            return None;
+        }
+
+        seconds += (hour * 3600 + minute * 60) as f64 + second;
+
+        if !p.eat(b'Z') {
+            let sign = if p.eat(b'+') {
+                -1
+            } else if p.eat(b'-') {
+                1
+            } else {
+                0
+            };
+
+            if sign != 0 {
+                let hours = p.number(2)?;
+                p.expect(b':')?;
+                let minutes = p.number(2)?;
+                seconds += (sign * (hours * 3600 + minutes * 60)) as f64;
+            }
+        }
+    }
+
+    if !p.0.is_empty() {
+        return None;
+    }
+
+    Some(seconds)
+}
+
+/// Compute the number of days since the unix epoch for the given civil date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl Parser<'_> {
+    fn eat(&mut self, b: u8) -> bool {
+        match self.0 {
+            [first, rest @ ..] if *first == b => {
+                self.0 = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        self.eat(b).then_some(())
+    }
+
+    fn number(&mut self, digits: usize) -> Option<i64> {
+        if self.0.len() < digits {
+            return None;
+        }
+
+        let (head, tail) = self.0.split_at(digits);
+        let mut value = 0;
+
+        for &b in head {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+
+            value = value * 10 + i64::from(b - b'0');
+        }
+
+        self.0 = tail;
+        Some(value)
+    }
+}
@@ -0,0 +1,193 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+/// A wrapper which binds durations and points in time as floating point
+/// seconds.
+///
+/// By default a [`Duration`] is stored as an integer number of nanoseconds,
+/// and a [`SystemTime`] as an integer number of milliseconds since the unix
+/// epoch. Through this wrapper they're instead stored as a floating point
+/// number of seconds, which for a [`SystemTime`] is what the `unixepoch`
+/// modifier of the date and time functions of SQLite expects.
+///
+/// Floating point values are read as seconds regardless of whether this
+/// wrapper is used, while integers read through this wrapper are interpreted
+/// as whole seconds.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use sqlite_ll::{Seconds, State};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// let time = UNIX_EPOCH + Duration::from_secs(1680352205);
+///
+/// let mut stmt = c.prepare("SELECT ?, ?, datetime(?2, 'unixepoch'), ?")?;
+/// stmt.bind_all((Duration::from_millis(1500), Seconds(time), Seconds(Duration::from_millis(1500))))?;
+/// assert_eq!(stmt.step()?, State::Row);
+///
+/// assert_eq!(stmt.read::<i64>(0)?, 1_500_000_000);
+/// assert_eq!(stmt.read::<String>(2)?, "2023-04-01 12:30:05");
+/// assert_eq!(stmt.read::<f64>(3)?, 1.5);
+///
+/// assert_eq!(stmt.read::<Seconds<std::time::SystemTime>>(1)?, Seconds(time));
+/// assert_eq!(stmt.read::<Duration>(3)?, Duration::from_millis(1500));
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seconds<T>(pub T);
+
+impl Bindable for Duration {
+    /// Bind as an integer number of nanoseconds, erroring with
+    /// [`Code::RANGE`] if it doesn't fit in an `i64`.
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        match i64::try_from(self.as_nanos()) {
+            Ok(nanos) => nanos.bind(statement, i),
+            Err(..) => Err(out_of_range(format_args!("{self:?}"))),
+        }
+    }
+}
+
+impl Readable for Duration {
+    /// Read an integer number of nanoseconds, or a floating point number of
+    /// seconds.
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Integer => {
+                let nanos = i64::read(statement, i)?;
+
+                match u64::try_from(nanos) {
+                    Ok(nanos) => Ok(Duration::from_nanos(nanos)),
+                    Err(..) => Err(out_of_range(nanos)),
+                }
+            }
+            Type::Float => float_seconds(f64::read(statement, i)?),
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Bindable for Seconds<Duration> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.0.as_secs_f64().bind(statement, i)
+    }
+}
+
+impl Readable for Seconds<Duration> {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Integer => {
+                let seconds = i64::read(statement, i)?;
+
+                match u64::try_from(seconds) {
+                    Ok(seconds) => Ok(Seconds(Duration::from_secs(seconds))),
+                    Err(..) => Err(out_of_range(seconds)),
+                }
+            }
+            Type::Float => Ok(Seconds(float_seconds(f64::read(statement, i)?)?)),
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Bindable for SystemTime {
+    /// Bind as an integer number of milliseconds since the unix epoch, which
+    /// is negative for points in time before it.
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        let millis = match self.duration_since(UNIX_EPOCH) {
+            Ok(duration) => i64::try_from(duration.as_millis()).ok(),
+            Err(error) => i64::try_from(error.duration().as_millis())
+                .ok()
+                .map(|millis| -millis),
+        };
+
+        match millis {
+            Some(millis) => millis.bind(statement, i),
+            None => Err(out_of_range(format_args!("{self:?}"))),
+        }
+    }
+}
+
+impl Readable for SystemTime {
+    /// Read an integer number of milliseconds, or a floating point number of
+    /// seconds, since the unix epoch.
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Integer => {
+                let millis = i64::read(statement, i)?;
+                from_epoch(millis >= 0, Duration::from_millis(millis.unsigned_abs()))
+            }
+            Type::Float => Ok(Seconds::<SystemTime>::read(statement, i)?.0),
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Bindable for Seconds<SystemTime> {
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        let seconds = match self.0.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64(),
+            Err(error) => -error.duration().as_secs_f64(),
+        };
+
+        seconds.bind(statement, i)
+    }
+}
+
+impl Readable for Seconds<SystemTime> {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Integer => {
+                let seconds = i64::read(statement, i)?;
+                let duration = Duration::from_secs(seconds.unsigned_abs());
+                Ok(Seconds(from_epoch(seconds >= 0, duration)?))
+            }
+            Type::Float => {
+                let seconds = f64::read(statement, i)?;
+                let duration = float_seconds(seconds.abs())?;
+                Ok(Seconds(from_epoch(seconds >= 0.0, duration)?))
+            }
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+pub(crate) fn from_epoch(after: bool, duration: Duration) -> Result<SystemTime> {
+    let time = if after {
+        UNIX_EPOCH.checked_add(duration)
+    } else {
+        UNIX_EPOCH.checked_sub(duration)
+    };
+
+    match time {
+        Some(time) => Ok(time),
+        None => Err(out_of_range(format_args!("{duration:?}"))),
+    }
+}
+
+pub(crate) fn float_seconds(seconds: f64) -> Result<Duration> {
+    if !seconds.is_finite() || seconds < 0.0 || seconds >= u64::MAX as f64 {
+        return Err(out_of_range(seconds));
+    }
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn out_of_range<T>(value: T) -> Error
+where
+    T: std::fmt::Display,
+{
+    Error::new(
+        sqlite3_sys::SQLITE_RANGE,
+        Some(format!("{value} is out of range for a duration or point in time").into()),
+    )
+}
@@ -119,8 +119,29 @@ impl fmt::Debug for Code {
     }
 }
 
+/// The kind of an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An error reported by SQLite, or a custom error. See [`Error::code`].
+    Sqlite,
+    /// A query which was expected to produce a row didn't produce any.
+    NoRows,
+    /// A query which was expected to produce a single row produced more than
+    /// one.
+    TooManyRows,
+    /// A statement was aborted since it exceeded the budget of virtual machine
+    /// operations set through [`Statement::set_op_budget`]. The error has the
+    /// code [`Code::INTERRUPT`].
+    ///
+    /// [`Statement::set_op_budget`]: crate::Statement::set_op_budget
+    OpBudgetExceeded,
+}
+
 /// An error.
 pub struct Error {
+    /// The kind of the error.
+    kind: ErrorKind,
     /// Error code.
     code: Code,
     /// Message.
@@ -134,6 +155,7 @@ impl Error {
         E: fmt::Display,
     {
         Self {
+            kind: ErrorKind::Sqlite,
             code: Code::ERROR,
             message: Some(error.to_string().into()),
         }
@@ -142,6 +164,7 @@ impl Error {
     /// Construct a new error with the specified message.
     pub(crate) fn new(code: c_int, message: Option<Box<str>>) -> Self {
         Self {
+            kind: ErrorKind::Sqlite,
             code: Code(code),
             message,
         }
@@ -150,12 +173,53 @@ impl Error {
     /// Construct from a code.
     pub(crate) fn from_code(code: c_int) -> Self {
         Self {
+            kind: ErrorKind::Sqlite,
             code: Code(code),
             message: None,
         }
     }
 
+    /// Construct an error of the given kind.
+    pub(crate) fn from_kind(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            code: Code::ERROR,
+            message: None,
+        }
+    }
+
+    /// Construct an error for a statement which exceeded the given budget of
+    /// virtual machine operations.
+    pub(crate) fn op_budget_exceeded(budget: usize) -> Self {
+        Self {
+            kind: ErrorKind::OpBudgetExceeded,
+            code: Code::INTERRUPT,
+            message: Some(format!("budget of {budget} operations exceeded").into()),
+        }
+    }
+
+    /// Prefix the message of the error with the given context.
+    pub(crate) fn with_context<C>(mut self, context: C) -> Self
+    where
+        C: fmt::Display,
+    {
+        self.message = Some(match self.message {
+            Some(message) => format!("{context}: {message}").into(),
+            None => context.to_string().into(),
+        });
+
+        self
+    }
+
+    /// The kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
     /// Error code.
+    ///
+    /// Errors which don't originate from SQLite have the code
+    /// [`Code::ERROR`].
     pub fn code(&self) -> Code {
         self.code
     }
@@ -163,6 +227,7 @@ impl Error {
     /// Construct a mismatch error.
     pub fn mismatch() -> Self {
         Self {
+            kind: ErrorKind::Sqlite,
             code: Code(sqlite3_sys::SQLITE_MISMATCH),
             message: None,
         }
@@ -172,6 +237,7 @@ impl Error {
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Error")
+            .field("kind", &self.kind)
             .field("code", &self.code)
             .field("message", &self.message)
             .finish()
@@ -180,6 +246,21 @@ impl fmt::Debug for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Sqlite => {}
+            ErrorKind::NoRows => return write!(f, "query returned no rows"),
+            ErrorKind::TooManyRows => return write!(f, "query returned more than one row"),
+            ErrorKind::OpBudgetExceeded => {
+                write!(f, "statement was aborted")?;
+
+                if let Some(message) = &self.message {
+                    write!(f, ": {}", message)?;
+                }
+
+                return Ok(());
+            }
+        }
+
         write!(f, "sqlite3 error (code {})", self.code.number())?;
 
         if let Some(string) = self.code.string() {
@@ -0,0 +1,52 @@
+//! [`Value`] and [`Type`] implement [`Arbitrary`], so that fuzz targets can
+//! generate them along with parameter sets such as `Vec<Value>`.
+//!
+//! Floats are never `NaN`, since SQLite stores them as `NULL` which would make
+//! round-trips through the database fail.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::value::{Type, Value};
+
+impl<'a> Arbitrary<'a> for Type {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            Type::Blob,
+            Type::Float,
+            Type::Integer,
+            Type::Text,
+            Type::Null,
+        ])?)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u32::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match Type::arbitrary(u)? {
+            Type::Blob => Value::Blob(Vec::arbitrary(u)?),
+            Type::Float => {
+                let value = f64::arbitrary(u)?;
+
+                if value.is_nan() {
+                    Value::Null
+                } else {
+                    Value::Float(value)
+                }
+            }
+            Type::Integer => Value::Integer(i64::arbitrary(u)?),
+            Type::Text => Value::Text(String::arbitrary(u)?),
+            Type::Null => Value::Null,
+        })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (lower, _) = Type::size_hint(depth);
+        (lower, None)
+    }
+}
@@ -0,0 +1,70 @@
+//! An [`ArrayVec`] of bytes is stored as a blob and an [`ArrayString`] as
+//! text. Reading a value which exceeds the capacity errors with
+//! [`Code::RANGE`].
+//!
+//! [`Code::RANGE`]: crate::Code::RANGE
+
+use ::arrayvec::{ArrayString, ArrayVec};
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+
+impl<const CAP: usize> Bindable for &ArrayVec<u8, CAP> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl<const CAP: usize> Bindable for ArrayVec<u8, CAP> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl<const CAP: usize> Readable for ArrayVec<u8, CAP> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let bytes = statement.column_blob(i);
+
+        match ArrayVec::try_from(bytes) {
+            Ok(vec) => Ok(vec),
+            Err(..) => Err(too_long(bytes.len(), CAP)),
+        }
+    }
+}
+
+impl<const CAP: usize> Bindable for &ArrayString<CAP> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_str().bind(statement, i)
+    }
+}
+
+impl<const CAP: usize> Bindable for ArrayString<CAP> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_str().bind(statement, i)
+    }
+}
+
+impl<const CAP: usize> Readable for ArrayString<CAP> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        match ArrayString::from(text) {
+            Ok(string) => Ok(string),
+            Err(..) => Err(too_long(text.len(), CAP)),
+        }
+    }
+}
+
+fn too_long(len: usize, capacity: usize) -> Error {
+    Error::new(
+        ffi::SQLITE_RANGE,
+        Some(format!("value of {len} bytes exceeds capacity of {capacity} bytes").into()),
+    )
+}
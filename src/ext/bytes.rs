@@ -0,0 +1,50 @@
+//! [`Bytes`] and [`BytesMut`] are stored as blobs.
+
+use ::bytes::{Bytes, BytesMut};
+
+use crate::error::Result;
+use crate::statement::{Bindable, Readable, Statement};
+
+impl Bindable for &Bytes {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl Bindable for Bytes {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl Bindable for &BytesMut {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl Bindable for BytesMut {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl Readable for Bytes {
+    /// Read a blob, copying it once out of the column.
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(Bytes::copy_from_slice(statement.column_blob(i)))
+    }
+}
+
+impl Readable for BytesMut {
+    /// Read a blob, copying it once out of the column.
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(BytesMut::from(statement.column_blob(i)))
+    }
+}
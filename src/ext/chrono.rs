@@ -0,0 +1,157 @@
+//! Date and time types from [`chrono`] are stored as ISO-8601 text in the
+//! format used by the date and time functions of SQLite, such as
+//! `2023-04-01 12:30:00.250`.
+//!
+//! When reading, text with either a space or a `T` separating the date and
+//! the time is accepted, and so is an integer which is interpreted as the
+//! number of seconds since the unix epoch or a float which is interpreted as
+//! a Julian day number.
+
+use ::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// The Julian day number of the unix epoch.
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2440587.5;
+
+impl Bindable for NaiveDate {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.format(DATE_FORMAT).to_string().bind(statement, i)
+    }
+}
+
+impl Readable for NaiveDate {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Text => {
+                let text = statement.column_text(i)?;
+
+                match NaiveDate::parse_from_str(text, DATE_FORMAT) {
+                    Ok(date) => Ok(date),
+                    Err(..) => Ok(parse_date_time(text)?.date()),
+                }
+            }
+            _ => Ok(NaiveDateTime::read(statement, i)?.date()),
+        }
+    }
+}
+
+impl Bindable for NaiveTime {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.format(TIME_FORMAT).to_string().bind(statement, i)
+    }
+}
+
+impl Readable for NaiveTime {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        for format in ["%H:%M:%S%.f", "%H:%M"] {
+            if let Ok(time) = NaiveTime::parse_from_str(text, format) {
+                return Ok(time);
+            }
+        }
+
+        Err(invalid(text, "time"))
+    }
+}
+
+impl Bindable for NaiveDateTime {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.format(DATE_TIME_FORMAT).to_string().bind(statement, i)
+    }
+}
+
+impl Readable for NaiveDateTime {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Text => parse_date_time(statement.column_text(i)?),
+            Type::Integer => {
+                let seconds = i64::read(statement, i)?;
+
+                match DateTime::from_timestamp(seconds, 0) {
+                    Some(date_time) => Ok(date_time.naive_utc()),
+                    None => Err(invalid(seconds, "unix timestamp")),
+                }
+            }
+            Type::Float => {
+                let day = f64::read(statement, i)?;
+                let millis = ((day - UNIX_EPOCH_JULIAN_DAY) * 86_400_000.0).round();
+
+                match DateTime::from_timestamp_millis(millis as i64) {
+                    Some(date_time) if millis.is_finite() => Ok(date_time.naive_utc()),
+                    _ => Err(invalid(day, "Julian day")),
+                }
+            }
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Bindable for DateTime<Utc> {
+    /// Bind as text with a `+00:00` offset.
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.format("%Y-%m-%d %H:%M:%S%.f%:z")
+            .to_string()
+            .bind(statement, i)
+    }
+}
+
+impl Readable for DateTime<Utc> {
+    /// Read a date and time, converting it to UTC if it has an offset.
+    ///
+    /// Date and times without an offset are assumed to be in UTC.
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        if let Type::Text = statement.column_type(i) {
+            let text = statement.column_text(i)?;
+
+            for format in ["%Y-%m-%d %H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%#z"] {
+                if let Ok(date_time) = DateTime::parse_from_str(text, format) {
+                    return Ok(date_time.with_timezone(&Utc));
+                }
+            }
+        }
+
+        Ok(Utc.from_utc_datetime(&NaiveDateTime::read(statement, i)?))
+    }
+}
+
+fn parse_date_time(text: &str) -> Result<NaiveDateTime> {
+    for format in [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(text, format) {
+            return Ok(date_time);
+        }
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(text, DATE_FORMAT) {
+        return Ok(date.and_time(NaiveTime::MIN));
+    }
+
+    Err(invalid(text, "date and time"))
+}
+
+fn invalid<T>(value: T, what: &str) -> Error
+where
+    T: std::fmt::Display,
+{
+    Error::new(
+        ffi::SQLITE_MISMATCH,
+        Some(format!("`{value}` is not a valid {what}").into()),
+    )
+}
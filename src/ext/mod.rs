@@ -0,0 +1,32 @@
+//! Implementations of [`Bindable`] and [`Readable`] for types from other
+//! crates, and of traits from other crates for types in this one.
+//!
+//! [`Bindable`]: crate::Bindable
+//! [`Readable`]: crate::Readable
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+
+#[cfg(feature = "bytes")]
+mod bytes;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "smallvec")]
+mod smallvec;
+
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+
+#[cfg(feature = "serde_json")]
+mod serde_json;
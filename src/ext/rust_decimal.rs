@@ -0,0 +1,51 @@
+//! A [`Decimal`] is stored as text, which round-trips losslessly.
+//!
+//! When reading, integers are accepted as well as floats, but note that the
+//! latter might already have lost precision when stored.
+
+use ::rust_decimal::Decimal;
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+impl Bindable for Decimal {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.to_string().bind(statement, i)
+    }
+}
+
+impl Bindable for &Decimal {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (*self).bind(statement, i)
+    }
+}
+
+impl Readable for Decimal {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let result = match statement.column_type(i) {
+            Type::Text => {
+                let text = statement.column_text(i)?;
+
+                match text.parse::<Decimal>() {
+                    Ok(decimal) => Ok(decimal),
+                    Err(..) => Decimal::from_scientific(text),
+                }
+            }
+            Type::Integer => return Ok(Decimal::from(i64::read(statement, i)?)),
+            Type::Float => Decimal::try_from(f64::read(statement, i)?),
+            _ => return Err(Error::mismatch()),
+        };
+
+        match result {
+            Ok(decimal) => Ok(decimal),
+            Err(error) => Err(Error::new(
+                ffi::SQLITE_MISMATCH,
+                Some(format!("invalid decimal: {error}").into()),
+            )),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! A [`serde_json::Value`] is stored as JSON text, which can be used with the
+//! JSON functions of SQLite.
+//!
+//! When reading, text is parsed as JSON, while numbers and `NULL` are
+//! converted into the corresponding JSON value. This allows reading the
+//! result of functions such as `json_extract`, which return numbers as SQL
+//! numbers.
+//!
+//! The binary JSONB format which SQLite uses for blobs is not supported, so
+//! reading a blob errors with [`Code::MISMATCH`]. JSONB can be converted into
+//! text with the `json` function in SQL before it is read.
+//!
+//! [`Code::MISMATCH`]: crate::Code::MISMATCH
+//!
+//! [`serde_json::Value`]: ::serde_json::Value
+
+use ::serde_json::{Number, Value};
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+impl Bindable for &Value {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.to_string().bind(statement, i)
+    }
+}
+
+impl Bindable for Value {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (&self).bind(statement, i)
+    }
+}
+
+impl Readable for Value {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let result = match statement.column_type(i) {
+            Type::Text => ::serde_json::from_str(statement.column_text(i)?),
+            Type::Blob => {
+                return Err(Error::new(
+                    ffi::SQLITE_MISMATCH,
+                    Some("blobs can't be read as json since JSONB is not supported".into()),
+                ))
+            }
+            Type::Integer => return Ok(Value::Number(i64::read(statement, i)?.into())),
+            Type::Float => {
+                return match Number::from_f64(f64::read(statement, i)?) {
+                    Some(number) => Ok(Value::Number(number)),
+                    None => Ok(Value::Null),
+                }
+            }
+            Type::Null => return Ok(Value::Null),
+        };
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(error) => Err(Error::new(
+                ffi::SQLITE_MISMATCH,
+                Some(format!("invalid json: {error}").into()),
+            )),
+        }
+    }
+}
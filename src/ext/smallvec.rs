@@ -0,0 +1,28 @@
+//! A [`SmallVec`] of bytes is stored as a blob, which is read without
+//! allocating if it fits inline.
+
+use ::smallvec::SmallVec;
+
+use crate::error::Result;
+use crate::statement::{Bindable, Readable, Statement};
+
+impl<const N: usize> Bindable for &SmallVec<[u8; N]> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl<const N: usize> Bindable for SmallVec<[u8; N]> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self[..].bind(statement, i)
+    }
+}
+
+impl<const N: usize> Readable for SmallVec<[u8; N]> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(SmallVec::from_slice(statement.column_blob(i)))
+    }
+}
@@ -0,0 +1,153 @@
+//! Date and time types from [`time`] are stored as text. Offset date and
+//! times use RFC 3339, such as `2023-04-01T12:30:05.25Z`, while the other
+//! types use the format of the date and time functions of SQLite, such as
+//! `2023-04-01 12:30:05.25`. Sub-second precision is preserved.
+//!
+//! When reading, text with either a space or a `T` separating the date and
+//! the time is accepted.
+
+use ::time::format_description::well_known::Rfc3339;
+use ::time::format_description::FormatItem;
+use ::time::macros::format_description;
+use ::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+
+const DATE: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+
+const TIME: &[FormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second].[subsecond digits:1+]");
+
+const TIME_PARSE: &[FormatItem<'_>] =
+    format_description!("[hour]:[minute][optional [:[second][optional [.[subsecond]]]]]");
+
+const DATE_TIME: &[FormatItem<'_>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:1+]");
+
+const DATE_TIME_PARSE: &[&[FormatItem<'_>]] = &[
+    format_description!(
+        "[year]-[month]-[day] [hour]:[minute][optional [:[second][optional [.[subsecond]]]]]"
+    ),
+    format_description!(
+        "[year]-[month]-[day]T[hour]:[minute][optional [:[second][optional [.[subsecond]]]]]"
+    ),
+];
+
+const OFFSET_DATE_TIME_PARSE: &[FormatItem<'_>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory]:[offset_minute]"
+);
+
+impl Bindable for Date {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        format(self.format(DATE))?.bind(statement, i)
+    }
+}
+
+impl Readable for Date {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        match Date::parse(text, DATE) {
+            Ok(date) => Ok(date),
+            Err(..) => Ok(parse_date_time(text)?.date()),
+        }
+    }
+}
+
+impl Bindable for Time {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        format(self.format(TIME))?.bind(statement, i)
+    }
+}
+
+impl Readable for Time {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        match Time::parse(text, TIME_PARSE) {
+            Ok(time) => Ok(time),
+            Err(error) => Err(invalid(text, error)),
+        }
+    }
+}
+
+impl Bindable for PrimitiveDateTime {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        format(self.format(DATE_TIME))?.bind(statement, i)
+    }
+}
+
+impl Readable for PrimitiveDateTime {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        parse_date_time(statement.column_text(i)?)
+    }
+}
+
+impl Bindable for OffsetDateTime {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        format(self.format(&Rfc3339))?.bind(statement, i)
+    }
+}
+
+impl Readable for OffsetDateTime {
+    /// Read an offset date and time.
+    ///
+    /// Date and times without an offset are assumed to be in UTC.
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        if let Ok(date_time) = OffsetDateTime::parse(text, &Rfc3339) {
+            return Ok(date_time);
+        }
+
+        if let Ok(date_time) = OffsetDateTime::parse(text, OFFSET_DATE_TIME_PARSE) {
+            return Ok(date_time);
+        }
+
+        Ok(parse_date_time(text)?.assume_utc())
+    }
+}
+
+fn parse_date_time(text: &str) -> Result<PrimitiveDateTime> {
+    let mut last = None;
+
+    for description in DATE_TIME_PARSE {
+        match PrimitiveDateTime::parse(text, description) {
+            Ok(date_time) => return Ok(date_time),
+            Err(error) => last = Some(error),
+        }
+    }
+
+    if let Ok(date) = Date::parse(text, DATE) {
+        return Ok(date.midnight());
+    }
+
+    match last {
+        Some(error) => Err(invalid(text, error)),
+        None => Err(Error::mismatch()),
+    }
+}
+
+fn format(result: Result<String, ::time::error::Format>) -> Result<String> {
+    match result {
+        Ok(string) => Ok(string),
+        Err(error) => Err(Error::new(
+            ffi::SQLITE_MISMATCH,
+            Some(error.to_string().into()),
+        )),
+    }
+}
+
+fn invalid(text: &str, error: ::time::error::Parse) -> Error {
+    Error::new(
+        ffi::SQLITE_MISMATCH,
+        Some(format!("`{text}` is not a valid date or time: {error}").into()),
+    )
+}
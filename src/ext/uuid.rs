@@ -0,0 +1,65 @@
+//! A [`Uuid`] is stored as a 16-byte blob. Wrapping it in [`Hyphenated`]
+//! stores it as hyphenated text instead, such as
+//! `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+//!
+//! When reading, either representation is accepted.
+
+use ::uuid::fmt::Hyphenated;
+use ::uuid::Uuid;
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+impl Bindable for Uuid {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_bytes()[..].bind(statement, i)
+    }
+}
+
+impl Bindable for &Uuid {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (*self).bind(statement, i)
+    }
+}
+
+impl Readable for Uuid {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Blob => match Uuid::from_slice(statement.column_blob(i)) {
+                Ok(uuid) => Ok(uuid),
+                Err(error) => Err(invalid(error)),
+            },
+            Type::Text => match Uuid::parse_str(statement.column_text(i)?) {
+                Ok(uuid) => Ok(uuid),
+                Err(error) => Err(invalid(error)),
+            },
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Bindable for Hyphenated {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        let mut buffer = Uuid::encode_buffer();
+        (&*self.encode_lower(&mut buffer)).bind(statement, i)
+    }
+}
+
+impl Readable for Hyphenated {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(Uuid::read(statement, i)?.hyphenated())
+    }
+}
+
+fn invalid(error: ::uuid::Error) -> Error {
+    Error::new(
+        ffi::SQLITE_MISMATCH,
+        Some(format!("invalid uuid: {error}").into()),
+    )
+}
@@ -0,0 +1,168 @@
+//! Plumbing for registering application-defined SQL functions implemented in
+//! Rust.
+
+use std::borrow::Cow;
+#[cfg(feature = "unicode")]
+use std::cmp::Ordering;
+use std::slice;
+
+#[cfg(feature = "unicode")]
+use libc::c_void;
+use libc::{c_char, c_int};
+use sqlite3_sys as ffi;
+
+use crate::error::Result;
+use crate::utils;
+
+/// The signature of a scalar SQL function.
+pub(crate) type ScalarFunction =
+    fn(&[*mut ffi::sqlite3_value]) -> Result<Output, Cow<'static, str>>;
+
+/// The signature of a collating function.
+#[cfg(feature = "unicode")]
+pub(crate) type Collation = fn(&str, &str) -> Ordering;
+
+/// The result of a scalar SQL function.
+pub(crate) enum Output {
+    Null,
+    Integer(i64),
+    #[cfg(feature = "unicode")]
+    Text(String),
+}
+
+/// Register a deterministic scalar function with the given `NUL`-terminated
+/// name and number of arguments.
+///
+/// # Safety
+///
+/// The connection pointer must be valid.
+pub(crate) unsafe fn create_scalar<const N: usize>(
+    db: *mut ffi::sqlite3,
+    name: &[u8],
+    function: ScalarFunction,
+) -> Result<()> {
+    debug_assert_eq!(name.last(), Some(&0));
+
+    sqlite3_try! {
+        db,
+        ffi::sqlite3_create_function_v2(
+            db,
+            name.as_ptr() as *const c_char,
+            N as c_int,
+            ffi::SQLITE_UTF8 | ffi::SQLITE_DETERMINISTIC,
+            function as *mut _,
+            Some(call_scalar::<N>),
+            None,
+            None,
+            None,
+        )
+    };
+
+    Ok(())
+}
+
+/// Register a collating sequence with the given `NUL`-terminated name.
+///
+/// # Safety
+///
+/// The connection pointer must be valid.
+#[cfg(feature = "unicode")]
+pub(crate) unsafe fn create_collation(
+    db: *mut ffi::sqlite3,
+    name: &[u8],
+    collation: Collation,
+) -> Result<()> {
+    debug_assert_eq!(name.last(), Some(&0));
+
+    sqlite3_try! {
+        db,
+        ffi::sqlite3_create_collation_v2(
+            db,
+            name.as_ptr() as *const c_char,
+            ffi::SQLITE_UTF8,
+            collation as *mut _,
+            Some(call_collation),
+            None,
+        )
+    };
+
+    Ok(())
+}
+
+/// Read an argument as text, or `None` if it is `NULL`.
+///
+/// # Safety
+///
+/// The value must be a valid argument passed to the current function, and the
+/// returned string must not outlive the call.
+pub(crate) unsafe fn text<'a>(value: *mut ffi::sqlite3_value) -> Option<Cow<'a, str>> {
+    if ffi::sqlite3_value_type(value) == ffi::SQLITE_NULL {
+        return None;
+    }
+
+    let pointer = ffi::sqlite3_value_text(value);
+
+    if pointer.is_null() {
+        return Some(Cow::Borrowed(""));
+    }
+
+    let len = ffi::sqlite3_value_bytes(value) as usize;
+    Some(String::from_utf8_lossy(slice::from_raw_parts(pointer, len)))
+}
+
+extern "C" fn call_scalar<const N: usize>(
+    context: *mut ffi::sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut ffi::sqlite3_value,
+) {
+    utils::catch_unwind((), || unsafe {
+        debug_assert_eq!(argc as usize, N);
+        let function: ScalarFunction = std::mem::transmute(ffi::sqlite3_user_data(context));
+        let args = slice::from_raw_parts(argv, N);
+
+        match function(args) {
+            Ok(Output::Null) => ffi::sqlite3_result_null(context),
+            Ok(Output::Integer(value)) => ffi::sqlite3_result_int64(context, value),
+            #[cfg(feature = "unicode")]
+            Ok(Output::Text(value)) => ffi::sqlite3_result_text(
+                context,
+                value.as_ptr() as *const c_char,
+                value.len() as c_int,
+                transient!(),
+            ),
+            Err(message) => {
+                ffi::sqlite3_result_error(
+                    context,
+                    message.as_ptr() as *const c_char,
+                    message.len() as c_int,
+                );
+            }
+        }
+    });
+}
+
+#[cfg(feature = "unicode")]
+extern "C" fn call_collation(
+    collation: *mut c_void,
+    a_len: c_int,
+    a: *const c_void,
+    b_len: c_int,
+    b: *const c_void,
+) -> c_int {
+    utils::catch_unwind(0, || unsafe {
+        let collation: Collation = std::mem::transmute(collation);
+        let a = String::from_utf8_lossy(bytes(a, a_len));
+        let b = String::from_utf8_lossy(bytes(b, b_len));
+        collation(&a, &b) as c_int
+    })
+}
+
+/// Construct a byte slice from a pointer which might be null if it is empty.
+#[cfg(feature = "unicode")]
+unsafe fn bytes<'a>(pointer: *const c_void, len: c_int) -> &'a [u8] {
+    if pointer.is_null() || len <= 0 {
+        return &[];
+    }
+
+    slice::from_raw_parts(pointer as *const u8, len as usize)
+}
@@ -0,0 +1,268 @@
+//! Helpers for the [Geopoly] extension.
+//!
+//! Geopoly represents polygons either as JSON text, such as
+//! `[[0,0],[1,0],[1,1],[0,0]]`, or as a compact binary blob which is what its
+//! functions return. A [`Polygon`] can be converted to and from both, and can
+//! be bound and read directly. It is bound as a blob and can be read from
+//! either representation.
+//!
+//! Using the functions of the extension requires SQLite to be compiled with
+//! `SQLITE_ENABLE_GEOPOLY`, but the helpers in this module can be used
+//! regardless.
+//!
+//! [Geopoly]: https://sqlite.org/geopoly.html
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::geopoly::Polygon;
+//!
+//! let square = Polygon::new([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+//! assert_eq!(square.to_json(), "[[0,0],[1,0],[1,1],[0,1],[0,0]]");
+//! assert_eq!(Polygon::from_json(&square.to_json())?, square);
+//! assert_eq!(Polygon::from_blob(&square.to_blob())?, square);
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+
+use core::fmt::Write;
+
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+/// The size of the header of the binary representation.
+const HEADER: usize = 4;
+
+/// A polygon as represented by the Geopoly extension.
+///
+/// Vertices are stored as pairs of `f32` coordinates, since that is the
+/// precision used by Geopoly. Polygons should be simple and have their
+/// vertices in counter-clockwise order for the functions of the extension to
+/// work as expected. The closing vertex is implied and is not stored.
+///
+/// See the [module level documentation][self] for more.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon {
+    vertices: Vec<[f32; 2]>,
+}
+
+impl Polygon {
+    /// Construct a polygon from its vertices.
+    pub fn new<I>(vertices: I) -> Self
+    where
+        I: IntoIterator<Item = [f32; 2]>,
+    {
+        Self {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+
+    /// Get the vertices of the polygon, without the closing vertex.
+    #[inline]
+    pub fn vertices(&self) -> &[[f32; 2]] {
+        &self.vertices
+    }
+
+    /// Convert the polygon into its vertices, without the closing vertex.
+    #[inline]
+    pub fn into_vertices(self) -> Vec<[f32; 2]> {
+        self.vertices
+    }
+
+    /// Encode the polygon as Geopoly JSON, including the closing vertex.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+
+        for (n, [x, y]) in self
+            .vertices
+            .iter()
+            .chain(self.vertices.first())
+            .enumerate()
+        {
+            if n > 0 {
+                out.push(',');
+            }
+
+            // Writing to a string can't fail.
+            _ = write!(out, "[{x},{y}]");
+        }
+
+        out.push(']');
+        out
+    }
+
+    /// Decode a polygon from Geopoly JSON.
+    ///
+    /// The closing vertex is optional. Errors with [`Code::ERROR`] unless the
+    /// JSON is an array of at least three vertices, each of which is an array
+    /// of two numbers.
+    ///
+    /// [`Code::ERROR`]: crate::Code::ERROR
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid = || {
+            Error::new(
+                ffi::SQLITE_ERROR,
+                Some(format!("`{json}` is not a valid geopoly polygon").into()),
+            )
+        };
+
+        let mut parser = Parser { input: json };
+        let mut vertices = Vec::new();
+
+        parser.expect('[').ok_or_else(invalid)?;
+
+        loop {
+            parser.expect('[').ok_or_else(invalid)?;
+            let x = parser.number().ok_or_else(invalid)?;
+            parser.expect(',').ok_or_else(invalid)?;
+            let y = parser.number().ok_or_else(invalid)?;
+            parser.expect(']').ok_or_else(invalid)?;
+            vertices.push([x, y]);
+
+            if parser.expect(',').is_none() {
+                break;
+            }
+        }
+
+        parser.expect(']').ok_or_else(invalid)?;
+
+        if !parser.input.trim().is_empty() {
+            return Err(invalid());
+        }
+
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        if vertices.len() < 3 {
+            return Err(invalid());
+        }
+
+        Ok(Self { vertices })
+    }
+
+    /// Encode the polygon in the binary representation used by Geopoly.
+    ///
+    /// The vertices are encoded in little-endian byte order.
+    pub fn to_blob(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER + self.vertices.len() * 8);
+        out.push(1);
+        out.extend_from_slice(&(self.vertices.len() as u32).to_be_bytes()[1..]);
+
+        for [x, y] in &self.vertices {
+            out.extend_from_slice(&x.to_le_bytes());
+            out.extend_from_slice(&y.to_le_bytes());
+        }
+
+        out
+    }
+
+    /// Decode a polygon from the binary representation used by Geopoly.
+    ///
+    /// Errors with [`Code::ERROR`] if the blob isn't a valid polygon.
+    ///
+    /// [`Code::ERROR`]: crate::Code::ERROR
+    pub fn from_blob(blob: &[u8]) -> Result<Self> {
+        let invalid = || {
+            Error::new(
+                ffi::SQLITE_ERROR,
+                Some("blob is not a valid geopoly polygon".into()),
+            )
+        };
+
+        let (endian, count, data) = match *blob {
+            [endian @ (0 | 1), a, b, c, ref data @ ..] => {
+                (endian, u32::from_be_bytes([0, a, b, c]) as usize, data)
+            }
+            _ => return Err(invalid()),
+        };
+
+        if count < 3 || data.len() != count * 8 {
+            return Err(invalid());
+        }
+
+        let read = |bytes: &[u8]| {
+            let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+            if endian == 1 {
+                f32::from_le_bytes(bytes)
+            } else {
+                f32::from_be_bytes(bytes)
+            }
+        };
+
+        let vertices = data
+            .chunks_exact(8)
+            .map(|v| [read(&v[..4]), read(&v[4..])])
+            .collect();
+
+        Ok(Self { vertices })
+    }
+}
+
+impl FromIterator<[f32; 2]> for Polygon {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = [f32; 2]>,
+    {
+        Self::new(iter)
+    }
+}
+
+impl From<Vec<[f32; 2]>> for Polygon {
+    #[inline]
+    fn from(vertices: Vec<[f32; 2]>) -> Self {
+        Self { vertices }
+    }
+}
+
+impl Bindable for &Polygon {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.to_blob().bind(statement, i)
+    }
+}
+
+impl Bindable for Polygon {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (&self).bind(statement, i)
+    }
+}
+
+impl Readable for Polygon {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Blob => Self::from_blob(statement.column_blob(i)),
+            Type::Text => Self::from_json(statement.column_text(i)?),
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+/// A minimal parser for the subset of JSON used by Geopoly.
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl Parser<'_> {
+    /// Consume the given character after any whitespace.
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.input = self.input.trim_start().strip_prefix(c)?;
+        Some(())
+    }
+
+    /// Consume a number after any whitespace.
+    fn number(&mut self) -> Option<f32> {
+        let input = self.input.trim_start();
+        let end = input
+            .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(input.len());
+        let value = input[..end].parse::<f32>().ok()?;
+        self.input = &input[end..];
+        Some(value)
+    }
+}
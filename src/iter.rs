@@ -0,0 +1,176 @@
+use core::marker::PhantomData;
+
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::row::FromRow;
+use crate::statement::{Readable, State, Statement};
+use crate::value::Value;
+
+/// An iterator over the rows of a borrowed [`Statement`].
+///
+/// See [`Statement::iter`].
+pub struct Iter<'a, T> {
+    statement: &'a mut Statement,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    #[inline]
+    pub(crate) fn new(statement: &'a mut Statement) -> Self {
+        Self {
+            statement,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: FromRow,
+{
+    type Item = Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        next(self.statement, &mut self.done)
+    }
+}
+
+/// An iterator over the rows of an owned [`Statement`].
+///
+/// This is constructed through the [`IntoIterator`] implementation of
+/// [`Statement`].
+pub struct IntoIter<T> {
+    statement: Statement,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> IntoIter<T> {
+    /// Coerce back into the underlying statement.
+    #[inline]
+    pub fn into_statement(self) -> Statement {
+        self.statement
+    }
+}
+
+impl<T> Iterator for IntoIter<T>
+where
+    T: FromRow,
+{
+    type Item = Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        next(&mut self.statement, &mut self.done)
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Statement {
+    type Item = Result<Vec<Value>>;
+    type IntoIter = Iter<'a, Vec<Value>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self)
+    }
+}
+
+impl IntoIterator for Statement {
+    type Item = Result<Vec<Value>>;
+    type IntoIter = IntoIter<Vec<Value>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            statement: self,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator over a single column of the rows of a statement.
+///
+/// See [`Statement::column_iter`].
+pub struct ColumnIter<'a, T> {
+    statement: &'a mut Statement,
+    index: usize,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> ColumnIter<'a, T> {
+    #[inline]
+    pub(crate) fn new(statement: &'a mut Statement, index: usize) -> Self {
+        Self {
+            statement,
+            index,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for ColumnIter<'_, T>
+where
+    T: Readable,
+{
+    type Item = Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.index >= self.statement.column_count() {
+            self.done = true;
+            return Some(Err(Error::from_code(ffi::SQLITE_RANGE)));
+        }
+
+        let result = match self.statement.step() {
+            Ok(State::Row) => T::read(self.statement, self.index),
+            Ok(State::Done) => {
+                self.done = true;
+                return None;
+            }
+            Err(error) => Err(error),
+        };
+
+        if result.is_err() {
+            self.done = true;
+        }
+
+        Some(result)
+    }
+}
+
+/// Step the statement and decode the next row. Once the statement is done or
+/// has errored the iterator is fused.
+fn next<T>(statement: &mut Statement, done: &mut bool) -> Option<Result<T>>
+where
+    T: FromRow,
+{
+    if *done {
+        return None;
+    }
+
+    let result = match statement.next_row() {
+        Ok(Some(row)) => T::from_row(&row),
+        Ok(None) => {
+            *done = true;
+            return None;
+        }
+        Err(error) => Err(error),
+    };
+
+    if result.is_err() {
+        *done = true;
+    }
+
+    Some(result)
+}
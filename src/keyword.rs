@@ -0,0 +1,67 @@
+use libc::{c_char, c_int};
+use sqlite3_sys as ffi;
+
+use crate::sys;
+
+/// Return the number of distinct keywords understood by SQLite.
+///
+/// # Examples
+///
+/// ```
+/// assert!(sqlite_ll::keyword_count() > 100);
+/// ```
+#[inline]
+pub fn keyword_count() -> usize {
+    unsafe { sys::sqlite3_keyword_count() as usize }
+}
+
+/// Return the keyword at the given index, or `None` if the index is out of
+/// range.
+///
+/// See [`keyword_count`].
+///
+/// # Examples
+///
+/// ```
+/// let keywords = (0..sqlite_ll::keyword_count())
+///     .filter_map(sqlite_ll::keyword_name)
+///     .collect::<Vec<_>>();
+///
+/// assert!(keywords.contains(&"SELECT"));
+/// ```
+pub fn keyword_name(index: usize) -> Option<&'static str> {
+    let index = c_int::try_from(index).ok()?;
+    let mut name = core::ptr::null::<c_char>();
+    let mut len = 0;
+
+    unsafe {
+        if sys::sqlite3_keyword_name(index, &mut name, &mut len) != ffi::SQLITE_OK {
+            return None;
+        }
+
+        // SAFETY: Keywords are static ASCII strings owned by SQLite.
+        let bytes = std::slice::from_raw_parts(name as *const u8, len as usize);
+        std::str::from_utf8(bytes).ok()
+    }
+}
+
+/// Test if the given identifier is a keyword, in which case it needs to be
+/// quoted to be used as the name of a schema object.
+///
+/// Keywords are matched case insensitively.
+///
+/// # Examples
+///
+/// ```
+/// assert!(sqlite_ll::is_keyword("order"));
+/// assert!(sqlite_ll::is_keyword("SELECT"));
+/// assert!(!sqlite_ll::is_keyword("users"));
+/// ```
+pub fn is_keyword(identifier: &str) -> bool {
+    let len = match c_int::try_from(identifier.len()) {
+        Ok(len) => len,
+        Err(..) => return false,
+    };
+
+    unsafe { sys::sqlite3_keyword_check(identifier.as_ptr() as *const c_char, len) != 0 }
+}
@@ -21,9 +21,10 @@
 //! the connection to be delayed until resources associated with it has been
 //! closed.
 //!
-//! The way this crate gets around this is by making the `prepare` function
-//! `unsafe`, so the impetus is on the caller to ensure that the connection it's
-//! related to stays alive for the duration of the prepared statement.
+//! This means that `prepare` is safe and that the statements it returns don't
+//! borrow the connection. They can be stored alongside it or outlive it
+//! entirely, in which case the underlying database handle is kept alive until
+//! the last statement has been dropped.
 //!
 //! <br>
 //!
@@ -102,20 +103,242 @@
 //! # Ok::<_, sqlite_ll::Error>(())
 //! ```
 //!
+//! <br>
+//!
+//! ## Interoperability with other bindings
+//!
+//! Connections and statements can be converted to and from raw SQLite handles
+//! through `Connection::as_raw`, `Connection::into_raw` and
+//! `Connection::from_raw`, and the corresponding methods on `Statement`. This
+//! allows a connection opened through other bindings, such as [rusqlite], to be
+//! driven through this crate, for example while gradually migrating between the
+//! two.
+//!
+//! Since both `sqlite3-sys` and `libsqlite3-sys` link to the `sqlite3` native
+//! library, only one of them can provide it. So to use this crate alongside
+//! rusqlite the `linkage` feature must be disabled, in which case the SQLite
+//! library linked by rusqlite is used:
+//!
+//! ```toml
+//! [dependencies]
+//! rusqlite = "0.29.0"
+//! sqlite-ll = { version = "0.1.0", default-features = false }
+//! ```
+//!
+//! A rusqlite connection can then be borrowed through
+//! `Connection::from_raw_borrowed` for as long as it stays open. The returned
+//! guard never closes the handle, so it's released correctly even if an error
+//! is returned while it's in use:
+//!
+//! ```
+//! # struct Rusqlite(sqlite_ll::Connection);
+//! # impl Rusqlite {
+//! #     unsafe fn handle(&self) -> *mut sqlite_ll::ffi::sqlite3 { self.0.as_raw() }
+//! # }
+//! # let conn = Rusqlite(sqlite_ll::Connection::open(":memory:")?);
+//! # conn.0.execute("CREATE TABLE users (name TEXT)")?;
+//! let c = unsafe { sqlite_ll::Connection::from_raw_borrowed(conn.handle().cast()) };
+//! let count = c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?;
+//! assert_eq!(count, 0);
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+//!
+//! Since this crate can't depend on rusqlite without conflicting over the
+//! `sqlite3` native library, it doesn't provide conversions between their value
+//! types. They map one-to-one, with `Value::Float` corresponding to
+//! `Value::Real`:
+//!
+//! ```
+//! # mod rusqlite { pub mod types {
+//! #     pub enum Value { Null, Integer(i64), Real(f64), Text(String), Blob(Vec<u8>) }
+//! # } }
+//! use rusqlite::types::Value as Rusqlite;
+//! use sqlite_ll::Value;
+//!
+//! fn from_rusqlite(value: Rusqlite) -> Value {
+//!     match value {
+//!         Rusqlite::Null => Value::Null,
+//!         Rusqlite::Integer(n) => Value::Integer(n),
+//!         Rusqlite::Real(n) => Value::Float(n),
+//!         Rusqlite::Text(s) => Value::Text(s),
+//!         Rusqlite::Blob(b) => Value::Blob(b),
+//!     }
+//! }
+//!
+//! fn to_rusqlite(value: Value) -> Rusqlite {
+//!     match value {
+//!         Value::Null => Rusqlite::Null,
+//!         Value::Integer(n) => Rusqlite::Integer(n),
+//!         Value::Float(n) => Rusqlite::Real(n),
+//!         Value::Text(s) => Rusqlite::Text(s),
+//!         Value::Blob(b) => Rusqlite::Blob(b),
+//!     }
+//! }
+//!
+//! assert_eq!(from_rusqlite(to_rusqlite(Value::Integer(42))), Value::Integer(42));
+//! ```
+//!
+//! <br>
+//!
+//! ## Features
+//!
+//! The following optional features are available:
+//! * `derive` - derive [`FromRow`] and [`Params`] for structs, and
+//!   [`Bindable`] and [`Readable`] for fieldless enums.
+//! * `serde` - bind and read structs through [serde], and serialize and
+//!   deserialize [`Value`] and [`Type`].
+//! * `arbitrary` - implement [arbitrary] for [`Value`] and [`Type`] to support
+//!   fuzzing.
+//! * `arrayvec` - bind and read [arrayvec] byte vectors as blobs and strings as
+//!   text without allocating.
+//! * `bytes` - bind and read [bytes] buffers as blobs.
+//! * `chrono` - bind and read date and time types from [chrono] as ISO-8601
+//!   text.
+//! * `is_interrupted` - expose `Connection::is_interrupted`, which requires SQLite
+//!   3.41.0 or later.
+//! * `normalize` - expose `Statement::normalized_sql`, which requires an
+//!   SQLite library compiled with `SQLITE_ENABLE_NORMALIZE`.
+//! * `proptest` - strategies for property testing with [proptest] in the
+//!   `proptest` module.
+//! * `rust_decimal` - bind and read [rust_decimal] decimals losslessly as text.
+//! * `serde_json` - bind and read [serde_json] values as JSON text. The
+//!   binary JSONB format is not supported.
+//! * `shell` - an embeddable interactive shell in the `shell` module, which
+//!   can be used to offer a diagnostics REPL.
+//! * `smallvec` - bind and read [smallvec] byte vectors as blobs, without
+//!   allocating if they fit inline.
+//! * `time` - bind and read date and time types from [time] as text, using RFC
+//!   3339 for offset date and times.
+//! * `tokio` - `AsyncConnection`, which runs a connection on a dedicated worker
+//!   thread and exposes it through async methods using [tokio] primitives.
+//! * `unicode` - `UnicodeFunctions`, which registers `upper`, `lower`, a `NOCASE`
+//!   collating sequence and normalization functions with full Unicode support
+//!   using [unicode-normalization].
+//! * `uuid` - bind and read [uuid] values as 16-byte blobs, or as hyphenated text
+//!   through `uuid::fmt::Hyphenated`.
+//!
+//! The `is_interrupted` and `normalize` features depend on how the linked
+//! SQLite library was built and fail to link otherwise, so they should be
+//! enabled explicitly rather than through `--all-features`.
+//!
+//! [arbitrary]: https://docs.rs/arbitrary
+//! [arrayvec]: https://docs.rs/arrayvec
+//! [bytes]: https://docs.rs/bytes
+//! [chrono]: https://docs.rs/chrono
+//! [proptest]: https://docs.rs/proptest
+//! [rust_decimal]: https://docs.rs/rust_decimal
+//! [serde]: https://serde.rs
+//! [serde_json]: https://docs.rs/serde_json
+//! [smallvec]: https://docs.rs/smallvec
+//! [time]: https://docs.rs/time
+//! [tokio]: https://docs.rs/tokio
+//! [unicode-normalization]: https://docs.rs/unicode-normalization
+//! [rusqlite]: https://docs.rs/rusqlite
+//! [uuid]: https://docs.rs/uuid
 //! [sqlite crate]: https://github.com/stainless-steel/sqlite
 //! [SQLite]: https://www.sqlite.org
 
 #[macro_use]
 mod utils;
+pub mod advisor;
+#[cfg(feature = "tokio")]
+mod async_connection;
+mod batched_writer;
+mod borrowed;
+mod cache;
+mod column_value;
+mod columns;
 mod connection;
+mod cursor;
+mod db_mutex;
+#[cfg(feature = "serde")]
+mod de;
+mod decltype;
+mod duration;
 mod error;
+mod ext;
+mod function;
+pub mod geopoly;
+mod iter;
+mod keyword;
+mod like;
+mod net;
+mod observer;
+mod owned;
+mod params;
+mod path;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+mod quote;
+mod reset_guard;
+mod row;
+mod row_buffer;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "shell")]
+pub mod shell;
 mod statement;
+mod statement_set;
+pub mod stats;
+mod storage;
+mod strict;
+mod strings;
+mod sync_connection;
+mod sys;
+mod table;
+pub mod testing;
+mod text;
+pub mod typestate;
+#[cfg(feature = "unicode")]
+mod unicode;
 mod value;
+mod wal_pool;
 
+#[cfg(feature = "tokio")]
+pub use self::async_connection::{AsyncConnection, AsyncStatement};
+pub use self::batched_writer::BatchedWriter;
+pub use self::borrowed::{BorrowedConnection, BorrowedStatement};
+pub use self::cache::CachedStatement;
+pub use self::column_value::{ColumnValue, OwnedValue};
+pub use self::columns::{Column, Columns};
 pub use self::connection::{Connection, OpenOptions};
-pub use self::error::{Code, Error, Result};
+pub use self::cursor::Cursor;
+pub use self::db_mutex::DbMutexGuard;
+pub use self::decltype::{Affinity, DeclaredValue};
+pub use self::duration::Seconds;
+pub use self::error::{Code, Error, ErrorKind, Result};
+pub use self::iter::{ColumnIter, IntoIter, Iter};
+pub use self::keyword::{is_keyword, keyword_count, keyword_name};
+pub use self::net::Packed;
+pub use self::observer::{Event, Observer};
+pub use self::owned::OwnedStatement;
+pub use self::params::Params;
+pub use self::quote::{
+    decode_base64, encode_base64, quote_blob, quote_identifier, quote_nullable, quote_string,
+    unquote_blob,
+};
+pub use self::reset_guard::ResetGuard;
+pub use self::row::{FromRow, Row};
+pub use self::row_buffer::RowBuffer;
 pub use self::statement::{Bindable, FixedBytes, Readable, State, Statement};
-pub use self::value::{Type, Value};
+pub use self::statement_set::{StatementSet, StatementSetBuilder};
+pub use self::storage::{StorageLimits, StorageUsage};
+pub use self::strict::Strict;
+pub use self::strings::{is_complete, strglob, stricmp, strlike, strnicmp};
+pub use self::sync_connection::SyncConnection;
+pub use self::table::{TableFormat, TableStyle};
+pub use self::text::AsText;
+#[cfg(feature = "unicode")]
+pub use self::unicode::UnicodeFunctions;
+pub use self::value::{Type, Value, ValueRef};
+pub use self::wal_pool::WalPool;
+#[cfg(feature = "derive")]
+pub use sqlite_ll_macros::{Bindable, FromRow, Params, Readable};
+
+/// The raw SQLite bindings used by this crate, for calling functions which
+/// aren't wrapped through handles such as [`Statement::as_raw`].
+#[doc(no_inline)]
+pub use sqlite3_sys as ffi;
 
 /// Return the version number of SQLite.
 ///
@@ -124,3 +347,55 @@ pub use self::value::{Type, Value};
 pub fn version() -> u64 {
     unsafe { sqlite3_sys::sqlite3_libversion_number() as u64 }
 }
+
+/// Fill the buffer with random bytes from the pseudo-random number generator
+/// used internally by SQLite, using `sqlite3_randomness`.
+///
+/// This is the same generator which SQLite uses for things such as the
+/// `random()` function and for picking new rowids, which can be used to
+/// generate tokens without depending on another random number generator.
+///
+/// # Examples
+///
+/// ```
+/// let mut token = [0u8; 16];
+/// sqlite_ll::randomness(&mut token);
+/// assert_ne!(token, [0u8; 16]);
+/// ```
+pub fn randomness(buf: &mut [u8]) {
+    for chunk in buf.chunks_mut(libc::c_int::MAX as usize) {
+        unsafe {
+            sqlite3_sys::sqlite3_randomness(chunk.len() as libc::c_int, chunk.as_mut_ptr().cast());
+        }
+    }
+}
+
+/// Suspend the current thread for at least the given duration, using
+/// `sqlite3_sleep`.
+///
+/// This sleeps through the default VFS like SQLite itself does, which makes it
+/// suitable for busy handlers and for pacing backups. The duration is rounded
+/// up to whole milliseconds, and the duration the VFS was actually asked to
+/// sleep for is returned, which might be longer if it only supports sleeping
+/// in whole seconds.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// sqlite_ll::sleep(Duration::from_millis(10));
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// ```
+pub fn sleep(duration: std::time::Duration) -> std::time::Duration {
+    let mut millis = duration.as_millis();
+
+    if duration.subsec_nanos() % 1_000_000 != 0 {
+        millis += 1;
+    }
+
+    let millis = libc::c_int::try_from(millis).unwrap_or(libc::c_int::MAX);
+    let slept = unsafe { sqlite3_sys::sqlite3_sleep(millis) };
+    std::time::Duration::from_millis(u64::try_from(slept).unwrap_or(0))
+}
@@ -0,0 +1,70 @@
+//! A Unicode-aware implementation of the `LIKE` operator.
+
+use std::borrow::Cow;
+
+use sqlite3_sys as ffi;
+
+use crate::function::{self, Output};
+
+/// The `like(pattern, text)` SQL function.
+pub(crate) fn like_function(args: &[*mut ffi::sqlite3_value]) -> Result<Output, Cow<'static, str>> {
+    // SAFETY: The arguments are only used for the duration of the call.
+    let (pattern, text) = unsafe { (function::text(args[0]), function::text(args[1])) };
+
+    match (pattern, text) {
+        (Some(pattern), Some(text)) => Ok(Output::Integer(like(&pattern, &text).into())),
+        _ => Ok(Output::Null),
+    }
+}
+
+/// Match text against a `LIKE` pattern, where `%` matches any sequence of
+/// zero or more characters and `_` matches any single character.
+///
+/// Other characters match if they are equal after case folding.
+pub(crate) fn like(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+
+    let mut p = 0;
+    let mut t = 0;
+    // The position of the last `%` in the pattern and the position in the
+    // text it currently matches up to, used to backtrack on a mismatch.
+    let mut wildcard = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                wildcard = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '_' || fold_eq(c, text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match wildcard {
+                Some((wp, wt)) => {
+                    wildcard = Some((wp, wt + 1));
+                    p = wp + 1;
+                    t = wt + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
+/// Compare two characters after folding their case.
+fn fold_eq(a: char, b: char) -> bool {
+    a == b || fold(a).eq(fold(b))
+}
+
+/// Fold the case of a character.
+///
+/// This maps a character to its uppercase and then lowercase form, which
+/// unlike only lowercasing also treats characters such as `ς` and `σ` as
+/// equal.
+pub(crate) fn fold(c: char) -> impl Iterator<Item = char> {
+    c.to_uppercase().flat_map(char::to_lowercase)
+}
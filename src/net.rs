@@ -0,0 +1,179 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+/// A wrapper which binds network addresses as packed big-endian blobs rather
+/// than as text.
+///
+/// IPv4 addresses are stored as 4 bytes and IPv6 addresses as 16 bytes.
+/// Socket addresses additionally have their port appended as 2 bytes.
+///
+/// Network addresses can be read from either representation, so reading
+/// through this wrapper is only needed to get back a `Packed` value.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// use sqlite_ll::{Packed, State};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// let addr = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+///
+/// let mut stmt = c.prepare("SELECT ?, ?")?;
+/// stmt.bind_all((addr, Packed(addr)))?;
+/// assert_eq!(stmt.step()?, State::Row);
+///
+/// assert_eq!(stmt.read::<String>(0)?, "192.168.0.1");
+/// assert_eq!(stmt.read::<Vec<u8>>(1)?, [192, 168, 0, 1]);
+/// assert_eq!(stmt.read::<IpAddr>(1)?, addr);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Packed<T>(pub T);
+
+macro_rules! text {
+    ($($ty:ty),*) => {
+        $(
+            impl Bindable for $ty {
+                /// Bind as canonical text.
+                #[inline]
+                fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+                    self.to_string().bind(statement, i)
+                }
+            }
+
+            impl Readable for $ty {
+                /// Read from text or from a packed blob.
+                #[inline]
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    match statement.column_type(i) {
+                        Type::Text => {
+                            let text = statement.column_text(i)?;
+
+                            match text.parse() {
+                                Ok(addr) => Ok(addr),
+                                Err(..) => Err(invalid(format_args!("`{text}`"), stringify!($ty))),
+                            }
+                        }
+                        Type::Blob => Ok(Packed::<$ty>::read(statement, i)?.0),
+                        _ => Err(Error::mismatch()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+text!(IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr);
+
+impl Bindable for Packed<Ipv4Addr> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.0.octets()[..].bind(statement, i)
+    }
+}
+
+impl Bindable for Packed<Ipv6Addr> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.0.octets()[..].bind(statement, i)
+    }
+}
+
+impl Bindable for Packed<IpAddr> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        match self.0 {
+            IpAddr::V4(addr) => Packed(addr).bind(statement, i),
+            IpAddr::V6(addr) => Packed(addr).bind(statement, i),
+        }
+    }
+}
+
+impl Bindable for Packed<SocketAddr> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        let mut bytes = Vec::with_capacity(18);
+
+        match self.0.ip() {
+            IpAddr::V4(addr) => bytes.extend_from_slice(&addr.octets()),
+            IpAddr::V6(addr) => bytes.extend_from_slice(&addr.octets()),
+        }
+
+        bytes.extend_from_slice(&self.0.port().to_be_bytes());
+        bytes.bind(statement, i)
+    }
+}
+
+impl Readable for Packed<Ipv4Addr> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match <[u8; 4]>::try_from(statement.column_blob(i)) {
+            Ok(octets) => Ok(Packed(Ipv4Addr::from(octets))),
+            Err(..) => Err(invalid("blob", "Ipv4Addr")),
+        }
+    }
+}
+
+impl Readable for Packed<Ipv6Addr> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match <[u8; 16]>::try_from(statement.column_blob(i)) {
+            Ok(octets) => Ok(Packed(Ipv6Addr::from(octets))),
+            Err(..) => Err(invalid("blob", "Ipv6Addr")),
+        }
+    }
+}
+
+impl Readable for Packed<IpAddr> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match parse_ip(statement.column_blob(i)) {
+            Some(addr) => Ok(Packed(addr)),
+            None => Err(invalid("blob", "IpAddr")),
+        }
+    }
+}
+
+impl Readable for Packed<SocketAddr> {
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let bytes = statement.column_blob(i);
+
+        if bytes.len() >= 2 {
+            let (ip, port) = bytes.split_at(bytes.len() - 2);
+
+            if let Some(ip) = parse_ip(ip) {
+                let port = u16::from_be_bytes([port[0], port[1]]);
+                return Ok(Packed(SocketAddr::new(ip, port)));
+            }
+        }
+
+        Err(invalid("blob", "SocketAddr"))
+    }
+}
+
+fn parse_ip(bytes: &[u8]) -> Option<IpAddr> {
+    if let Ok(octets) = <[u8; 4]>::try_from(bytes) {
+        return Some(IpAddr::V4(Ipv4Addr::from(octets)));
+    }
+
+    if let Ok(octets) = <[u8; 16]>::try_from(bytes) {
+        return Some(IpAddr::V6(Ipv6Addr::from(octets)));
+    }
+
+    None
+}
+
+fn invalid<T>(what: T, ty: &str) -> Error
+where
+    T: std::fmt::Display,
+{
+    Error::new(
+        sqlite3_sys::SQLITE_MISMATCH,
+        Some(format!("{what} is not a valid `{ty}`").into()),
+    )
+}
@@ -0,0 +1,252 @@
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::path::Path;
+
+use libc::{c_char, c_int, c_uint, c_void};
+use sqlite3_sys as ffi;
+
+use crate::error::Error;
+use crate::utils;
+
+/// An event observed on a connection, see [`Observer`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Event<'a> {
+    /// The connection was opened through [`OpenOptions::open_observed`].
+    ///
+    /// [`OpenOptions::open_observed`]: crate::OpenOptions::open_observed
+    Open {
+        /// The path the connection was opened with.
+        path: &'a Path,
+    },
+    /// The connection is being closed.
+    Close,
+    /// An explicit transaction was started using `BEGIN`, or a `SAVEPOINT`
+    /// outside of a transaction.
+    Begin,
+    /// A transaction is being committed, including the implicit transactions
+    /// of statements executed outside of an explicit transaction.
+    Commit,
+    /// A transaction was rolled back.
+    Rollback,
+    /// A checkpoint was requested using `PRAGMA wal_checkpoint`.
+    ///
+    /// Automatic checkpoints performed by SQLite are not reported.
+    Checkpoint,
+    /// An operation performed through the connection failed.
+    ///
+    /// This is reported for errors returned by the methods of [`Connection`],
+    /// but not for errors from stepping statements prepared from it.
+    ///
+    /// [`Connection`]: crate::Connection
+    Error(&'a Error),
+}
+
+/// An observer which receives [`Event`]s for a connection, installed using
+/// [`Connection::set_observer`].
+///
+/// This allows observability such as logging and metrics to be centralized,
+/// instead of wrapping every call made to the connection. Observers are called
+/// synchronously from the thread using the connection, and must not use the
+/// connection they are observing.
+///
+/// This is implemented for closures taking an [`Event`].
+///
+/// [`Connection::set_observer`]: crate::Connection::set_observer
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use sqlite_ll::Event;
+///
+/// let events = Arc::new(Mutex::new(Vec::new()));
+///
+/// let mut c = sqlite_ll::Connection::open(":memory:")?;
+///
+/// c.set_observer({
+///     let events = events.clone();
+///
+///     move |event: Event<'_>| {
+///         let name = match event {
+///             Event::Begin => "begin",
+///             Event::Commit => "commit",
+///             Event::Rollback => "rollback",
+///             Event::Error(..) => "error",
+///             Event::Close => "close",
+///             _ => "other",
+///         };
+///
+///         events.lock().unwrap().push(name);
+///     }
+/// });
+///
+/// c.execute("CREATE TABLE users (name TEXT)")?;
+/// c.execute("BEGIN; INSERT INTO users VALUES ('Alice'); ROLLBACK")?;
+/// assert!(c.execute("SELECT * FROM missing").is_err());
+/// drop(c);
+///
+/// let events = events.lock().unwrap();
+/// assert_eq!(*events, ["commit", "begin", "rollback", "error", "close"]);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub trait Observer: Send {
+    /// Receive an event.
+    fn event(&mut self, event: Event<'_>);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(Event<'_>) + Send,
+{
+    #[inline]
+    fn event(&mut self, event: Event<'_>) {
+        self(event)
+    }
+}
+
+/// The boxed observer of a connection, which has a stable address so that it
+/// can be passed to hooks.
+pub(crate) type ObserverCell = RefCell<Box<dyn Observer>>;
+
+/// Send an event to the observer, ignoring re-entrant events.
+pub(crate) fn emit(observer: &ObserverCell, event: Event<'_>) {
+    if let Ok(mut observer) = observer.try_borrow_mut() {
+        observer.event(event);
+    }
+}
+
+/// Install hooks delivering events to the given observer.
+///
+/// # Safety
+///
+/// The observer must outlive the hooks, which are removed with
+/// [`uninstall`].
+pub(crate) unsafe fn install(handle: *mut ffi::sqlite3, observer: &ObserverCell) {
+    let data = observer as *const ObserverCell as *mut c_void;
+    ffi::sqlite3_commit_hook(handle, Some(commit_hook), data);
+    ffi::sqlite3_rollback_hook(handle, Some(rollback_hook), data);
+    ffi::sqlite3_trace_v2(
+        handle,
+        ffi::SQLITE_TRACE_STMT as c_uint,
+        Some(trace_hook),
+        data,
+    );
+}
+
+/// Remove hooks installed with [`install`].
+pub(crate) unsafe fn uninstall(handle: *mut ffi::sqlite3) {
+    ffi::sqlite3_commit_hook(handle, None, core::ptr::null_mut());
+    ffi::sqlite3_rollback_hook(handle, None, core::ptr::null_mut());
+    ffi::sqlite3_trace_v2(handle, 0, None, core::ptr::null_mut());
+}
+
+extern "C" fn commit_hook(data: *mut c_void) -> c_int {
+    utils::catch_unwind(0, || unsafe {
+        emit(&*(data as *const ObserverCell), Event::Commit);
+        0
+    })
+}
+
+extern "C" fn rollback_hook(data: *mut c_void) {
+    utils::catch_unwind((), || unsafe {
+        emit(&*(data as *const ObserverCell), Event::Rollback);
+    })
+}
+
+extern "C" fn trace_hook(
+    kind: c_uint,
+    data: *mut c_void,
+    stmt: *mut c_void,
+    sql: *mut c_void,
+) -> c_int {
+    if kind != ffi::SQLITE_TRACE_STMT as c_uint || sql.is_null() {
+        return 0;
+    }
+
+    utils::catch_unwind(0, || unsafe {
+        let sql = CStr::from_ptr(sql as *const c_char).to_bytes();
+
+        let event = match classify(sql) {
+            Some(Kind::Begin) => {
+                let handle = ffi::sqlite3_db_handle(stmt as *mut ffi::sqlite3_stmt);
+
+                if ffi::sqlite3_get_autocommit(handle) == 0 {
+                    return 0;
+                }
+
+                Event::Begin
+            }
+            Some(Kind::Checkpoint) => Event::Checkpoint,
+            None => return 0,
+        };
+
+        emit(&*(data as *const ObserverCell), event);
+        0
+    })
+}
+
+enum Kind {
+    Begin,
+    Checkpoint,
+}
+
+/// Classify a statement by its leading keywords.
+fn classify(sql: &[u8]) -> Option<Kind> {
+    let mut words = Words(sql);
+    let first = words.next()?;
+
+    if first.eq_ignore_ascii_case(b"BEGIN") || first.eq_ignore_ascii_case(b"SAVEPOINT") {
+        return Some(Kind::Begin);
+    }
+
+    if first.eq_ignore_ascii_case(b"PRAGMA")
+        && words.any(|w| w.eq_ignore_ascii_case(b"wal_checkpoint"))
+    {
+        return Some(Kind::Checkpoint);
+    }
+
+    None
+}
+
+/// Iterator over the words of a statement, skipping whitespace, punctuation
+/// and comments.
+struct Words<'a>(&'a [u8]);
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.0 {
+                [b'-', b'-', rest @ ..] => {
+                    let n = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+                    self.0 = &rest[n..];
+                }
+                [b'/', b'*', rest @ ..] => {
+                    let n = rest
+                        .windows(2)
+                        .position(|w| w == b"*/")
+                        .map_or(rest.len(), |n| n + 2);
+                    self.0 = &rest[n..];
+                }
+                [b, rest @ ..] if !(b.is_ascii_alphanumeric() || *b == b'_') => {
+                    self.0 = rest;
+                }
+                [] => return None,
+                _ => {
+                    let n = self
+                        .0
+                        .iter()
+                        .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+                        .unwrap_or(self.0.len());
+
+                    let (word, rest) = self.0.split_at(n);
+                    self.0 = rest;
+                    return Some(word);
+                }
+            }
+        }
+    }
+}
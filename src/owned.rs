@@ -0,0 +1,98 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::statement::Statement;
+
+/// A prepared statement stored together with the connection it was prepared
+/// from.
+///
+/// This makes it possible to return a statement from a constructor or store
+/// it in a struct without separately keeping track of its connection. It
+/// dereferences to [`Statement`], and the connection is available through
+/// [`OwnedStatement::connection`].
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{Connection, OwnedStatement, State};
+///
+/// struct Counter {
+///     next: OwnedStatement,
+/// }
+///
+/// impl Counter {
+///     fn new() -> sqlite_ll::Result<Self> {
+///         let c = Connection::open(":memory:")?;
+///         c.execute("CREATE TABLE counter (value INTEGER); INSERT INTO counter VALUES (0);")?;
+///         let next = OwnedStatement::new(c, "UPDATE counter SET value = value + 1 RETURNING value")?;
+///         Ok(Self { next })
+///     }
+///
+///     fn next(&mut self) -> sqlite_ll::Result<i64> {
+///         self.next.reset()?;
+///         assert_eq!(self.next.step()?, State::Row);
+///         self.next.read(0)
+///     }
+/// }
+///
+/// let mut counter = Counter::new()?;
+/// assert_eq!(counter.next()?, 1);
+/// assert_eq!(counter.next()?, 2);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub struct OwnedStatement {
+    // NB: The statement is declared first so that it's dropped before the
+    // connection.
+    statement: Statement,
+    connection: Connection,
+}
+
+impl OwnedStatement {
+    /// Prepare a statement and store it together with the connection.
+    pub fn new<T>(connection: Connection, statement: T) -> Result<Self>
+    where
+        T: AsRef<str>,
+    {
+        let statement = connection.prepare(statement)?;
+
+        Ok(Self {
+            statement,
+            connection,
+        })
+    }
+
+    /// Access the connection the statement was prepared from.
+    #[inline]
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Access the connection the statement was prepared from mutably.
+    #[inline]
+    pub fn connection_mut(&mut self) -> &mut Connection {
+        &mut self.connection
+    }
+
+    /// Split into the underlying connection and statement.
+    #[inline]
+    pub fn into_parts(self) -> (Connection, Statement) {
+        (self.connection, self.statement)
+    }
+}
+
+impl Deref for OwnedStatement {
+    type Target = Statement;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.statement
+    }
+}
+
+impl DerefMut for OwnedStatement {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.statement
+    }
+}
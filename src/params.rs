@@ -0,0 +1,116 @@
+use crate::error::Result;
+use crate::statement::{Bindable, Statement};
+use crate::value::Value;
+
+/// A full set of parameters which can be bound to a statement in one go.
+///
+/// Slices of values and tuples of [`Bindable`] values are bound to successive
+/// indexes starting at 1.
+///
+/// With the `derive` feature enabled, this can be derived for structs. Named
+/// fields are bound to the parameter with the same name prefixed with `:`,
+/// and unnamed fields are bound by position. This can be customized with the
+/// following field attributes:
+/// * `#[sqlite(rename = ":name")]` - bind the field to the parameter with the
+///   given name, including its prefix.
+/// * `#[sqlite(index = 1)]` - bind the field to the parameter at the given
+///   index.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use sqlite_ll::{Connection, Params};
+///
+/// #[derive(Params)]
+/// struct NewUser<'a> {
+///     name: &'a str,
+///     #[sqlite(rename = "@age")]
+///     age: i64,
+/// }
+///
+/// let c = Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+///
+/// let mut stmt = c.prepare("INSERT INTO users (name, age) VALUES (:name, @age)")?;
+/// stmt.cursor(NewUser { name: "Alice", age: 42 })?.next_row()?;
+/// stmt.cursor(NewUser { name: "Bob", age: 69 })?.next_row()?;
+///
+/// assert_eq!(c.change_count(), 1);
+/// # }
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+///
+/// Since parameters are indexed from 1, an index of 0 is rejected:
+///
+/// ```compile_fail
+/// #[derive(sqlite_ll::Params)]
+/// struct Positional {
+///     #[sqlite(index = 0)]
+///     id: i64,
+/// }
+/// ```
+pub trait Params {
+    /// Bind the parameters to the statement.
+    fn bind_params(self, statement: &mut Statement) -> Result<()>;
+}
+
+impl Params for () {
+    #[inline]
+    fn bind_params(self, _: &mut Statement) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Params for &[Value] {
+    #[inline]
+    fn bind_params(self, statement: &mut Statement) -> Result<()> {
+        for (i, value) in self.iter().enumerate() {
+            statement.bind(i + 1, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Params for &[Value; N] {
+    #[inline]
+    fn bind_params(self, statement: &mut Statement) -> Result<()> {
+        self[..].bind_params(statement)
+    }
+}
+
+impl Params for &Vec<Value> {
+    #[inline]
+    fn bind_params(self, statement: &mut Statement) -> Result<()> {
+        self[..].bind_params(statement)
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident $n:tt),*) => {
+        impl<$($ty,)*> Params for ($($ty,)*)
+        where
+            $($ty: Bindable,)*
+        {
+            #[inline]
+            fn bind_params(self, statement: &mut Statement) -> Result<()> {
+                $(statement.bind($n + 1, self.$n)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+tuple!(A 0);
+tuple!(A 0, B 1);
+tuple!(A 0, B 1, C 2);
+tuple!(A 0, B 1, C 2, D 3);
+tuple!(A 0, B 1, C 2, D 3, E 4);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -0,0 +1,121 @@
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+impl Bindable for &OsStr {
+    /// Bind as text if the string is valid UTF-8, and as a blob of its raw
+    /// bytes otherwise.
+    ///
+    /// On platforms other than Unix strings which aren't valid UTF-8 can't be
+    /// bound and result in an error.
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        if let Some(string) = self.to_str() {
+            return string.bind(statement, i);
+        }
+
+        os_str_to_bytes(self)?.bind(statement, i)
+    }
+}
+
+impl Bindable for OsString {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_os_str().bind(statement, i)
+    }
+}
+
+impl Bindable for &OsString {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_os_str().bind(statement, i)
+    }
+}
+
+impl Bindable for &Path {
+    /// Bind as text if the path is valid UTF-8, and as a blob of its raw
+    /// bytes otherwise.
+    ///
+    /// On platforms other than Unix paths which aren't valid UTF-8 can't be
+    /// bound and result in an error.
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_os_str().bind(statement, i)
+    }
+}
+
+impl Bindable for PathBuf {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_os_str().bind(statement, i)
+    }
+}
+
+impl Bindable for &PathBuf {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_os_str().bind(statement, i)
+    }
+}
+
+impl Readable for OsString {
+    /// Read from text, or from a blob of raw bytes.
+    ///
+    /// On platforms other than Unix blobs must be valid UTF-8.
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        match statement.column_type(i) {
+            Type::Text => Ok(OsString::from(statement.column_text(i)?)),
+            Type::Blob => bytes_to_os_string(statement.column_blob(i)),
+            _ => Err(Error::mismatch()),
+        }
+    }
+}
+
+impl Readable for PathBuf {
+    /// Read from text, or from a blob of raw bytes.
+    ///
+    /// On platforms other than Unix blobs must be valid UTF-8.
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(PathBuf::from(OsString::read(statement, i)?))
+    }
+}
+
+#[cfg(unix)]
+fn os_str_to_bytes(string: &OsStr) -> Result<&[u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(string.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn os_str_to_bytes(string: &OsStr) -> Result<&[u8]> {
+    match string.to_str() {
+        Some(string) => Ok(string.as_bytes()),
+        None => Err(not_utf8()),
+    }
+}
+
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: &[u8]) -> Result<OsString> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(OsStr::from_bytes(bytes).to_owned())
+}
+
+#[cfg(not(unix))]
+fn bytes_to_os_string(bytes: &[u8]) -> Result<OsString> {
+    match std::str::from_utf8(bytes) {
+        Ok(string) => Ok(OsString::from(string)),
+        Err(..) => Err(not_utf8()),
+    }
+}
+
+#[cfg(not(unix))]
+fn not_utf8() -> Error {
+    Error::new(
+        sqlite3_sys::SQLITE_MISMATCH,
+        Some("path is not valid UTF-8".into()),
+    )
+}
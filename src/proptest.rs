@@ -0,0 +1,247 @@
+//! Strategies for property testing with [proptest].
+//!
+//! These generate values, column types and small schemas, which together with
+//! [`round_trip`] and [`Schema::round_trip`] can be used to check that data
+//! survives being stored in and read back from a database.
+//!
+//! Generated floats are never `NaN` and generated text never contains `NUL`
+//! characters, since SQLite doesn't store those faithfully.
+//!
+//! [proptest]: https://docs.rs/proptest
+//!
+//! # Examples
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use sqlite_ll::Connection;
+//!
+//! proptest!(|(schema in sqlite_ll::proptest::schema())| {
+//!     let c = Connection::open(":memory:")?;
+//!     let rows = vec![schema.columns.iter().map(|_| sqlite_ll::Value::Null).collect::<Vec<_>>()];
+//!     prop_assert_eq!(schema.round_trip(&c, &rows)?, rows);
+//! });
+//! ```
+
+use ::proptest::collection::vec;
+use ::proptest::num::f64;
+use ::proptest::prelude::*;
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, State};
+use crate::utils;
+use crate::value::{Type, Value};
+
+/// Generate a [`Type`] which can be used for a column, which is any type
+/// except [`Type::Null`].
+pub fn column_type() -> impl Strategy<Value = Type> {
+    prop_oneof![
+        Just(Type::Blob),
+        Just(Type::Float),
+        Just(Type::Integer),
+        Just(Type::Text),
+    ]
+}
+
+/// Generate a non-empty list of column types.
+pub fn column_types() -> impl Strategy<Value = Vec<Type>> {
+    vec(column_type(), 1..8)
+}
+
+/// Generate a [`Value`] of any type.
+pub fn value() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        value_of(Type::Blob),
+        value_of(Type::Float),
+        value_of(Type::Integer),
+        value_of(Type::Text),
+        Just(Value::Null),
+    ]
+}
+
+/// Generate a [`Value`] of the given type.
+pub fn value_of(ty: Type) -> BoxedStrategy<Value> {
+    match ty {
+        Type::Blob => vec(any::<u8>(), 0..64).prop_map(Value::Blob).boxed(),
+        Type::Float => (f64::POSITIVE
+            | f64::NEGATIVE
+            | f64::NORMAL
+            | f64::SUBNORMAL
+            | f64::ZERO
+            | f64::INFINITE)
+            .prop_map(Value::Float)
+            .boxed(),
+        Type::Integer => any::<i64>().prop_map(Value::Integer).boxed(),
+        Type::Text => "[^\0]{0,32}".prop_map(Value::Text).boxed(),
+        Type::Null => Just(Value::Null).boxed(),
+    }
+}
+
+/// A column in a generated [`Schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Column {
+    /// The name of the column.
+    pub name: String,
+    /// The type of the column.
+    pub ty: Type,
+}
+
+/// A generated table with a name and a list of columns.
+///
+/// See [`schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schema {
+    /// The name of the table.
+    pub table: String,
+    /// The columns of the table.
+    pub columns: Vec<Column>,
+}
+
+impl Schema {
+    /// The statement which creates the table.
+    pub fn create_table(&self) -> String {
+        let mut sql = String::from("CREATE TABLE ");
+        utils::push_identifier(&mut sql, &self.table);
+        sql.push_str(" (");
+
+        for (n, column) in self.columns.iter().enumerate() {
+            if n > 0 {
+                sql.push_str(", ");
+            }
+
+            utils::push_identifier(&mut sql, &column.name);
+
+            sql.push_str(match column.ty {
+                Type::Blob => " BLOB",
+                Type::Float => " REAL",
+                Type::Integer => " INTEGER",
+                Type::Text => " TEXT",
+                Type::Null => "",
+            });
+        }
+
+        sql.push(')');
+        sql
+    }
+
+    /// The statement which inserts a row with one positional parameter per
+    /// column.
+    pub fn insert(&self) -> String {
+        let mut sql = String::from("INSERT INTO ");
+        utils::push_identifier(&mut sql, &self.table);
+        sql.push_str(" VALUES (");
+
+        for n in 0..self.columns.len() {
+            if n > 0 {
+                sql.push_str(", ");
+            }
+
+            sql.push('?');
+        }
+
+        sql.push(')');
+        sql
+    }
+
+    /// The statement which selects every column from the table, in the order
+    /// in which rows were inserted.
+    pub fn select(&self) -> String {
+        let mut sql = String::from("SELECT ");
+
+        for (n, column) in self.columns.iter().enumerate() {
+            if n > 0 {
+                sql.push_str(", ");
+            }
+
+            utils::push_identifier(&mut sql, &column.name);
+        }
+
+        sql.push_str(" FROM ");
+        utils::push_identifier(&mut sql, &self.table);
+        sql.push_str(" ORDER BY rowid");
+        sql
+    }
+
+    /// Generate rows for the table, where each value either matches the type
+    /// of its column or is `NULL`.
+    pub fn rows(&self, len: std::ops::Range<usize>) -> BoxedStrategy<Vec<Vec<Value>>> {
+        let row = self
+            .columns
+            .iter()
+            .map(|column| prop_oneof![4 => value_of(column.ty), 1 => Just(Value::Null)])
+            .collect::<Vec<_>>();
+
+        vec(row, len).boxed()
+    }
+
+    /// Create the table, insert the given rows into it, and read them back.
+    pub fn round_trip(&self, c: &Connection, rows: &[Vec<Value>]) -> Result<Vec<Vec<Value>>> {
+        c.execute(self.create_table())?;
+
+        let mut insert = c.prepare(self.insert())?;
+
+        for row in rows {
+            insert.reset()?;
+            insert.bind_values(row)?;
+
+            while let State::Row = insert.step()? {}
+        }
+
+        let mut select = c.prepare(self.select())?;
+        let mut output = Vec::with_capacity(rows.len());
+
+        while let State::Row = select.step()? {
+            let mut row = Vec::new();
+            select.read_row(&mut row)?;
+            output.push(row);
+        }
+
+        Ok(output)
+    }
+}
+
+/// Generate a small [`Schema`] with a random table name and columns.
+///
+/// Names are random identifiers which are quoted when used, so they might
+/// coincide with SQL keywords.
+pub fn schema() -> impl Strategy<Value = Schema> {
+    let column = ("[a-z][a-z0-9_]{0,7}", column_type());
+
+    ("[a-z][a-z0-9_]{0,7}", vec(column, 1..8)).prop_map(|(table, columns)| Schema {
+        table,
+        columns: columns
+            .into_iter()
+            .enumerate()
+            .map(|(n, (name, ty))| Column {
+                // Suffix names with their position to make them unique.
+                name: format!("{name}_{n}"),
+                ty,
+            })
+            .collect(),
+    })
+}
+
+/// Bind a value to a statement in an in-memory database and read it back.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// proptest!(|(value in any::<u32>())| {
+///     prop_assert_eq!(sqlite_ll::proptest::round_trip(value)?, value);
+/// });
+/// ```
+pub fn round_trip<T>(value: T) -> Result<T>
+where
+    T: Bindable + Readable,
+{
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?")?;
+    stmt.bind(1, value)?;
+
+    match stmt.step()? {
+        State::Row => stmt.read(0),
+        State::Done => Err(Error::custom("statement produced no rows")),
+    }
+}
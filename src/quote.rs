@@ -0,0 +1,267 @@
+use core::fmt;
+
+use libc::{c_char, c_void};
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::utils;
+
+/// Quote a string as an SQL text literal, using the `%Q` conversion of
+/// `sqlite3_mprintf`.
+///
+/// The string is surrounded by single quotes and any single quotes in it are
+/// doubled. This is intended for the rare cases where a value can't be bound
+/// as a parameter, like the arguments of some pragmas.
+///
+/// Errors with [`Code::MISUSE`] if the string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::quote_string("it's")?, "'it''s'");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn quote_string(string: &str) -> Result<String> {
+    quote_nullable(Some(string))
+}
+
+/// Quote an optional string as an SQL text literal, or `NULL` if it is
+/// `None`, using the `%Q` conversion of `sqlite3_mprintf`.
+///
+/// See [`quote_string`].
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::quote_nullable(Some("it's"))?, "'it''s'");
+/// assert_eq!(sqlite_ll::quote_nullable(None)?, "NULL");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn quote_nullable(string: Option<&str>) -> Result<String> {
+    let string = string.map(utils::string_to_cstring).transpose()?;
+    let pointer = string.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+    unsafe { mprintf(b"%Q\0", pointer) }
+}
+
+/// Quote a string as an SQL identifier, using the `%w` conversion of
+/// `sqlite3_mprintf`.
+///
+/// The string is surrounded by double quotes and any double quotes in it are
+/// doubled, so that it can be used as the name of a table, column or other
+/// schema object even if it is a keyword or contains special characters.
+///
+/// Errors with [`Code::MISUSE`] if the string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::quote_identifier("order")?, "\"order\"");
+/// assert_eq!(sqlite_ll::quote_identifier("a \"b\"")?, "\"a \"\"b\"\"\"");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn quote_identifier(identifier: &str) -> Result<String> {
+    let identifier = utils::string_to_cstring(identifier)?;
+    unsafe { mprintf(b"\"%w\"\0", identifier.as_ptr()) }
+}
+
+/// Encode bytes as an SQL blob literal such as `X'CAFE'`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::quote_blob(&[0xca, 0xfe]), "X'CAFE'");
+/// assert_eq!(sqlite_ll::quote_blob(&[]), "X''");
+/// ```
+pub fn quote_blob(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2 + 3);
+    // Writing to a string can't fail.
+    _ = write_blob(&mut out, bytes);
+    out
+}
+
+/// Decode an SQL blob literal such as `X'CAFE'` into bytes.
+///
+/// Like in SQLite the prefix can be either `X` or `x`, and the hexadecimal
+/// digits can be in either case. Surrounding whitespace is ignored.
+///
+/// Errors with [`Code::ERROR`] if the string isn't a valid blob literal.
+///
+/// [`Code::ERROR`]: crate::Code::ERROR
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::unquote_blob("X'CAFE'")?, [0xca, 0xfe]);
+/// assert_eq!(sqlite_ll::unquote_blob("x'cafe'")?, [0xca, 0xfe]);
+/// assert!(sqlite_ll::unquote_blob("X'CAF'").is_err());
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn unquote_blob(literal: &str) -> Result<Vec<u8>> {
+    let trimmed = literal.trim();
+
+    let blob = trimmed
+        .strip_prefix(['X', 'x'])
+        .and_then(|s| s.strip_prefix('\''))
+        .and_then(|s| s.strip_suffix('\''))
+        .and_then(parse_hex);
+
+    match blob {
+        Some(blob) => Ok(blob),
+        None => Err(Error::new(
+            ffi::SQLITE_ERROR,
+            Some(format!("`{literal}` is not a valid blob literal").into()),
+        )),
+    }
+}
+
+/// Encode bytes as standard base64 with padding.
+///
+/// This is the same encoding as is used by the `base64()` function of the
+/// SQLite shell, and can be used when blobs need to be embedded in text
+/// formats such as JSON.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::encode_base64(b"sqlite"), "c3FsaXRl");
+/// assert_eq!(sqlite_ll::encode_base64(b"ll"), "bGw=");
+/// ```
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+/// Decode standard base64, with or without padding.
+///
+/// Errors with [`Code::ERROR`] if the string isn't valid base64.
+///
+/// [`Code::ERROR`]: crate::Code::ERROR
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sqlite_ll::decode_base64("c3FsaXRl")?, b"sqlite");
+/// assert_eq!(sqlite_ll::decode_base64("bGw=")?, b"ll");
+/// assert_eq!(sqlite_ll::decode_base64("bGw")?, b"ll");
+/// assert!(sqlite_ll::decode_base64("b").is_err());
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn decode_base64(string: &str) -> Result<Vec<u8>> {
+    let invalid = || {
+        Error::new(
+            ffi::SQLITE_ERROR,
+            Some(format!("`{string}` is not valid base64").into()),
+        )
+    };
+
+    let trimmed = string.trim_end_matches('=');
+
+    if string.len() - trimmed.len() > 2 || trimmed.len() % 4 == 1 {
+        return Err(invalid());
+    }
+
+    let mut out = Vec::with_capacity(trimmed.len() * 3 / 4);
+    let mut n = 0u32;
+
+    for (i, b) in trimmed.bytes().enumerate() {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(invalid()),
+        };
+
+        n = n << 6 | u32::from(value);
+
+        if i % 4 == 3 {
+            out.extend_from_slice(&n.to_be_bytes()[1..]);
+            n = 0;
+        }
+    }
+
+    match trimmed.len() % 4 {
+        2 => out.push((n >> 4) as u8),
+        3 => out.extend_from_slice(&((n >> 2) as u16).to_be_bytes()),
+        _ => {}
+    }
+
+    Ok(out)
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Write bytes as an SQL blob literal.
+pub(crate) fn write_blob<W>(out: &mut W, bytes: &[u8]) -> fmt::Result
+where
+    W: ?Sized + fmt::Write,
+{
+    out.write_str("X'")?;
+
+    for b in bytes {
+        write!(out, "{b:02X}")?;
+    }
+
+    out.write_str("'")
+}
+
+/// Parse hexadecimal digits into bytes.
+pub(crate) fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            // `from_str_radix` accepts a leading `+`, which SQLite doesn't.
+            if !pair.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+
+            u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()
+        })
+        .collect()
+}
+
+/// Format a single string argument with `sqlite3_mprintf`.
+///
+/// # Safety
+///
+/// The format must be `NUL`-terminated and consume exactly one string
+/// argument.
+unsafe fn mprintf(format: &[u8], argument: *const c_char) -> Result<String> {
+    let pointer = ffi::sqlite3_mprintf(format.as_ptr() as *const c_char, argument);
+
+    if pointer.is_null() {
+        return Err(Error::from_code(ffi::SQLITE_NOMEM));
+    }
+
+    let result = utils::cstr_to_str(pointer).map(str::to_owned);
+    ffi::sqlite3_free(pointer as *mut c_void);
+    result
+}
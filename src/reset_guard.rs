@@ -0,0 +1,45 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::statement::Statement;
+
+/// A statement which is reset when the guard is dropped.
+///
+/// This dereferences to [`Statement`], and ensures that the statement is
+/// returned to a reusable state even if processing its rows stops early,
+/// such as when breaking out of a loop or returning an error with `?`.
+/// Bindings are retained, just like with [`Statement::reset`].
+///
+/// See [`Statement::run`].
+pub struct ResetGuard<'a> {
+    statement: &'a mut Statement,
+}
+
+impl<'a> ResetGuard<'a> {
+    #[inline]
+    pub(crate) fn new(statement: &'a mut Statement) -> Self {
+        Self { statement }
+    }
+}
+
+impl Deref for ResetGuard<'_> {
+    type Target = Statement;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.statement
+    }
+}
+
+impl DerefMut for ResetGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.statement
+    }
+}
+
+impl Drop for ResetGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.statement.reset();
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use sqlite3_sys as ffi;
+
+use crate::error::{Error, Result};
+use crate::statement::{Readable, Statement};
+use crate::value::Value;
+
+/// A row produced by [`Statement::next_row`].
+///
+/// The row borrows the statement it was produced from, which ensures that it
+/// can't be read from after the statement has been stepped again or reset.
+pub struct Row<'a> {
+    statement: &'a Statement,
+}
+
+impl<'a> Row<'a> {
+    #[inline]
+    pub(crate) fn new(statement: &'a Statement) -> Self {
+        Self { statement }
+    }
+
+    /// Return the number of columns in the row.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.statement.column_count()
+    }
+
+    /// Test if the row has no columns.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the name of a column.
+    ///
+    /// The first column has index 0.
+    #[inline]
+    pub fn column_name(&self, i: usize) -> Result<&'a str> {
+        self.check(i)?;
+        self.statement.column_name(i)
+    }
+
+    /// Read a value from a column.
+    ///
+    /// The first column has index 0. Reading a column which is out of range
+    /// results in an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name, age FROM users")?;
+    ///
+    /// while let Some(row) = stmt.next_row()? {
+    ///     assert_eq!(row.get::<String>(0)?, "Alice");
+    ///     assert_eq!(row.get::<i64>(1)?, 42);
+    ///     assert!(row.get::<i64>(2).is_err());
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn get<T>(&self, i: usize) -> Result<T>
+    where
+        T: Readable,
+    {
+        self.check(i)?;
+        T::read(self.statement, i)
+    }
+
+    /// Read a value from the column with the given name.
+    ///
+    /// If no column with the given name exists, the returned error lists the
+    /// available columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name, age FROM users")?;
+    ///
+    /// while let Some(row) = stmt.next_row()? {
+    ///     assert_eq!(row.get_by_name::<i64>("age")?, 42);
+    ///     assert!(row.get_by_name::<i64>("email").is_err());
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn get_by_name<T>(&self, name: &str) -> Result<T>
+    where
+        T: Readable,
+    {
+        let i = self.statement.column_position(name)?;
+        T::read(self.statement, i)
+    }
+
+    /// Deserialize the row into `T` using [serde].
+    ///
+    /// See [`Statement::de`] for details.
+    ///
+    /// [serde]: https://serde.rs
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn de<T>(&self) -> Result<T>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        self.statement.de()
+    }
+
+    /// Read every column of the row into a map from column names to values.
+    ///
+    /// If multiple columns have the same name, the value of the last one is
+    /// kept. Use [`Row::to_pairs`] to preserve column order and duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT * FROM users")?;
+    ///
+    /// while let Some(row) = stmt.next_row()? {
+    ///     let map = row.to_map()?;
+    ///     assert_eq!(map["name"], Value::Text(String::from("Alice")));
+    ///     assert_eq!(map["age"], Value::Integer(42));
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn to_map(&self) -> Result<HashMap<String, Value>> {
+        FromRow::from_row(self)
+    }
+
+    /// Read every column of the row into a vector of column names and values,
+    /// in column order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT * FROM users")?;
+    ///
+    /// while let Some(row) = stmt.next_row()? {
+    ///     let pairs = row.to_pairs()?;
+    ///     assert_eq!(pairs[0], (String::from("name"), Value::Text(String::from("Alice"))));
+    ///     assert_eq!(pairs[1], (String::from("age"), Value::Integer(42)));
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn to_pairs(&self) -> Result<Vec<(String, Value)>> {
+        FromRow::from_row(self)
+    }
+
+    /// Check that the given column index is in range.
+    #[inline]
+    fn check(&self, i: usize) -> Result<()> {
+        if i >= self.len() {
+            return Err(Error::from_code(ffi::SQLITE_RANGE));
+        }
+
+        Ok(())
+    }
+}
+
+/// A type which can be constructed from a [`Row`].
+///
+/// This is implemented for tuples of [`Readable`] types, which reads each
+/// element from successive columns. To read every column dynamically it's
+/// implemented for `Vec<Value>`, and for `HashMap<String, Value>` and
+/// `Vec<(String, Value)>` which also include column names.
+///
+/// With the `derive` feature enabled, this can be derived for structs. Named
+/// fields are read from the column with the same name, and unnamed fields are
+/// read by position. This can be customized with the following field
+/// attributes:
+/// * `#[sqlite(rename = "name")]` - read the field from the column with the
+///   given name.
+/// * `#[sqlite(index = 1)]` - read the field from the column at the given
+///   index.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use sqlite_ll::{Connection, FromRow};
+///
+/// #[derive(Debug, PartialEq, FromRow)]
+/// struct User {
+///     name: String,
+///     #[sqlite(rename = "user_age")]
+///     age: i64,
+///     email: Option<String>,
+/// }
+///
+/// let c = Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, user_age INTEGER, email TEXT); INSERT INTO users VALUES ('Alice', 42, NULL);")?;
+///
+/// let mut stmt = c.prepare("SELECT * FROM users")?;
+/// let users = stmt.iter::<User>().collect::<sqlite_ll::Result<Vec<_>>>()?;
+///
+/// assert_eq!(users, [User { name: String::from("Alice"), age: 42, email: None }]);
+/// # }
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+///
+/// [`Readable`]: crate::Readable
+pub trait FromRow: Sized {
+    /// Construct a value from a row.
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
+impl FromRow for Vec<Value> {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        (0..row.len()).map(|i| row.get(i)).collect()
+    }
+}
+
+impl FromRow for HashMap<String, Value> {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        let mut map = HashMap::with_capacity(row.len());
+
+        for i in 0..row.len() {
+            map.insert(row.column_name(i)?.to_owned(), row.get(i)?);
+        }
+
+        Ok(map)
+    }
+}
+
+impl FromRow for Vec<(String, Value)> {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        (0..row.len())
+            .map(|i| Ok((row.column_name(i)?.to_owned(), row.get(i)?)))
+            .collect()
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident $n:tt),*) => {
+        impl<$($ty,)*> FromRow for ($($ty,)*)
+        where
+            $($ty: Readable,)*
+        {
+            #[inline]
+            fn from_row(row: &Row<'_>) -> Result<Self> {
+                Ok(($(row.get::<$ty>($n)?,)*))
+            }
+        }
+    };
+}
+
+tuple!(A 0);
+tuple!(A 0, B 1);
+tuple!(A 0, B 1, C 2);
+tuple!(A 0, B 1, C 2, D 3);
+tuple!(A 0, B 1, C 2, D 3, E 4);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -0,0 +1,105 @@
+use core::slice;
+
+use crate::value::Value;
+
+/// A buffer of rows filled by [`Statement::step_batch`].
+///
+/// Values are stored in a single contiguous allocation, which is reused when
+/// the buffer is filled again.
+///
+/// [`Statement::step_batch`]: crate::Statement::step_batch
+#[derive(Default)]
+pub struct RowBuffer {
+    values: Vec<Value>,
+    columns: usize,
+    rows: usize,
+}
+
+impl RowBuffer {
+    /// Construct a new empty buffer.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of rows in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    /// Test if the buffer contains no rows.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Return the number of columns in each row.
+    #[inline]
+    pub fn column_count(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the values of the row at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{RowBuffer, Value};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT 1, 'a' UNION ALL SELECT 2, 'b'")?;
+    ///
+    /// let mut buf = RowBuffer::new();
+    /// assert_eq!(stmt.step_batch(10, &mut buf)?, 2);
+    ///
+    /// assert_eq!(buf.get(1), Some(&[Value::Integer(2), Value::Text(String::from("b"))][..]));
+    /// assert_eq!(buf.get(2), None);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn get(&self, row: usize) -> Option<&[Value]> {
+        if row >= self.rows {
+            return None;
+        }
+
+        let start = row * self.columns;
+        Some(&self.values[start..start + self.columns])
+    }
+
+    /// Iterate over the rows in the buffer.
+    #[inline]
+    pub fn iter(&self) -> slice::ChunksExact<'_, Value> {
+        self.values[..self.rows * self.columns].chunks_exact(self.columns.max(1))
+    }
+
+    /// Clear the buffer and prepare it for rows with the given number of
+    /// columns.
+    pub(crate) fn clear(&mut self, columns: usize) {
+        self.columns = columns;
+        self.rows = 0;
+    }
+
+    /// Add a row to the buffer, returning its values to be overwritten.
+    pub(crate) fn push_row(&mut self) -> &mut [Value] {
+        let start = self.rows * self.columns;
+        let end = start + self.columns;
+
+        if self.values.len() < end {
+            self.values.resize(end, Value::Null);
+        }
+
+        self.rows += 1;
+        &mut self.values[start..end]
+    }
+}
+
+impl<'a> IntoIterator for &'a RowBuffer {
+    type Item = &'a [Value];
+    type IntoIter = slice::ChunksExact<'a, Value>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,627 @@
+use core::fmt;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::error::{Error, Result};
+use crate::statement::Statement;
+use crate::value::{Type, Value};
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::custom(message)
+    }
+}
+
+/// Construct the error raised for values which can't be bound.
+fn unsupported(what: &str) -> Error {
+    Error::custom(format_args!("{what} can't be bound to a parameter"))
+}
+
+/// Construct the name of a parameter from a field or map key, adding the `:`
+/// prefix unless the name already has a parameter prefix.
+fn parameter_name(name: &str) -> String {
+    match name.as_bytes().first() {
+        Some(b':' | b'@' | b'$') => name.to_owned(),
+        _ => format!(":{name}"),
+    }
+}
+
+/// Serializer binding the fields of a struct or the entries of a map to the
+/// named parameters of a statement.
+pub(crate) struct ParamsSerializer<'a> {
+    statement: &'a mut Statement,
+}
+
+impl<'a> ParamsSerializer<'a> {
+    #[inline]
+    pub(crate) fn new(statement: &'a mut Statement) -> Self {
+        Self { statement }
+    }
+
+    #[inline]
+    fn bind<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let name = parameter_name(name);
+
+        let index = match self.statement.parameter_index(&name)? {
+            Some(index) => index,
+            None => return Err(Error::custom(format_args!("no parameter named `{name}`"))),
+        };
+
+        value.serialize(ValueSerializer {
+            statement: self.statement,
+            index,
+        })
+    }
+}
+
+macro_rules! expected_struct {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            #[inline]
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok> {
+                Err(Error::custom("only structs and maps can be bound to named parameters"))
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for ParamsSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = MapParams<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    expected_struct! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported("an enum"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported("a sequence"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported("a tuple"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported("a tuple struct"))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported("an enum"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(MapParams {
+            params: self,
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported("an enum"))
+    }
+}
+
+impl ser::SerializeStruct for ParamsSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.bind(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+}
+
+/// Serializer binding the entries of a map.
+pub(crate) struct MapParams<'a> {
+    params: ParamsSerializer<'a>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapParams<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(Error::custom("map value serialized without a key")),
+        };
+
+        self.params.bind(&key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+}
+
+/// Serializer for map keys, which must be strings.
+struct KeySerializer;
+
+macro_rules! expected_string {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            #[inline]
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok> {
+                Err(unsupported("a map key which is not a string"))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    expected_string! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Self::Ok> {
+        Ok(value.to_string())
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        Ok(value.to_owned())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Ok(variant.to_owned())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct> {
+        Err(unsupported("a map key which is not a string"))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported("a map key which is not a string"))
+    }
+}
+
+/// Serializer binding a single value to the parameter at the given index.
+struct ValueSerializer<'a> {
+    statement: &'a mut Statement,
+    index: usize,
+}
+
+impl ser::Serializer for ValueSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value)
+    }
+
+    #[inline]
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as i64)
+    }
+
+    #[inline]
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok> {
+        match i64::try_from(value) {
+            Ok(value) => self.statement.bind(self.index, value),
+            Err(..) => Err(Error::custom(format_args!(
+                "{value} is out of range for a 64-bit signed integer"
+            ))),
+        }
+    }
+
+    #[inline]
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value as f64)
+    }
+
+    #[inline]
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value)
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Self::Ok> {
+        let mut buf = [0; 4];
+        self.statement
+            .bind(self.index, &*value.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value)
+    }
+
+    #[inline]
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok> {
+        self.statement.bind(self.index, value)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok> {
+        self.statement.bind(self.index, ())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        self.statement.bind(self.index, ())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok> {
+        self.statement.bind(self.index, ())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        self.statement.bind(self.index, variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported("a nested sequence"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported("a nested tuple"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported("a nested tuple struct"))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(unsupported("a nested map"))
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct> {
+        Err(unsupported("a nested struct"))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported("an enum variant with data"))
+    }
+}
+
+impl Serialize for Value {
+    /// Serialize as the value it contains without a tag, with `NULL` as a
+    /// unit.
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Value::Blob(value) => serializer.serialize_bytes(value),
+            Value::Float(value) => serializer.serialize_f64(*value),
+            Value::Integer(value) => serializer.serialize_i64(*value),
+            Value::Text(value) => serializer.serialize_str(value),
+            Value::Null => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl Serialize for Type {
+    /// Serialize as a unit variant.
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let (index, name) = match self {
+            Type::Blob => (0, "Blob"),
+            Type::Float => (1, "Float"),
+            Type::Integer => (2, "Integer"),
+            Type::Text => (3, "Text"),
+            Type::Null => (4, "Null"),
+        };
+
+        serializer.serialize_unit_variant("Type", index, name)
+    }
+}
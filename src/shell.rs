@@ -0,0 +1,205 @@
+//! A minimal interactive shell which can be embedded in applications.
+//!
+//! The [`Shell`] accepts input one line at a time, like the SQLite command
+//! line shell, and returns the output to display. Statements can span multiple
+//! lines and are executed once they are complete. Lines starting with a `.`
+//! are interpreted as dot-commands, see `.help` for the available commands.
+//!
+//! Reading input and displaying output is left to the embedder, which makes it
+//! possible to expose the shell over any kind of transport, such as a
+//! diagnostics endpoint. See the `shell` example for a shell reading from
+//! standard input.
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::shell::Shell;
+//!
+//! let c = sqlite_ll::Connection::open(":memory:")?;
+//! let mut shell = Shell::new(&c);
+//!
+//! assert_eq!(shell.input("CREATE TABLE users (name TEXT);")?, "");
+//! assert_eq!(shell.input("INSERT INTO users")?, "");
+//! assert!(shell.is_pending());
+//! assert_eq!(shell.input("VALUES ('Alice');")?, "");
+//! assert_eq!(shell.input(".tables")?, "users\n");
+//!
+//! assert_eq!(shell.input("SELECT name FROM users;")?, "\
+//! +-------+
+//! | name  |
+//! +-------+
+//! | Alice |
+//! +-------+
+//! ");
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::{State, Statement};
+use crate::strings::is_complete;
+use crate::table::{TableFormat, TableStyle};
+
+const HELP: &str = "\
+.help                  Show this message
+.indexes ?TABLE?       Show the names of indexes
+.mode ascii|markdown   Set the output mode
+.nullvalue TEXT        Use TEXT in place of NULL values
+.schema ?TABLE?        Show the CREATE statements
+.tables                List the names of tables
+";
+
+/// An embeddable interactive shell.
+///
+/// See the [module level documentation][self] for more.
+pub struct Shell<'a> {
+    connection: &'a Connection,
+    buffer: String,
+    format: TableFormat,
+}
+
+impl<'a> Shell<'a> {
+    /// Construct a new shell for the given connection.
+    pub fn new(connection: &'a Connection) -> Self {
+        Self {
+            connection,
+            buffer: String::new(),
+            format: TableFormat::new(),
+        }
+    }
+
+    /// Test if the shell is waiting for more input to complete a statement.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Return the prompt to display before reading the next line.
+    #[inline]
+    pub fn prompt(&self) -> &'static str {
+        if self.is_pending() {
+            "   ...> "
+        } else {
+            "sqlite> "
+        }
+    }
+
+    /// Process a line of input, returning the output to display.
+    ///
+    /// The output is empty if the line didn't complete a statement, or if the
+    /// completed statements didn't produce any rows. If an error occurs the
+    /// pending input is discarded.
+    pub fn input(&mut self, line: &str) -> Result<String> {
+        if self.buffer.is_empty() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() {
+                return Ok(String::new());
+            }
+
+            if let Some(command) = trimmed.strip_prefix('.') {
+                return self.command(command);
+            }
+        }
+
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+
+        match is_complete(&self.buffer) {
+            Ok(true) => {}
+            Ok(false) => return Ok(String::new()),
+            Err(error) => {
+                self.buffer.clear();
+                return Err(error);
+            }
+        }
+
+        let sql = std::mem::take(&mut self.buffer);
+        self.execute(&sql)
+    }
+
+    /// Execute the given statements, returning their output.
+    fn execute(&self, sql: &str) -> Result<String> {
+        let mut out = String::new();
+        let mut rest = sql;
+
+        while let (Some(mut statement), tail) =
+            Statement::prepare_next(self.connection.as_raw(), rest)?
+        {
+            if statement.column_count() > 0 {
+                out.push_str(&self.format.format(&mut statement)?);
+            } else {
+                while let State::Row = statement.step()? {}
+            }
+
+            rest = tail;
+        }
+
+        Ok(out)
+    }
+
+    /// Run a dot-command.
+    fn command(&mut self, command: &str) -> Result<String> {
+        let mut args = command.split_whitespace();
+        let name = args.next().unwrap_or_default();
+        let arg = args.next();
+
+        match (name, arg) {
+            ("help", None) => Ok(HELP.to_owned()),
+            ("tables", None) => self.names("table", None),
+            ("indexes", table) => self.names("index", table),
+            ("schema", table) => {
+                let mut stmt = self.connection.prepare(
+                    "SELECT sql || ';' FROM sqlite_schema \
+                     WHERE sql IS NOT NULL AND (?1 IS NULL OR tbl_name = ?1) \
+                     ORDER BY tbl_name, type DESC, name",
+                )?;
+
+                stmt.bind(1, table)?;
+                lines(&mut stmt)
+            }
+            ("mode", Some(mode)) => {
+                let style = match mode {
+                    "ascii" | "table" => TableStyle::Ascii,
+                    "markdown" => TableStyle::Markdown,
+                    _ => return Err(Error::custom(format!("unknown mode `{mode}`"))),
+                };
+
+                self.format = self.format.clone().style(style);
+                Ok(String::new())
+            }
+            ("nullvalue", Some(null)) => {
+                self.format = self.format.clone().null(null);
+                Ok(String::new())
+            }
+            _ => Err(Error::custom(format!(
+                "unknown command or invalid arguments `.{command}`, see `.help`"
+            ))),
+        }
+    }
+
+    /// List the names of schema objects of the given type.
+    fn names(&self, ty: &str, table: Option<&str>) -> Result<String> {
+        let mut stmt = self.connection.prepare(
+            "SELECT name FROM sqlite_schema \
+             WHERE type = ?1 AND name NOT LIKE 'sqlite_%' AND (?2 IS NULL OR tbl_name = ?2) \
+             ORDER BY name",
+        )?;
+
+        stmt.bind(1, ty)?;
+        stmt.bind(2, table)?;
+        lines(&mut stmt)
+    }
+}
+
+/// Collect the first column of every row as lines.
+fn lines(stmt: &mut Statement) -> Result<String> {
+    let mut out = String::new();
+
+    while let State::Row = stmt.step()? {
+        out.push_str(stmt.read_str(0)?);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
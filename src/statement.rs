@@ -1,26 +1,72 @@
-use core::mem::{transmute, MaybeUninit};
+use core::cell::{Cell, RefCell};
+use core::mem::MaybeUninit;
 use core::ptr;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::rc::Rc;
+use std::sync::Arc;
 
-use libc::{c_char, c_double, c_int};
+use libc::{c_char, c_double, c_int, c_uint, c_void};
 use sqlite3_sys as ffi;
 
-use crate::error::{Error, Result};
+use crate::column_value::ColumnValue;
+use crate::columns::Columns;
+use crate::connection;
+use crate::cursor::Cursor;
+use crate::decltype::{self, DeclaredValue};
+use crate::error::{Code, Error, Result};
+use crate::iter::{ColumnIter, Iter};
+use crate::params::Params;
+use crate::reset_guard::ResetGuard;
+use crate::row::{FromRow, Row};
+use crate::row_buffer::RowBuffer;
+use crate::sys;
 use crate::utils;
-use crate::value::{Type, Value};
-
-// https://sqlite.org/c3ref/c_static.html
-macro_rules! transient(
-    () => {
-        transmute::<*const libc::c_void, Option<ffi::sqlite3_callback>>(
-            !0 as *const libc::c_void
-        )
-    };
-);
+use crate::value::{Type, Value, ValueRef};
 
 /// A prepared statement.
-#[repr(transparent)]
+///
+/// A statement doesn't borrow the [`Connection`] it was prepared from. The
+/// connection is closed using `sqlite3_close_v2`, which defers closing the
+/// underlying database handle until every statement prepared from it has been
+/// finalized. A statement can therefore never observe a closed connection,
+/// and using it after the [`Connection`] has been dropped is well-defined.
+///
+/// [`Connection`]: crate::Connection
 pub struct Statement {
     raw: ptr::NonNull<ffi::sqlite3_stmt>,
+    /// Lazily built lookup table from column names to indexes.
+    column_index: RefCell<Option<ColumnIndex>>,
+    /// Lazily built snapshot of column metadata, along with the number of
+    /// times the statement had been re-prepared when it was built.
+    columns: RefCell<Option<(c_int, Columns)>>,
+    /// Which parameters have been bound since the bindings were last cleared,
+    /// where the first element corresponds to the parameter with index 1.
+    bound: Vec<bool>,
+    /// Buffers bound through [`Statement::bind_owned`] and
+    /// [`Statement::bind_owned_blob`], which SQLite refers to without copying
+    /// until the parameter is rebound or the statement is finalized. Indexed
+    /// like `bound`.
+    owned: Vec<Option<Vec<u8>>>,
+    /// The flags the statement was prepared with, which are reused if it has
+    /// to be re-prepared.
+    flags: c_uint,
+    /// The budget of virtual machine operations set through
+    /// [`Statement::set_op_budget`].
+    op_budget: Option<usize>,
+}
+
+/// Cached lookup table from column names to indexes.
+struct ColumnIndex {
+    /// The number of times the statement had been re-prepared when the table
+    /// was built.
+    reprepared: c_int,
+    map: HashMap<Box<str>, usize>,
 }
 
 /// A prepared statement is `Send`.
@@ -36,6 +82,46 @@ pub enum State {
 }
 
 /// A type suitable for binding to a prepared statement.
+///
+/// With the `derive` feature enabled, this can be derived for fieldless enums
+/// together with [`Readable`]. By default variants are stored as their integer
+/// discriminant, which can be customized with the following attributes:
+/// * `#[sqlite(text)]` - on the enum, store variants as text using their name.
+/// * `#[sqlite(rename = "name")]` - on a variant, use the given name when it is
+///   stored as text.
+///
+/// Reading a value which doesn't correspond to any variant results in an
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use sqlite_ll::{Bindable, Connection, Readable, State};
+///
+/// #[derive(Debug, PartialEq, Bindable, Readable)]
+/// #[sqlite(text)]
+/// enum Role {
+///     Admin,
+///     #[sqlite(rename = "regular")]
+///     User,
+/// }
+///
+/// let c = Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, role TEXT)")?;
+///
+/// let mut stmt = c.prepare("INSERT INTO users (name, role) VALUES (?, ?)")?;
+/// stmt.bind(1, "Alice")?;
+/// stmt.bind(2, Role::User)?;
+/// assert_eq!(stmt.step()?, State::Done);
+///
+/// let mut stmt = c.prepare("SELECT role FROM users")?;
+/// assert_eq!(stmt.step()?, State::Row);
+/// assert_eq!(stmt.read::<String>(0)?, "regular");
+/// assert_eq!(stmt.read::<Role>(0)?, Role::User);
+/// # }
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
 pub trait Bindable {
     /// Bind to a parameter.
     ///
@@ -44,6 +130,9 @@ pub trait Bindable {
 }
 
 /// A type suitable for reading from a prepared statement.
+///
+/// With the `derive` feature enabled, this can be derived for fieldless enums.
+/// See [`Bindable`] for details.
 pub trait Readable: Sized {
     /// Read from a column.
     ///
@@ -58,156 +147,1935 @@ impl Statement {
     where
         T: AsRef<str>,
     {
+        Self::new_with_flags(handle, statement.as_ref(), 0)
+    }
+
+    /// Construct a new statement using the given `SQLITE_PREPARE_*` flags.
+    pub(crate) fn new_with_flags(
+        handle: *mut ffi::sqlite3,
+        statement: &str,
+        flags: c_uint,
+    ) -> Result<Statement> {
+        match Self::prepare_next_with_flags(handle, statement, flags)? {
+            (Some(statement), _) => Ok(statement),
+            (None, _) => Err(Error::from_code(ffi::SQLITE_MISUSE)),
+        }
+    }
+
+    /// Prepare the first statement in the given SQL, returning it if present
+    /// together with the remaining unprepared SQL.
+    ///
+    /// The statement is `None` if the input only consisted of whitespace or
+    /// comments.
+    #[inline]
+    pub(crate) fn prepare_next(
+        handle: *mut ffi::sqlite3,
+        statement: &str,
+    ) -> Result<(Option<Statement>, &str)> {
+        Self::prepare_next_with_flags(handle, statement, 0)
+    }
+
+    fn prepare_next_with_flags(
+        handle: *mut ffi::sqlite3,
+        statement: &str,
+        flags: c_uint,
+    ) -> Result<(Option<Statement>, &str)> {
         let mut raw = MaybeUninit::uninit();
-        let statement = statement.as_ref();
+        let mut tail = MaybeUninit::uninit();
 
-        unsafe {
-            sqlite3_try! {
+        let raw = unsafe {
+            let code = sys::sqlite3_prepare_v3(
                 handle,
-                ffi::sqlite3_prepare_v2(
-                    handle,
-                    statement.as_bytes().as_ptr() as *const _,
-                    statement.len() as c_int,
-                    raw.as_mut_ptr(),
-                    ptr::null_mut(),
-                )
-            };
-        }
+                statement.as_bytes().as_ptr() as *const _,
+                statement.len() as c_int,
+                flags,
+                raw.as_mut_ptr(),
+                tail.as_mut_ptr(),
+            );
 
-        Ok(Statement {
-            raw: unsafe { ptr::NonNull::new_unchecked(raw.assume_init()) },
-        })
+            // Preparing might invoke the busy handler.
+            utils::resume_unwind();
+            sqlite3_try!(handle, code);
+            raw.assume_init()
+        };
+
+        // SAFETY: SQLite guarantees that the tail points into the statement
+        // at a character boundary.
+        let rest = unsafe {
+            let offset = (tail.assume_init() as usize) - (statement.as_ptr() as usize);
+            statement.get_unchecked(offset..)
+        };
+
+        let statement = ptr::NonNull::new(raw).map(|raw| Statement {
+            raw,
+            column_index: RefCell::new(None),
+            columns: RefCell::new(None),
+            bound: Vec::new(),
+            owned: Vec::new(),
+            flags,
+            op_budget: None,
+        });
+        Ok((statement, rest))
     }
 
     /// Bind a value to a parameter by index.
     ///
-    /// The first parameter has index 1.
+    /// The first parameter has index 1.
+    #[inline]
+    pub fn bind<T: Bindable>(&mut self, i: usize, value: T) -> Result<()> {
+        if let Err(error) = value.bind(self, i) {
+            return Err(self.bind_error(i, error));
+        }
+
+        self.mark_bound(i, None);
+        Ok(())
+    }
+
+    /// Bind a string to a parameter by index, handing the string over to the
+    /// statement instead of having SQLite copy it.
+    ///
+    /// Binding a `String` through [`Statement::bind`] copies it, since SQLite
+    /// might refer to the value after the call returns. This instead keeps the
+    /// string alive in the statement until the parameter is rebound, the
+    /// bindings are cleared or the statement is dropped, which avoids copying
+    /// large values which would be discarded after binding anyway.
+    ///
+    /// The first parameter has index 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE documents (body TEXT)")?;
+    ///
+    /// let mut stmt = c.prepare("INSERT INTO documents VALUES (?)")?;
+    /// stmt.bind_owned(1, "lorem ipsum ".repeat(1000))?;
+    /// assert_eq!(stmt.step()?, State::Done);
+    ///
+    /// assert_eq!(c.query_value::<i64, _>("SELECT length(body) FROM documents", ())?, 12000);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn bind_owned(&mut self, i: usize, value: String) -> Result<()> {
+        debug_assert!(i > 0, "the indexing starts from 1");
+        let value = value.into_bytes();
+
+        // SAFETY: The buffer is kept alive by the statement until the
+        // parameter is rebound. Moving the vector doesn't move its contents.
+        let code = unsafe {
+            ffi::sqlite3_bind_text64(
+                self.raw.as_ptr(),
+                i as c_int,
+                value.as_ptr() as *const _,
+                value.len() as u64,
+                None,
+                ffi::SQLITE_UTF8 as u8,
+            )
+        };
+
+        self.check_bind(i, code)?;
+
+        self.mark_bound(i, Some(value));
+        Ok(())
+    }
+
+    /// Bind a blob to a parameter by index, handing the buffer over to the
+    /// statement instead of having SQLite copy it.
+    ///
+    /// See [`Statement::bind_owned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT length(?)")?;
+    /// stmt.bind_owned_blob(1, vec![0; 1 << 20])?;
+    /// assert_eq!(stmt.iter::<(i64,)>().next().transpose()?, Some((1 << 20,)));
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn bind_owned_blob(&mut self, i: usize, value: Vec<u8>) -> Result<()> {
+        debug_assert!(i > 0, "the indexing starts from 1");
+
+        // SAFETY: See `bind_owned`.
+        let code = unsafe {
+            ffi::sqlite3_bind_blob64(
+                self.raw.as_ptr(),
+                i as c_int,
+                value.as_ptr() as *const _,
+                value.len() as u64,
+                None,
+            )
+        };
+
+        self.check_bind(i, code)?;
+
+        self.mark_bound(i, Some(value));
+        Ok(())
+    }
+
+    /// Check the code returned when binding the parameter with the given
+    /// index.
+    fn check_bind(&self, i: usize, code: c_int) -> Result<()> {
+        match code {
+            ffi::SQLITE_OK => Ok(()),
+            ffi::SQLITE_MISUSE => Err(self.bind_error(i, Error::from_code(code))),
+            _ => unsafe {
+                sqlite3_try!(ffi::sqlite3_db_handle(self.raw.as_ptr()), code);
+                Ok(())
+            },
+        }
+    }
+
+    /// Describe an error raised while binding the parameter with the given
+    /// index.
+    ///
+    /// SQLite refuses to bind parameters to a statement which has been
+    /// stepped until it has been reset, but only reports [`Code::MISUSE`].
+    /// Other errors are passed through unchanged.
+    fn bind_error(&self, i: usize, error: Error) -> Error {
+        if error.code() != Code::MISUSE || unsafe { ffi::sqlite3_stmt_busy(self.raw.as_ptr()) } == 0
+        {
+            return error;
+        }
+
+        let message = format!(
+            "parameter {i} can't be bound since the statement has been stepped, it must be reset first"
+        );
+
+        Error::new(ffi::SQLITE_MISUSE, Some(message.into()))
+    }
+
+    /// Record that the parameter with the given index has been bound, along
+    /// with the buffer it refers to if it is owned by the statement.
+    ///
+    /// Any buffer previously bound to the parameter is released, since SQLite
+    /// no longer refers to it.
+    fn mark_bound(&mut self, i: usize, owned: Option<Vec<u8>>) {
+        let index = match i.checked_sub(1) {
+            Some(index) => index,
+            None => return,
+        };
+
+        if self.bound.len() <= index {
+            self.bound.resize(index + 1, false);
+        }
+
+        self.bound[index] = true;
+
+        if owned.is_some() && self.owned.len() <= index {
+            self.owned.resize(index + 1, None);
+        }
+
+        if let Some(slot) = self.owned.get_mut(index) {
+            *slot = owned;
+        }
+    }
+
+    /// Bind a full set of parameters to the statement.
+    ///
+    /// Tuples bind each element to successive indexes starting at 1. See
+    /// [`Params`] for what else can be bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (id INTEGER, name TEXT, age REAL)")?;
+    ///
+    /// let mut stmt = c.prepare("INSERT INTO users VALUES (?, ?, ?)")?;
+    /// stmt.bind_all((1, "Bob", 69.42))?;
+    /// assert_eq!(stmt.step()?, State::Done);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn bind_all<P>(&mut self, params: P) -> Result<()>
+    where
+        P: Params,
+    {
+        params.bind_params(self)
+    }
+
+    /// Bind a slice of values to successive parameters starting at index 1.
+    ///
+    /// Errors with [`Code::RANGE`] if the number of values doesn't match
+    /// [`parameter_count`].
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    /// [`parameter_count`]: Statement::parameter_count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{Code, Value};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT ?, ?")?;
+    /// stmt.bind_values(&[Value::Integer(1), Value::Integer(2)])?;
+    ///
+    /// let e = stmt.bind_values(&[Value::Integer(1)]).unwrap_err();
+    /// assert_eq!(e.code(), Code::RANGE);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn bind_values(&mut self, values: &[Value]) -> Result<()> {
+        self.bind_iter(values)
+    }
+
+    /// Bind values from an iterator to successive parameters starting at index
+    /// 1.
+    ///
+    /// Errors with [`Code::RANGE`] if the number of values doesn't match
+    /// [`parameter_count`]. Values preceding the mismatch might already have
+    /// been bound if the iterator produces too many values.
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    /// [`parameter_count`]: Statement::parameter_count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT ? + ? + ?")?;
+    /// stmt.bind_iter([1, 2, 3])?;
+    ///
+    /// assert_eq!(stmt.step()?, State::Row);
+    /// assert_eq!(stmt.read::<i64>(0)?, 6);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn bind_iter<I>(&mut self, values: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Bindable,
+    {
+        let count = self.parameter_count();
+        let mut n = 0;
+
+        for value in values {
+            n += 1;
+
+            if n > count {
+                return Err(Error::new(
+                    ffi::SQLITE_RANGE,
+                    Some(format!("too many values, statement has {count} parameters").into()),
+                ));
+            }
+
+            self.bind(n, value)?;
+        }
+
+        if n != count {
+            return Err(Error::new(
+                ffi::SQLITE_RANGE,
+                Some(format!("{n} values bound, but statement has {count} parameters").into()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Bind a value to a parameter by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite_ll::Connection::open(":memory:")?;
+    /// # connection.execute("CREATE TABLE users (name STRING)");
+    /// let mut statement = connection.prepare("SELECT * FROM users WHERE name = :name")?;
+    /// statement.bind_by_name(":name", "Bob")?;
+    /// # Ok::<(), sqlite_ll::Error>(())
+    /// ```
+    pub fn bind_by_name<T: Bindable>(&mut self, name: &str, value: T) -> Result<()> {
+        if let Some(i) = self.parameter_index(name)? {
+            self.bind(i, value)?;
+            Ok(())
+        } else {
+            Err(Error::from_code(ffi::SQLITE_MISMATCH))
+        }
+    }
+
+    /// Bind values to parameters by name.
+    ///
+    /// Every name is resolved before any value is bound, so if some names
+    /// don't correspond to a parameter of the statement nothing is bound and
+    /// an error with [`Code::RANGE`] listing the unknown names is returned.
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{Code, State, Value};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT :a + :b")?;
+    ///
+    /// stmt.bind_named([(":a", &Value::Integer(1)), (":b", &Value::Integer(2))])?;
+    /// assert_eq!(stmt.step()?, State::Row);
+    /// assert_eq!(stmt.read::<i64>(0)?, 3);
+    ///
+    /// let e = stmt.bind_named([(":a", 1), (":c", 2)]).unwrap_err();
+    /// assert_eq!(e.code(), Code::RANGE);
+    /// # Ok::<(), sqlite_ll::Error>(())
+    /// ```
+    pub fn bind_named<I, N, T>(&mut self, params: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (N, T)>,
+        N: AsRef<str>,
+        T: Bindable,
+    {
+        let mut resolved = Vec::new();
+        let mut unknown = Vec::new();
+
+        for (name, value) in params {
+            let name = name.as_ref();
+
+            match self.parameter_index(name)? {
+                Some(i) => resolved.push((i, value)),
+                None => unknown.push(format!("`{name}`")),
+            }
+        }
+
+        if !unknown.is_empty() {
+            let message = format!("unknown parameters: {}", unknown.join(", "));
+            return Err(Error::new(ffi::SQLITE_RANGE, Some(message.into())));
+        }
+
+        for (i, value) in resolved {
+            self.bind(i, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reset the statement, bind a full set of parameters, and return a cursor
+    /// over the resulting rows.
+    ///
+    /// Any bindings from a previous execution are cleared before the new
+    /// parameters are bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name FROM users WHERE age > ?")?;
+    ///
+    /// for age in [40, 50] {
+    ///     let mut cursor = stmt.cursor(&[Value::Integer(age)])?;
+    ///
+    ///     while let Some(row) = cursor.next_row()? {
+    ///         println!("{}", row.get::<String>(0)?);
+    ///     }
+    /// }
+    ///
+    /// let names = stmt
+    ///     .cursor(&[Value::Integer(50)])?
+    ///     .iter::<(String,)>()
+    ///     .collect::<sqlite_ll::Result<Vec<_>>>()?;
+    ///
+    /// assert_eq!(names, [(String::from("Bob"),)]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn cursor<P>(&mut self, params: P) -> Result<Cursor<'_>>
+    where
+        P: Params,
+    {
+        self.reset()?;
+        self.clear_bindings()?;
+        params.bind_params(self)?;
+        Ok(Cursor::new(self))
+    }
+
+    /// Borrow the statement through a guard which resets it when dropped.
+    ///
+    /// A statement which has been stepped but not run to completion keeps its
+    /// read transaction open and must be reset before it's executed again.
+    /// The guard makes sure this happens even if processing stops early, such
+    /// as when breaking out of a loop or returning an error with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name FROM users ORDER BY age")?;
+    ///
+    /// fn first(stmt: &mut sqlite_ll::Statement) -> sqlite_ll::Result<Option<String>> {
+    ///     let mut stmt = stmt.run();
+    ///
+    ///     match stmt.next_row()? {
+    ///         Some(row) => Ok(Some(row.get(0)?)),
+    ///         None => Ok(None),
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(first(&mut stmt)?.as_deref(), Some("Alice"));
+    /// assert_eq!(first(&mut stmt)?.as_deref(), Some("Alice"));
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn run(&mut self) -> ResetGuard<'_> {
+        ResetGuard::new(self)
+    }
+
+    /// Bind the fields of a struct or the entries of a map to named parameters
+    /// using [serde].
+    ///
+    /// Each field or key is bound to the parameter with the same name prefixed
+    /// with `:`, unless it already starts with one of the parameter prefixes
+    /// `:`, `@`, or `$`. Binding a field which has no corresponding parameter,
+    /// or a value which can't be represented as a single SQLite value such as a
+    /// nested sequence or map, results in an error.
+    ///
+    /// [serde]: https://serde.rs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct User<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    ///     email: Option<&'a str>,
+    /// }
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER, email TEXT)")?;
+    ///
+    /// let mut stmt = c.prepare("INSERT INTO users VALUES (:name, :age, :email)")?;
+    /// stmt.bind_serialize(&User { name: "Alice", age: 42, email: None })?;
+    /// stmt.step()?;
+    ///
+    /// assert_eq!(c.change_count(), 1);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn bind_serialize<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        value.serialize(crate::ser::ParamsSerializer::new(self))
+    }
+
+    /// Execute the statement once for every parameter set in `params`, inside
+    /// of a single transaction.
+    ///
+    /// For each parameter set the statement is reset, its bindings are
+    /// cleared, the parameters are bound, and the statement is stepped until
+    /// it's done. Running many inserts in one transaction is dramatically
+    /// faster than letting each one commit on its own.
+    ///
+    /// If the connection is already inside of a transaction, the statements
+    /// are executed as part of it. Otherwise a transaction is started which is
+    /// committed once all parameter sets have been executed, or rolled back if
+    /// an error occurs.
+    ///
+    /// Returns the number of parameter sets executed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE numbers (value INTEGER)")?;
+    ///
+    /// let mut stmt = c.prepare("INSERT INTO numbers VALUES (?)")?;
+    /// let count = stmt.execute_batch((0..1000).map(|n| [Value::Integer(n)]).collect::<Vec<_>>().iter())?;
+    ///
+    /// assert_eq!(count, 1000);
+    /// assert_eq!(c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?, 1000);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn execute_batch<I>(&mut self, params: I) -> Result<usize>
+    where
+        I: IntoIterator,
+        I::Item: Params,
+    {
+        self.execute_batch_chunked(usize::MAX, params)
+    }
+
+    /// Execute the statement once for every parameter set in `params`,
+    /// committing every `chunk` parameter sets.
+    ///
+    /// This behaves like [`Statement::execute_batch`], except that the
+    /// transaction is committed and a new one started every `chunk` parameter
+    /// sets, which bounds the size of each transaction. If an error occurs, only
+    /// the chunk currently being executed is rolled back.
+    ///
+    /// If the connection is already inside of a transaction, no intermediate
+    /// commits are performed.
+    pub fn execute_batch_chunked<I>(&mut self, chunk: usize, params: I) -> Result<usize>
+    where
+        I: IntoIterator,
+        I::Item: Params,
+    {
+        let handle = unsafe { ffi::sqlite3_db_handle(self.raw.as_ptr()) };
+        let owned = unsafe { ffi::sqlite3_get_autocommit(handle) != 0 };
+        let chunk = chunk.max(1);
+
+        let mut count = 0;
+        let mut pending = 0;
+
+        if owned {
+            connection::execute(handle, "BEGIN")?;
+        }
+
+        let result = (|| {
+            for params in params {
+                if owned && pending == chunk {
+                    connection::execute(handle, "COMMIT; BEGIN")?;
+                    pending = 0;
+                }
+
+                self.reset()?;
+                self.clear_bindings()?;
+                params.bind_params(self)?;
+                while let State::Row = self.step()? {}
+
+                count += 1;
+                pending += 1;
+            }
+
+            Ok(())
+        })();
+
+        self.reset()?;
+
+        if owned {
+            connection::commit_or_rollback(handle, result)?;
+        } else {
+            result?;
+        }
+
+        Ok(count)
+    }
+
+    /// Return the normalized SQL text of the statement, using
+    /// `sqlite3_normalized_sql`.
+    ///
+    /// Literals are replaced with `?` and whitespace and keywords are
+    /// normalized, so that statements which only differ in the values they
+    /// use have the same text. This is useful for grouping statements in
+    /// metrics.
+    ///
+    /// This requires the `normalize` feature and an SQLite library compiled
+    /// with `SQLITE_ENABLE_NORMALIZE`, which isn't the case for most builds.
+    /// Enabling the feature without it causes linking to fail.
+    ///
+    /// Returns `None` if the normalized text couldn't be produced, such as if
+    /// memory allocation failed.
+    #[cfg(feature = "normalize")]
+    pub fn normalized_sql(&self) -> Result<Option<&str>> {
+        unsafe {
+            let pointer = sys::sqlite3_normalized_sql(self.raw.as_ptr());
+
+            if pointer.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(utils::cstr_to_str(pointer)?))
+        }
+    }
+
+    /// Return the number of columns.
+    #[inline]
+    pub fn column_count(&self) -> usize {
+        unsafe { ffi::sqlite3_column_count(self.raw.as_ptr()) as usize }
+    }
+
+    /// Return the name of a column.
+    ///
+    /// The first column has index 0. Errors with [`Code::RANGE`] if the
+    /// column doesn't exist.
+    #[inline]
+    pub fn column_name(&self, i: usize) -> Result<&str> {
+        self.check_column(i)?;
+
+        unsafe {
+            let pointer = ffi::sqlite3_column_name(self.raw.as_ptr(), i as c_int);
+
+            if pointer.is_null() {
+                let handle = ffi::sqlite3_db_handle(self.raw.as_ptr());
+                let code = ffi::sqlite3_errcode(handle);
+                return Err(Error::from_code(code));
+            }
+
+            utils::cstr_to_str(pointer)
+        }
+    }
+
+    /// Return column names.
+    #[inline]
+    pub fn column_names(&self) -> Result<Vec<&str>> {
+        (0..self.column_count())
+            .map(|i| self.column_name(i))
+            .collect()
+    }
+
+    /// Return the index of the column with the given name if it exists.
+    ///
+    /// The lookup table from names to indexes is built the first time this is
+    /// called and is cached for the lifetime of the statement, so repeated
+    /// lookups are cheap. If multiple columns have the same name, the index of
+    /// the first one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, email TEXT)")?;
+    ///
+    /// let stmt = c.prepare("SELECT name, email FROM users")?;
+    /// assert_eq!(stmt.column_index("email")?, Some(1));
+    /// assert_eq!(stmt.column_index("age")?, None);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn column_index(&self, name: &str) -> Result<Option<usize>> {
+        let reprepared = self.reprepared();
+        let mut cache = self.column_index.borrow_mut();
+
+        let map = match &mut *cache {
+            // The columns might change if the statement has been re-prepared
+            // due to a schema change.
+            Some(index) if index.reprepared == reprepared => &index.map,
+            cache => {
+                let count = self.column_count();
+                let mut map = HashMap::with_capacity(count);
+
+                for i in 0..count {
+                    map.entry(self.column_name(i)?.into()).or_insert(i);
+                }
+
+                &cache.insert(ColumnIndex { reprepared, map }).map
+            }
+        };
+
+        Ok(map.get(name).copied())
+    }
+
+    /// Return a snapshot of the metadata of all columns.
+    ///
+    /// The snapshot is built the first time this is called and is cached for
+    /// the lifetime of the statement, so calling this repeatedly is cheap. See
+    /// [`Columns`] for more.
+    pub fn columns(&self) -> Result<Columns> {
+        let reprepared = self.reprepared();
+        let mut cache = self.columns.borrow_mut();
+
+        // The columns might change if the statement has been re-prepared due
+        // to a schema change.
+        if let Some((n, columns)) = &*cache {
+            if *n == reprepared {
+                return Ok(columns.clone());
+            }
+        }
+
+        let count = self.column_count();
+        let mut metadata = Vec::with_capacity(count);
+
+        for i in 0..count {
+            metadata.push((self.column_name(i)?, self.column_decltype(i)?));
+        }
+
+        let columns = Columns::new(metadata.into_iter());
+        *cache = Some((reprepared, columns.clone()));
+        Ok(columns)
+    }
+
+    /// Return the type of a column.
+    ///
+    /// The first column has index 0. The type becomes available after taking a step.
+    pub fn column_type(&self, i: usize) -> Type {
+        self.debug_assert_column(i);
+
+        match unsafe { ffi::sqlite3_column_type(self.raw.as_ptr(), i as c_int) } {
+            ffi::SQLITE_BLOB => Type::Blob,
+            ffi::SQLITE_FLOAT => Type::Float,
+            ffi::SQLITE_INTEGER => Type::Integer,
+            ffi::SQLITE_TEXT => Type::Text,
+            ffi::SQLITE_NULL => Type::Null,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Return the declared type of a column.
+    ///
+    /// This is the type used in the `CREATE TABLE` statement for the column
+    /// which the result column originates from, or `None` if it is an
+    /// expression. Note that SQLite only uses the declared type to determine
+    /// the [`Affinity`] of the column, so values of any type might be stored
+    /// in it.
+    ///
+    /// The first column has index 0.
+    ///
+    /// [`Affinity`]: crate::Affinity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name VARCHAR(255), age INTEGER)")?;
+    ///
+    /// let stmt = c.prepare("SELECT name, age, age + 1 FROM users")?;
+    /// assert_eq!(stmt.column_decltype(0)?, Some("VARCHAR(255)"));
+    /// assert_eq!(stmt.column_decltype(1)?, Some("INTEGER"));
+    /// assert_eq!(stmt.column_decltype(2)?, None);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn column_decltype(&self, i: usize) -> Result<Option<&str>> {
+        self.check_column(i)?;
+
+        unsafe {
+            let pointer = ffi::sqlite3_column_decltype(self.raw.as_ptr(), i as c_int);
+
+            if pointer.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(utils::cstr_to_str(pointer)?))
+        }
+    }
+
+    /// Return the protected value of a column in the current row.
+    ///
+    /// The value can be bound to another statement as-is, or copied with
+    /// [`ColumnValue::dup`] so that it outlives the current row.
+    ///
+    /// The first column has index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{State, Type};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut select = c.prepare("SELECT 42")?;
+    /// assert_eq!(select.step()?, State::Row);
+    ///
+    /// let value = select.column_value(0);
+    /// assert_eq!(value.value_type(), Type::Integer);
+    ///
+    /// let mut echo = c.prepare("SELECT ?")?;
+    /// echo.bind(1, &value)?;
+    /// assert_eq!(echo.step()?, State::Row);
+    /// assert_eq!(echo.read::<i64>(0)?, 42);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn column_value(&self, i: usize) -> ColumnValue<'_> {
+        self.debug_assert_row(i);
+
+        unsafe {
+            let raw = ffi::sqlite3_column_value(self.raw.as_ptr(), i as c_int);
+            // SQLite returns a pointer to a static `NULL` value rather than a
+            // null pointer for columns which are out of range.
+            let raw = ptr::NonNull::new(raw).expect("sqlite3_column_value returned NULL");
+            ColumnValue::new(raw)
+        }
+    }
+
+    /// Return the length in bytes of a column.
+    ///
+    /// For text and blob columns this is the number of bytes that would be
+    /// read by [`read_str`] or [`read_blob`], without reading them. Integer and
+    /// float columns are converted to text to determine their length, and
+    /// `NULL` has a length of zero.
+    ///
+    /// The first column has index 0. The length becomes available after taking
+    /// a step.
+    ///
+    /// [`read_str`]: Statement::read_str
+    /// [`read_blob`]: Statement::read_blob
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT zeroblob(1024), 'hello', NULL")?;
+    /// assert_eq!(stmt.step()?, sqlite_ll::State::Row);
+    ///
+    /// assert_eq!(stmt.column_len(0), 1024);
+    /// assert_eq!(stmt.column_len(1), 5);
+    /// assert_eq!(stmt.column_len(2), 0);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn column_len(&self, i: usize) -> usize {
+        self.debug_assert_row(i);
+
+        // Blobs report their length as-is, while everything else goes through
+        // the text conversion which `read_str` and `read_blob` also use.
+        unsafe {
+            if self.column_type(i) != Type::Blob {
+                ffi::sqlite3_column_text(self.raw.as_ptr(), i as c_int);
+            }
+
+            ffi::sqlite3_column_bytes(self.raw.as_ptr(), i as c_int) as usize
+        }
+    }
+
+    /// Return the text representation of a column, converting it to text if
+    /// necessary.
+    ///
+    /// The returned string is valid until the statement is stepped or reset.
+    pub(crate) fn column_text(&self, i: usize) -> Result<&str> {
+        utf8(self.column_text_bytes(i))
+    }
+
+    /// Return the bytes of the text representation of a column, converting it
+    /// to text if necessary.
+    ///
+    /// The returned slice is valid until the statement is stepped or reset.
+    fn column_text_bytes(&self, i: usize) -> &[u8] {
+        unsafe {
+            let pointer = ffi::sqlite3_column_text(self.raw.as_ptr(), i as c_int);
+
+            if pointer.is_null() {
+                return &[];
+            }
+
+            let len = ffi::sqlite3_column_bytes(self.raw.as_ptr(), i as c_int) as usize;
+            std::slice::from_raw_parts(pointer, len)
+        }
+    }
+
+    /// Return the bytes of a column, which for text columns are the bytes of
+    /// the text.
+    ///
+    /// The returned slice is valid until the statement is stepped or reset.
+    pub(crate) fn column_blob(&self, i: usize) -> &[u8] {
+        unsafe {
+            let pointer = ffi::sqlite3_column_blob(self.raw.as_ptr(), i as c_int);
+
+            if pointer.is_null() {
+                return &[];
+            }
+
+            let len = ffi::sqlite3_column_bytes(self.raw.as_ptr(), i as c_int) as usize;
+            std::slice::from_raw_parts(pointer as *const u8, len)
+        }
+    }
+
+    /// Step to the next state, erroring if any parameter of the statement
+    /// hasn't been bound.
+    ///
+    /// SQLite treats parameters which haven't been bound as `NULL`, which can
+    /// silently hide mistakes. This tracks which parameters have been bound
+    /// through [`bind`] and related functions since the statement was
+    /// prepared or [`clear_bindings`] was last called, and errors with
+    /// [`Code::RANGE`] before stepping if any remain. Note that
+    /// [`reset`] doesn't clear bindings.
+    ///
+    /// [`bind`]: Statement::bind
+    /// [`clear_bindings`]: Statement::clear_bindings
+    /// [`reset`]: Statement::reset
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{Code, State};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT ?, :name")?;
+    /// stmt.bind(1, 42)?;
+    ///
+    /// let e = stmt.step_checked().unwrap_err();
+    /// assert_eq!(e.code(), Code::RANGE);
+    ///
+    /// stmt.bind_by_name(":name", "Alice")?;
+    /// assert_eq!(stmt.step_checked()?, State::Row);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn step_checked(&mut self) -> Result<State> {
+        for i in 1..=self.parameter_count() {
+            if self.bound.get(i - 1).copied().unwrap_or(false) {
+                continue;
+            }
+
+            let message = match self.parameter_name(i)? {
+                Some(name) => format!("parameter {i} (`{name}`) is not bound"),
+                None => format!("parameter {i} is not bound"),
+            };
+
+            return Err(Error::new(ffi::SQLITE_RANGE, Some(message.into())));
+        }
+
+        self.step()
+    }
+
+    /// Limit the number of virtual machine operations the statement may
+    /// perform each time it's evaluated.
+    ///
+    /// Once roughly `budget` operations have been performed since the
+    /// statement was last reset or ran to completion, stepping it errors with
+    /// [`ErrorKind::OpBudgetExceeded`]. This bounds the amount of work a
+    /// single query can perform without relying on wall-clock timers.
+    ///
+    /// The budget is enforced through the progress handler of the connection,
+    /// which is installed while the statement is being stepped and cleared
+    /// afterwards. SQLite only supports one progress handler per connection,
+    /// so any progress handler installed on the connection through other
+    /// means, such as through its raw handle, is removed the first time a
+    /// statement with a budget is stepped and is not restored.
+    ///
+    /// [`ErrorKind::OpBudgetExceeded`]: crate::ErrorKind::OpBudgetExceeded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{ErrorKind, State};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    ///
+    /// let mut stmt = c.prepare("WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) SELECT COUNT(*) FROM n")?;
+    /// stmt.set_op_budget(10_000);
+    ///
+    /// let e = stmt.step().unwrap_err();
+    /// assert_eq!(e.kind(), ErrorKind::OpBudgetExceeded);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_op_budget(&mut self, budget: usize) {
+        self.op_budget = Some(budget);
+    }
+
+    /// Remove the budget of virtual machine operations set through
+    /// [`Statement::set_op_budget`].
+    #[inline]
+    pub fn remove_op_budget(&mut self) {
+        self.op_budget = None;
+    }
+
+    /// Step to the next state.
+    ///
+    /// The function should be called multiple times until `State::Done` is
+    /// reached in order to evaluate the statement entirely.
+    ///
+    /// If the statement fails because the schema has changed since it was
+    /// prepared in a way which SQLite couldn't recover from on its own, it is
+    /// re-prepared from its original SQL and stepped once more. Bindings are
+    /// carried over to the new statement if it has the same number of
+    /// parameters, otherwise they are cleared. This keeps statements which
+    /// are retained for a long time, such as through
+    /// [`Connection::prepare_cached`], usable across migrations.
+    ///
+    /// [`Connection::prepare_cached`]: crate::Connection::prepare_cached
+    pub fn step(&mut self) -> Result<State> {
+        match self.step_once() {
+            Err(error) if error.code() == Code::SCHEMA => {
+                self.reprepare()?;
+                self.step_once()
+            }
+            result => result,
+        }
+    }
+
+    /// Step the statement once.
+    fn step_once(&mut self) -> Result<State> {
+        let code = match self.op_budget {
+            Some(budget) => self.step_with_budget(budget)?,
+            None => unsafe { ffi::sqlite3_step(self.raw.as_ptr()) },
+        };
+
+        unsafe {
+            utils::resume_unwind();
+
+            match code {
+                ffi::SQLITE_ROW => Ok(State::Row),
+                ffi::SQLITE_DONE => Ok(State::Done),
+                ffi::SQLITE_SCHEMA => Err(Error::from_code(ffi::SQLITE_SCHEMA)),
+                // Statements prepared through the legacy interface report a
+                // generic error when stepped, and only reveal the specific
+                // one when reset. Other statements have already been stopped
+                // by the error, so resetting them doesn't lose any progress.
+                ffi::SQLITE_ERROR => {
+                    let code = match ffi::sqlite3_reset(self.raw.as_ptr()) {
+                        ffi::SQLITE_SCHEMA => ffi::SQLITE_SCHEMA,
+                        _ => ffi::sqlite3_errcode(ffi::sqlite3_db_handle(self.raw.as_ptr())),
+                    };
+
+                    Err(Error::from_code(code))
+                }
+                _ => {
+                    let handle = ffi::sqlite3_db_handle(self.raw.as_ptr());
+                    Err(Error::from_code(ffi::sqlite3_errcode(handle)))
+                }
+            }
+        }
+    }
+
+    /// Step the statement with a progress handler installed which interrupts
+    /// it once it has used up the given budget of operations.
+    fn step_with_budget(&mut self, budget: usize) -> Result<c_int> {
+        let raw = self.raw.as_ptr();
+
+        unsafe {
+            // Statements which aren't in progress start over on the next step,
+            // so the operations counted towards the budget start over too.
+            let reset = c_int::from(ffi::sqlite3_stmt_busy(raw) == 0);
+            let used = ffi::sqlite3_stmt_status(raw, ffi::SQLITE_STMTSTATUS_VM_STEP, reset);
+            let remaining = budget.saturating_sub(used as usize);
+
+            if remaining == 0 {
+                return Err(Error::op_budget_exceeded(budget));
+            }
+
+            let handle = ffi::sqlite3_db_handle(raw);
+            let exceeded = Cell::new(false);
+
+            ffi::sqlite3_progress_handler(
+                handle,
+                c_int::try_from(remaining).unwrap_or(c_int::MAX),
+                Some(budget_exceeded),
+                &exceeded as *const Cell<bool> as *mut c_void,
+            );
+
+            let code = ffi::sqlite3_step(raw);
+            ffi::sqlite3_progress_handler(handle, 0, None, ptr::null_mut());
+            // Panics caught in callbacks take precedence over the budget.
+            utils::resume_unwind();
+
+            if exceeded.get() {
+                return Err(Error::op_budget_exceeded(budget));
+            }
+
+            Ok(code)
+        }
+    }
+
+    /// Prepare the statement again from its original SQL, replacing the
+    /// underlying handle and carrying over bindings if possible.
+    fn reprepare(&mut self) -> Result<()> {
+        let (handle, sql) = unsafe {
+            let handle = ffi::sqlite3_db_handle(self.raw.as_ptr());
+            let sql = ffi::sqlite3_sql(self.raw.as_ptr());
+
+            if sql.is_null() {
+                return Err(Error::from_code(ffi::SQLITE_SCHEMA));
+            }
+
+            match CStr::from_ptr(sql).to_str() {
+                Ok(sql) => (handle, sql.to_owned()),
+                Err(..) => return Err(Error::from_code(ffi::SQLITE_SCHEMA)),
+            }
+        };
+
+        let mut statement = match Self::prepare_next_with_flags(handle, &sql, self.flags)? {
+            (Some(statement), _) => statement,
+            (None, _) => return Err(Error::from_code(ffi::SQLITE_SCHEMA)),
+        };
+
+        // SAFETY: Both statements belong to the same connection. Any buffers
+        // bound without copying are retained by this statement, which keeps
+        // them alive for as long as the new handle refers to them.
+        let code =
+            unsafe { ffi::sqlite3_transfer_bindings(self.raw.as_ptr(), statement.raw.as_ptr()) };
+
+        if code != ffi::SQLITE_OK {
+            self.bound.clear();
+            self.owned.clear();
+        }
+
+        // The previous handle is finalized when the replacement is dropped.
+        core::mem::swap(&mut self.raw, &mut statement.raw);
+        *self.column_index.get_mut() = None;
+        *self.columns.get_mut() = None;
+        Ok(())
+    }
+
+    /// Step to the next row, returning it if one is available.
+    ///
+    /// This returns `None` once the statement has been entirely evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice'), ('Bob');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name FROM users")?;
+    /// let mut names = Vec::new();
+    ///
+    /// while let Some(row) = stmt.next_row()? {
+    ///     names.push(row.get::<String>(0)?);
+    /// }
+    ///
+    /// assert_eq!(names, ["Alice", "Bob"]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn next_row(&mut self) -> Result<Option<Row<'_>>> {
+        match self.step()? {
+            State::Row => Ok(Some(Row::new(self))),
+            State::Done => Ok(None),
+        }
+    }
+
+    /// Step the statement up to `n` times, reading every produced row into
+    /// `buf`.
+    ///
+    /// The buffer is cleared before reading, and allocations already present in
+    /// it are reused where the column types line up. This amortizes the
+    /// overhead of reading rows one by one when scanning large results.
+    ///
+    /// Returns the number of rows read, which is less than `n` once the
+    /// statement has been entirely evaluated. Like [`Statement::step`],
+    /// stepping a statement which has been entirely evaluated starts it over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{RowBuffer, Value};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1), (2), (3);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT n FROM numbers")?;
+    /// let mut buf = RowBuffer::new();
+    /// let mut sum = 0;
+    ///
+    /// loop {
+    ///     let count = stmt.step_batch(2, &mut buf)?;
+    ///
+    ///     for row in buf.iter() {
+    ///         if let [Value::Integer(n)] = row {
+    ///             sum += n;
+    ///         }
+    ///     }
+    ///
+    ///     if count < 2 {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(sum, 6);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn step_batch(&mut self, n: usize, buf: &mut RowBuffer) -> Result<usize> {
+        let columns = self.column_count();
+        buf.clear(columns);
+
+        while buf.len() < n {
+            if let State::Done = self.step()? {
+                break;
+            }
+
+            let row = buf.push_row();
+            self.read_values(row)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    /// Construct an iterator over the remaining rows of the statement, where
+    /// each row is decoded into `T` through [`FromRow`].
+    ///
+    /// Iterating with `for` over a `&mut Statement` produces rows as
+    /// `Vec<Value>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name, age FROM users")?;
+    ///
+    /// let users = stmt.iter::<(String, i64)>().collect::<sqlite_ll::Result<Vec<_>>>()?;
+    /// assert_eq!(users, [(String::from("Alice"), 42), (String::from("Bob"), 69)]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter<T>(&mut self) -> Iter<'_, T>
+    where
+        T: FromRow,
+    {
+        Iter::new(self)
+    }
+
+    /// Construct an iterator over a single column of the remaining rows of the
+    /// statement, reading each value as `T`.
+    ///
+    /// This avoids the overhead of decoding whole rows for queries where only
+    /// one column is of interest. The first column has index 0, and iterating
+    /// over a column which is out of range produces an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (id INTEGER, name TEXT); INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT id, name FROM users")?;
+    /// let names = stmt.column_iter::<String>(1).collect::<sqlite_ll::Result<Vec<_>>>()?;
+    /// assert_eq!(names, ["Alice", "Bob"]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn column_iter<T>(&mut self, index: usize) -> ColumnIter<'_, T>
+    where
+        T: Readable,
+    {
+        ColumnIter::new(self, index)
+    }
+
+    /// Return the number of parameters of the statement.
+    ///
+    /// This is the largest parameter index, which can exceed the number of
+    /// distinct parameters if numbered parameters like `?3` are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let stmt = c.prepare("SELECT ?, :name, ?5")?;
+    /// assert_eq!(stmt.parameter_count(), 5);
+    /// # Ok::<(), sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn parameter_count(&self) -> usize {
+        unsafe { ffi::sqlite3_bind_parameter_count(self.raw.as_ptr()) as usize }
+    }
+
+    /// Return the name of the parameter with the given index, including its
+    /// prefix such as `:`.
+    ///
+    /// Returns `None` if the parameter is nameless, like `?`, or if the index
+    /// is out of range. The first parameter has index 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let stmt = c.prepare("SELECT ?, :name, ?5")?;
+    /// assert_eq!(stmt.parameter_name(1)?, None);
+    /// assert_eq!(stmt.parameter_name(2)?, Some(":name"));
+    /// assert_eq!(stmt.parameter_name(5)?, Some("?5"));
+    /// assert_eq!(stmt.parameter_name(6)?, None);
+    /// # Ok::<(), sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn parameter_name(&self, i: usize) -> Result<Option<&str>> {
+        unsafe {
+            let name = ffi::sqlite3_bind_parameter_name(self.raw.as_ptr(), i as c_int);
+
+            if name.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(utils::cstr_to_str(name)?))
+        }
+    }
+
+    /// Return the index for a named parameter if exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite_ll::Connection::open(":memory:")?;
+    /// # connection.execute("CREATE TABLE users (name STRING)");
+    /// let statement = connection.prepare("SELECT * FROM users WHERE name = :name")?;
+    /// assert_eq!(statement.parameter_index(":name")?, Some(1));
+    /// assert_eq!(statement.parameter_index(":asdf")?, None);
+    /// # Ok::<(), sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn parameter_index(&self, parameter: &str) -> Result<Option<usize>> {
+        let index = unsafe {
+            ffi::sqlite3_bind_parameter_index(
+                self.raw.as_ptr(),
+                utils::string_to_cstring(parameter)?.as_ptr(),
+            )
+        };
+
+        match index {
+            0 => Ok(None),
+            _ => Ok(Some(index as usize)),
+        }
+    }
+
+    /// Read a value from a column.
+    ///
+    /// The first column has index 0. Errors with [`Code::RANGE`] if the
+    /// column doesn't exist, and with [`Code::MISUSE`] if the statement
+    /// hasn't been stepped to a row.
+    #[inline]
+    pub fn read<T: Readable>(&self, i: usize) -> Result<T> {
+        self.check_row(i)?;
+        Readable::read(self, i)
+    }
+
+    /// Read a text column without copying it.
+    ///
+    /// The returned string borrows from SQLite's own buffer, and the borrow of
+    /// the statement ensures that it can't be used after the statement has
+    /// been stepped or reset. Integer and float columns are converted to text
+    /// and blob columns are read as text if they are valid UTF-8, while `NULL`
+    /// results in an error with [`Code::MISMATCH`] like reading a [`String`]
+    /// would.
+    ///
+    /// [`Code::MISMATCH`]: crate::Code::MISMATCH
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice'), ('Bob');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name FROM users")?;
+    /// let mut long = 0;
+    ///
+    /// while let State::Row = stmt.step()? {
+    ///     if stmt.read_str(0)?.len() > 3 {
+    ///         long += 1;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(long, 1);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_str(&self, i: usize) -> Result<&str> {
+        self.check_row(i)?;
+
+        match self.column_type(i) {
+            Type::Null => Err(Error::mismatch()),
+            // Reading a blob as text might require SQLite to reallocate it to
+            // add a terminator, which would invalidate slices previously
+            // handed out for the same column, so the bytes are used as-is.
+            Type::Blob => utf8(self.column_blob(i)),
+            _ => self.column_text(i),
+        }
+    }
+
+    /// Read a text column without copying it or validating that it is UTF-8.
+    ///
+    /// This behaves like [`read_str`], except that `NULL` is read as an empty
+    /// string since this can't fail.
+    ///
+    /// [`read_str`]: Statement::read_str
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the column is valid UTF-8, such as when the
+    /// application itself has written it from a Rust string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT 'Alice'")?;
+    /// assert_eq!(stmt.step()?, sqlite_ll::State::Row);
+    ///
+    /// // SAFETY: The column is a string literal which is valid UTF-8.
+    /// assert_eq!(unsafe { stmt.read_text_unchecked(0) }, "Alice");
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub unsafe fn read_text_unchecked(&self, i: usize) -> &str {
+        self.debug_assert_row(i);
+
+        let bytes = match self.column_type(i) {
+            Type::Blob => self.column_blob(i),
+            _ => self.column_text_bytes(i),
+        };
+
+        std::str::from_utf8_unchecked(bytes)
+    }
+
+    /// Read a blob column without copying it.
+    ///
+    /// The returned slice borrows from SQLite's own buffer, and the borrow of
+    /// the statement ensures that it can't be used after the statement has
+    /// been stepped or reset. Other columns are read as the bytes of their
+    /// text representation, and `NULL` is read as an empty slice like reading
+    /// a [`Vec<u8>`] would.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE records (data BLOB); INSERT INTO records VALUES (X'0102'), (X'030405');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT data FROM records")?;
+    /// let mut total = 0;
+    ///
+    /// while let State::Row = stmt.step()? {
+    ///     total += stmt.read_blob(0)?.len();
+    /// }
+    ///
+    /// assert_eq!(total, 5);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_blob(&self, i: usize) -> Result<&[u8]> {
+        self.check_row(i)?;
+
+        match self.column_type(i) {
+            Type::Blob => Ok(self.column_blob(i)),
+            // Going through the text conversion ensures that a later call to
+            // `read_str` for the same column doesn't reallocate the value.
+            _ => Ok(self.column_text_bytes(i)),
+        }
+    }
+
+    /// Read a column into the given buffer, replacing its contents.
+    ///
+    /// This reads the same bytes as [`read_blob`], but reuses the allocation
+    /// of `buf` so that reading many rows doesn't allocate for each of them.
+    ///
+    /// [`read_blob`]: Statement::read_blob
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE records (data BLOB); INSERT INTO records VALUES (X'0102'), (X'03');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT data FROM records")?;
+    /// let mut buf = Vec::new();
+    ///
+    /// while let State::Row = stmt.step()? {
+    ///     stmt.read_into(0, &mut buf)?;
+    /// }
+    ///
+    /// assert_eq!(buf, [3]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_into(&self, i: usize, buf: &mut Vec<u8>) -> Result<()> {
+        let blob = self.read_blob(i)?;
+        buf.clear();
+        buf.extend_from_slice(blob);
+        Ok(())
+    }
+
+    /// Read a column into the given string, replacing its contents.
+    ///
+    /// This reads the same text as [`read_str`], but reuses the allocation of
+    /// `buf` so that reading many rows doesn't allocate for each of them. If
+    /// reading fails the string is left untouched.
+    ///
+    /// [`read_str`]: Statement::read_str
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::State;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice'), ('Bob');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name FROM users")?;
+    /// let mut name = String::new();
+    ///
+    /// while let State::Row = stmt.step()? {
+    ///     stmt.read_str_into(0, &mut name)?;
+    /// }
+    ///
+    /// assert_eq!(name, "Bob");
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_str_into(&self, i: usize, buf: &mut String) -> Result<()> {
+        let text = self.read_str(i)?;
+        buf.clear();
+        buf.push_str(text);
+        Ok(())
+    }
+
+    /// Read a column into a dynamic value according to its declared type.
+    ///
+    /// This is intended for generic tooling which displays arbitrary tables,
+    /// where the storage class of a value alone loses information. Integers in
+    /// columns declared as `BOOL` or `BOOLEAN` are read as booleans, and
+    /// values in columns declared as `DATE`, `DATETIME` or `TIMESTAMP` are read
+    /// as timestamps if they use any of the formats understood by the date and
+    /// time functions of SQLite. That is ISO-8601 text, integer seconds since
+    /// the unix epoch, or floating point julian days. Note that since such
+    /// columns have numeric affinity, julian days which are whole numbers are
+    /// stored as integers and can't be told apart from seconds. Everything
+    /// else is read according to its storage class like a [`Value`].
+    ///
+    /// See [`column_decltype`].
+    ///
+    /// [`column_decltype`]: Statement::column_decltype
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// use sqlite_ll::{DeclaredValue, State};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute(
+    ///     "
+    ///     CREATE TABLE users (name TEXT, admin BOOLEAN, created DATETIME);
+    ///     INSERT INTO users VALUES ('Alice', 1, '2023-04-01 12:30:05');
+    ///     ",
+    /// )?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name, admin, created FROM users")?;
+    /// assert_eq!(stmt.step()?, State::Row);
+    ///
+    /// assert_eq!(stmt.read_declared(0)?, DeclaredValue::Text(String::from("Alice")));
+    /// assert_eq!(stmt.read_declared(1)?, DeclaredValue::Boolean(true));
+    ///
+    /// let created = UNIX_EPOCH + Duration::from_secs(1680352205);
+    /// assert_eq!(stmt.read_declared(2)?, DeclaredValue::Timestamp(created));
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_declared(&self, i: usize) -> Result<DeclaredValue> {
+        self.check_row(i)?;
+        decltype::read(self, i)
+    }
+
+    /// Read a column into a dynamic value which borrows text and blobs from
+    /// the statement.
+    ///
+    /// This is like reading a [`Value`], but without allocating. The returned
+    /// value can't be used after the statement has been stepped or reset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{State, ValueRef};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT 'Alice', 42, NULL")?;
+    /// assert_eq!(stmt.step()?, State::Row);
+    ///
+    /// assert_eq!(stmt.read_value_ref(0)?, ValueRef::Text("Alice"));
+    /// assert_eq!(stmt.read_value_ref(1)?, ValueRef::Integer(42));
+    /// assert_eq!(stmt.read_value_ref(2)?, ValueRef::Null);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_value_ref(&self, i: usize) -> Result<ValueRef<'_>> {
+        self.check_row(i)?;
+
+        Ok(match self.column_type(i) {
+            Type::Blob => ValueRef::Blob(self.column_blob(i)),
+            Type::Float => ValueRef::Float(f64::read(self, i)?),
+            Type::Integer => ValueRef::Integer(i64::read(self, i)?),
+            Type::Text => ValueRef::Text(self.column_text(i)?),
+            Type::Null => ValueRef::Null,
+        })
+    }
+
+    /// Read every column of the current row into `row` in a single pass.
+    ///
+    /// The buffer is resized to the number of columns, and text and blob
+    /// allocations already present in it are reused where the column types
+    /// line up, so reading many rows into the same buffer avoids most
+    /// allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::Value;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT * FROM users")?;
+    /// let mut row = Vec::new();
+    ///
+    /// while let sqlite_ll::State::Row = stmt.step()? {
+    ///     stmt.read_row(&mut row)?;
+    ///     assert_eq!(row.len(), 2);
+    /// }
+    ///
+    /// assert_eq!(row, [Value::Text(String::from("Bob")), Value::Integer(69)]);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_row(&self, row: &mut Vec<Value>) -> Result<()> {
+        let count = self.column_count();
+        row.truncate(count);
+        row.resize(count, Value::Null);
+        self.read_values(row)
+    }
+
+    /// Read the columns of the current row into `values`, reusing the
+    /// allocations of existing values where possible.
+    fn read_values(&self, values: &mut [Value]) -> Result<()> {
+        for (i, value) in values.iter_mut().enumerate() {
+            match self.column_type(i) {
+                Type::Blob => {
+                    let blob = self.column_blob(i);
+
+                    match value {
+                        Value::Blob(buf) => {
+                            buf.clear();
+                            buf.extend_from_slice(blob);
+                        }
+                        value => *value = Value::Blob(blob.to_vec()),
+                    }
+                }
+                Type::Float => {
+                    *value = Value::Float(unsafe {
+                        ffi::sqlite3_column_double(self.raw.as_ptr(), i as c_int)
+                    });
+                }
+                Type::Integer => {
+                    *value = Value::Integer(unsafe {
+                        ffi::sqlite3_column_int64(self.raw.as_ptr(), i as c_int)
+                    });
+                }
+                Type::Text => {
+                    let text = self.column_text(i)?;
+
+                    match value {
+                        Value::Text(buf) => {
+                            buf.clear();
+                            buf.push_str(text);
+                        }
+                        value => *value = Value::Text(text.to_owned()),
+                    }
+                }
+                Type::Null => {
+                    *value = Value::Null;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read a value from the column with the given name.
+    ///
+    /// If no column with the given name exists, the returned error lists the
+    /// available columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, email TEXT); INSERT INTO users VALUES ('Alice', 'alice@example.com');")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT name, email FROM users")?;
+    ///
+    /// while let sqlite_ll::State::Row = stmt.step()? {
+    ///     assert_eq!(stmt.read_by_name::<String>("email")?, "alice@example.com");
+    ///
+    ///     let e = stmt.read_by_name::<String>("age").unwrap_err();
+    ///     assert!(e.to_string().contains("available columns: `name`, `email`"));
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn read_by_name<T: Readable>(&self, name: &str) -> Result<T> {
+        let i = self.column_position(name)?;
+        self.check_row(i)?;
+        Readable::read(self, i)
+    }
+
+    /// Deserialize the current row into `T` using [serde].
+    ///
+    /// Structs and maps are deserialized using column names as keys, while
+    /// sequences and tuples are deserialized by position. NULL columns
+    /// deserialize into `None`, and blobs can be deserialized into byte
+    /// containers. Text and blobs can be borrowed directly from the statement.
+    ///
+    /// [serde]: https://serde.rs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct User<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    ///     email: Option<String>,
+    /// }
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// c.execute("CREATE TABLE users (name TEXT, age INTEGER, email TEXT); INSERT INTO users VALUES ('Alice', 42, NULL);")?;
+    ///
+    /// let mut stmt = c.prepare("SELECT * FROM users")?;
+    ///
+    /// while let sqlite_ll::State::Row = stmt.step()? {
+    ///     let user = stmt.de::<User<'_>>()?;
+    ///     assert_eq!(user, User { name: "Alice", age: 42, email: None });
+    /// }
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn de<'de, T>(&'de self) -> Result<T>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        T::deserialize(crate::de::RowDeserializer::new(self))
+    }
+
+    /// Access the raw statement handle.
+    ///
+    /// This can be used to call SQLite functions which this crate doesn't
+    /// wrap, such as `sqlite3_stmt_scanstatus`. The handle must not be
+    /// finalized, and changes made through it which this crate tracks such as
+    /// parameter bindings might not be reflected in methods like
+    /// [`Statement::step_checked`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::ffi;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let stmt = c.prepare("SELECT 1")?;
+    ///
+    /// let read_only = unsafe { ffi::sqlite3_stmt_readonly(stmt.as_raw()) };
+    /// assert_ne!(read_only, 0);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
     #[inline]
-    pub fn bind<T: Bindable>(&mut self, i: usize, value: T) -> Result<()> {
-        value.bind(self, i)
+    pub fn as_raw(&self) -> *mut ffi::sqlite3_stmt {
+        self.raw.as_ptr()
     }
 
-    /// Bind a value to a parameter by name.
+    /// Construct a statement from a raw statement handle, taking ownership of
+    /// it.
+    ///
+    /// The statement is finalized when the returned value is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The handle must be a valid, non-null statement prepared with one of the
+    /// `sqlite3_prepare` functions which is not owned by anything else. It
+    /// must not be finalized elsewhere, and its connection must outlive it.
     ///
     /// # Examples
     ///
     /// ```
-    /// # let connection = sqlite_ll::Connection::open(":memory:")?;
-    /// # connection.execute("CREATE TABLE users (name STRING)");
-    /// let mut statement = unsafe { connection.prepare("SELECT * FROM users WHERE name = :name")? };
-    /// statement.bind_by_name(":name", "Bob")?;
-    /// # Ok::<(), sqlite_ll::Error>(())
+    /// use std::ptr;
+    ///
+    /// use sqlite_ll::{ffi, State, Statement};
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    ///
+    /// let mut raw = ptr::null_mut();
+    /// let sql = "SELECT 42";
+    ///
+    /// let code = unsafe {
+    ///     ffi::sqlite3_prepare_v2(c.as_raw(), sql.as_ptr().cast(), sql.len() as _, &mut raw, ptr::null_mut())
+    /// };
+    ///
+    /// assert_eq!(code, ffi::SQLITE_OK);
+    ///
+    /// let mut stmt = unsafe { Statement::from_raw(raw) };
+    /// assert_eq!(stmt.step()?, State::Row);
+    /// assert_eq!(stmt.read::<i64>(0)?, 42);
+    /// # Ok::<_, sqlite_ll::Error>(())
     /// ```
-    pub fn bind_by_name<T: Bindable>(&mut self, name: &str, value: T) -> Result<()> {
-        if let Some(i) = self.parameter_index(name)? {
-            self.bind(i, value)?;
-            Ok(())
-        } else {
-            Err(Error::from_code(ffi::SQLITE_MISMATCH))
+    #[inline]
+    pub unsafe fn from_raw(raw: *mut ffi::sqlite3_stmt) -> Statement {
+        Statement {
+            raw: ptr::NonNull::new_unchecked(raw),
+            column_index: RefCell::new(None),
+            columns: RefCell::new(None),
+            bound: Vec::new(),
+            owned: Vec::new(),
+            flags: 0,
+            op_budget: None,
         }
     }
 
-    /// Return the number of columns.
-    #[inline]
-    pub fn column_count(&self) -> usize {
-        unsafe { ffi::sqlite3_column_count(self.raw.as_ptr()) as usize }
+    /// The number of times SQLite has automatically re-prepared the statement
+    /// due to schema changes.
+    fn reprepared(&self) -> c_int {
+        unsafe { ffi::sqlite3_stmt_status(self.raw.as_ptr(), sys::SQLITE_STMTSTATUS_REPREPARE, 0) }
     }
 
-    /// Return the name of a column.
-    ///
-    /// The first column has index 0.
+    /// Assert that the column with the given index exists in debug builds.
     #[inline]
-    pub fn column_name(&self, i: usize) -> Result<&str> {
-        debug_assert!(i < self.column_count(), "the index is out of range");
-        unsafe {
-            let pointer = ffi::sqlite3_column_name(self.raw.as_ptr(), i as c_int);
-
-            if pointer.is_null() {
-                let handle = ffi::sqlite3_db_handle(self.raw.as_ptr());
-                let code = ffi::sqlite3_errcode(handle);
-                return Err(Error::from_code(code));
+    #[track_caller]
+    fn debug_assert_column(&self, i: usize) {
+        if cfg!(debug_assertions) {
+            if let Err(error) = self.check_column(i) {
+                panic!("{error}");
             }
-
-            utils::cstr_to_str(pointer)
         }
     }
 
-    /// Return column names.
+    /// Assert that the column with the given index exists and that a row is
+    /// available to read it from in debug builds.
     #[inline]
-    pub fn column_names(&self) -> Result<Vec<&str>> {
-        (0..self.column_count())
-            .map(|i| self.column_name(i))
-            .collect()
+    #[track_caller]
+    fn debug_assert_row(&self, i: usize) {
+        if cfg!(debug_assertions) {
+            if let Err(error) = self.check_row(i) {
+                panic!("{error}");
+            }
+        }
     }
 
-    /// Return the type of a column.
-    ///
-    /// The first column has index 0. The type becomes available after taking a step.
-    pub fn column_type(&self, i: usize) -> Type {
-        debug_assert!(i < self.column_count(), "the index is out of range");
+    /// Check that the column with the given index exists.
+    fn check_column(&self, i: usize) -> Result<()> {
+        let count = self.column_count();
 
-        match unsafe { ffi::sqlite3_column_type(self.raw.as_ptr(), i as c_int) } {
-            ffi::SQLITE_BLOB => Type::Blob,
-            ffi::SQLITE_FLOAT => Type::Float,
-            ffi::SQLITE_INTEGER => Type::Integer,
-            ffi::SQLITE_TEXT => Type::Text,
-            ffi::SQLITE_NULL => Type::Null,
-            _ => unreachable!(),
+        if i >= count {
+            let message =
+                format!("column {i} is out of range for a statement with {count} columns");
+            return Err(Error::new(ffi::SQLITE_RANGE, Some(message.into())));
         }
+
+        Ok(())
     }
 
-    /// Step to the next state.
-    ///
-    /// The function should be called multiple times until `State::Done` is
-    /// reached in order to evaluate the statement entirely.
-    pub fn step(&mut self) -> Result<State> {
-        unsafe {
-            match ffi::sqlite3_step(self.raw.as_ptr()) {
-                ffi::SQLITE_ROW => Ok(State::Row),
-                ffi::SQLITE_DONE => Ok(State::Done),
-                _ => {
-                    let handle = ffi::sqlite3_db_handle(self.raw.as_ptr());
-                    let code = ffi::sqlite3_errcode(handle);
-                    Err(Error::from_code(code))
-                }
-            }
+    /// Check that the column with the given index exists and that a row is
+    /// available to read it from.
+    fn check_row(&self, i: usize) -> Result<()> {
+        self.check_column(i)?;
+
+        if unsafe { ffi::sqlite3_data_count(self.raw.as_ptr()) } == 0 {
+            let message = format!(
+                "column {i} was read while no row is available, the statement must be stepped to a row first"
+            );
+            return Err(Error::new(ffi::SQLITE_MISUSE, Some(message.into())));
         }
+
+        Ok(())
     }
 
-    /// Return the index for a named parameter if exists.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # let connection = sqlite_ll::Connection::open(":memory:")?;
-    /// # connection.execute("CREATE TABLE users (name STRING)");
-    /// let statement = unsafe { connection.prepare("SELECT * FROM users WHERE name = :name")? };
-    /// assert_eq!(statement.parameter_index(":name")?, Some(1));
-    /// assert_eq!(statement.parameter_index(":asdf")?, None);
-    /// # Ok::<(), sqlite_ll::Error>(())
-    /// ```
-    #[inline]
-    pub fn parameter_index(&self, parameter: &str) -> Result<Option<usize>> {
-        let index = unsafe {
-            ffi::sqlite3_bind_parameter_index(
-                self.raw.as_ptr(),
-                utils::string_to_cstring(parameter)?.as_ptr(),
-            )
-        };
+    /// Find the index of the column with the given name, or construct an
+    /// error listing the available columns.
+    pub(crate) fn column_position(&self, name: &str) -> Result<usize> {
+        if let Some(i) = self.column_index(name)? {
+            return Ok(i);
+        }
 
-        match index {
-            0 => Ok(None),
-            _ => Ok(Some(index as usize)),
+        let names = self.column_names()?;
+
+        let mut message = format!("no column named `{name}`, available columns: ");
+
+        for (n, column) in names.iter().enumerate() {
+            if n > 0 {
+                message.push_str(", ");
+            }
+
+            message.push('`');
+            message.push_str(column);
+            message.push('`');
         }
-    }
 
-    /// Read a value from a column.
-    ///
-    /// The first column has index 0.
-    #[inline]
-    pub fn read<T: Readable>(&self, i: usize) -> Result<T> {
-        debug_assert!(i < self.column_count(), "the index is out of range");
-        Readable::read(self, i)
+        Err(Error::new(ffi::SQLITE_RANGE, Some(message.into())))
     }
 
     /// Reset the statement.
@@ -216,6 +2084,17 @@ impl Statement {
         unsafe { ffi::sqlite3_reset(self.raw.as_ptr()) };
         Ok(())
     }
+
+    /// Clear all parameter bindings, setting them to NULL.
+    ///
+    /// Note that resetting the statement doesn't clear bindings.
+    #[inline]
+    pub fn clear_bindings(&mut self) -> Result<()> {
+        unsafe { ffi::sqlite3_clear_bindings(self.raw.as_ptr()) };
+        self.bound.clear();
+        self.owned.clear();
+        Ok(())
+    }
 }
 
 impl Drop for Statement {
@@ -237,6 +2116,39 @@ impl Bindable for &Value {
     }
 }
 
+impl Bindable for ValueRef<'_> {
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        match self {
+            ValueRef::Blob(value) => value.bind(statement, i),
+            ValueRef::Float(value) => value.bind(statement, i),
+            ValueRef::Integer(value) => value.bind(statement, i),
+            ValueRef::Text(value) => value.bind(statement, i),
+            ValueRef::Null => ().bind(statement, i),
+        }
+    }
+}
+
+impl Bindable for &ValueRef<'_> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (*self).bind(statement, i)
+    }
+}
+
+impl Bindable for Value {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        (&self).bind(statement, i)
+    }
+}
+
+impl Bindable for Vec<u8> {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_slice().bind(statement, i)
+    }
+}
+
 impl Bindable for &[u8] {
     #[inline]
     fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
@@ -299,6 +2211,126 @@ impl Bindable for i64 {
     }
 }
 
+macro_rules! bind_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Bindable for $ty {
+                /// Bind as an integer, erroring with [`Code::RANGE`] if the
+                /// value doesn't fit in an `i64`.
+                ///
+                /// [`Code::RANGE`]: crate::Code::RANGE
+                #[inline]
+                fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+                    match i64::try_from(self) {
+                        Ok(value) => value.bind(statement, i),
+                        Err(..) => Err(out_of_range(self, "i64")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+bind_integer!(i8, i16, i32, isize, u8, u16, u32, u64, usize);
+
+impl Bindable for f32 {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        f64::from(self).bind(statement, i)
+    }
+}
+
+impl Bindable for bool {
+    /// Bind as the integer `1` if `true`, or `0` if `false`.
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        i64::from(self).bind(statement, i)
+    }
+}
+
+macro_rules! non_zero {
+    ($($ty:ident($inner:ty)),*) => {
+        $(
+            impl Bindable for $ty {
+                #[inline]
+                fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+                    self.get().bind(statement, i)
+                }
+            }
+
+            impl Readable for $ty {
+                /// Read a non-zero integer, erroring with [`Code::MISMATCH`]
+                /// if the value is zero.
+                ///
+                /// [`Code::MISMATCH`]: crate::Code::MISMATCH
+                #[inline]
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    match $ty::new(<$inner>::read(statement, i)?) {
+                        Some(value) => Ok(value),
+                        None => Err(Error::new(
+                            ffi::SQLITE_MISMATCH,
+                            Some(concat!("zero is not a valid `", stringify!($ty), "`").into()),
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+non_zero! {
+    NonZeroI8(i8), NonZeroI16(i16), NonZeroI32(i32), NonZeroI64(i64), NonZeroIsize(isize),
+    NonZeroU8(u8), NonZeroU16(u16), NonZeroU32(u32), NonZeroU64(u64), NonZeroUsize(usize)
+}
+
+macro_rules! wide_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Bindable for $ty {
+                /// Bind as a 16-byte big-endian blob.
+                ///
+                /// Use [`AsText`] to bind the value as decimal text instead.
+                ///
+                /// [`AsText`]: crate::AsText
+                #[inline]
+                fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+                    self.to_be_bytes()[..].bind(statement, i)
+                }
+            }
+
+            impl Readable for $ty {
+                /// Read from a 16-byte big-endian blob, or from an integer.
+                ///
+                /// Use [`AsText`] to read the value from decimal text instead.
+                ///
+                /// [`AsText`]: crate::AsText
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    match statement.column_type(i) {
+                        Type::Blob => match <[u8; 16]>::try_from(statement.column_blob(i)) {
+                            Ok(bytes) => Ok(<$ty>::from_be_bytes(bytes)),
+                            Err(..) => Err(Error::new(
+                                ffi::SQLITE_MISMATCH,
+                                Some(concat!("expected a 16-byte blob for `", stringify!($ty), "`").into()),
+                            )),
+                        },
+                        Type::Integer => {
+                            let value = i64::read(statement, i)?;
+
+                            match <$ty>::try_from(value) {
+                                Ok(value) => Ok(value),
+                                Err(..) => Err(out_of_range(value, stringify!($ty))),
+                            }
+                        }
+                        _ => Err(Error::mismatch()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+wide_integer!(i128, u128);
+
 impl Bindable for &str {
     #[inline]
     fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
@@ -321,6 +2353,13 @@ impl Bindable for &str {
     }
 }
 
+impl Bindable for String {
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.as_str().bind(statement, i)
+    }
+}
+
 impl Bindable for () {
     #[inline]
     fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
@@ -377,6 +2416,56 @@ impl Readable for i64 {
     }
 }
 
+macro_rules! read_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Readable for $ty {
+                /// Read as an integer, erroring with [`Code::RANGE`] if the
+                /// value doesn't fit in the target type.
+                ///
+                /// [`Code::RANGE`]: crate::Code::RANGE
+                #[inline]
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    let value = i64::read(statement, i)?;
+
+                    match <$ty>::try_from(value) {
+                        Ok(value) => Ok(value),
+                        Err(..) => Err(out_of_range(value, stringify!($ty))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+read_integer!(i8, i16, i32, isize, u8, u16, u32, u64, usize);
+
+impl Readable for f32 {
+    /// Read as a floating point number, erroring with [`Code::RANGE`] if a
+    /// finite value is too large to be represented as an `f32`.
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let value = f64::read(statement, i)?;
+        let narrowed = value as f32;
+
+        if value.is_finite() && !narrowed.is_finite() {
+            return Err(out_of_range(value, "f32"));
+        }
+
+        Ok(narrowed)
+    }
+}
+
+impl Readable for bool {
+    /// Read an integer, where any non-zero value is `true`.
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(i64::read(statement, i)? != 0)
+    }
+}
+
 impl Readable for String {
     #[inline]
     fn read(statement: &Statement, i: usize) -> Result<Self> {
@@ -409,6 +2498,44 @@ impl Readable for Vec<u8> {
     }
 }
 
+macro_rules! read_owned {
+    ($($ty:ty => $read:ident),* $(,)?) => {
+        $(
+            impl Readable for $ty {
+                /// Read by copying directly out of the column into the
+                /// allocation being returned.
+                #[inline]
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    Ok(<$ty>::from(statement.$read(i)?))
+                }
+            }
+        )*
+    };
+}
+
+read_owned! {
+    Box<str> => read_str,
+    Rc<str> => read_str,
+    Arc<str> => read_str,
+    Box<[u8]> => read_blob,
+    Rc<[u8]> => read_blob,
+    Arc<[u8]> => read_blob,
+}
+
+impl Readable for Cow<'static, str> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(Cow::Owned(String::read(statement, i)?))
+    }
+}
+
+impl Readable for Cow<'static, [u8]> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        Ok(Cow::Owned(Vec::read(statement, i)?))
+    }
+}
+
 /// A helper to read at most a fixed number of `N` bytes from a column. This
 /// allocates the storage for the bytes read on the stack.
 pub struct FixedBytes<const N: usize> {
@@ -416,6 +2543,8 @@ pub struct FixedBytes<const N: usize> {
     data: [MaybeUninit<u8>; N],
     /// Number of bytes initialized.
     init: usize,
+    /// Length of the column, which might exceed `N`.
+    len: usize,
 }
 
 impl<const N: usize> FixedBytes<N> {
@@ -427,7 +2556,7 @@ impl<const N: usize> FixedBytes<N> {
     /// use sqlite_ll::{Connection, State, FixedBytes};
     ///
     /// let c: Connection = todo!();
-    /// let stmt = unsafe { c.prepare("SELECT id FROM users")? };
+    /// let stmt = c.prepare("SELECT id FROM users")?;
     ///
     /// while let State::Row = stmt.step()? {
     ///     let id = stmt.read::<FixedBytes<16>>(0)?;
@@ -455,6 +2584,41 @@ impl<const N: usize> FixedBytes<N> {
         }
     }
 
+    /// Coerce into the underlying bytes, erroring if the column didn't contain
+    /// exactly `N` bytes.
+    ///
+    /// Unlike [`into_bytes`], the error is a [`Code::MISMATCH`] which includes
+    /// the actual length of the column, so it can be propagated when a column
+    /// of the wrong size indicates a bug.
+    ///
+    /// [`into_bytes`]: FixedBytes::into_bytes
+    /// [`Code::MISMATCH`]: crate::Code::MISMATCH
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{Connection, FixedBytes, State};
+    ///
+    /// let c = Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT X'01020304', X'010203040506'")?;
+    /// assert_eq!(stmt.step()?, State::Row);
+    ///
+    /// let id = stmt.read::<FixedBytes<4>>(0)?.try_into_bytes()?;
+    /// assert_eq!(id, [1, 2, 3, 4]);
+    ///
+    /// let e = stmt.read::<FixedBytes<4>>(1)?.try_into_bytes().unwrap_err();
+    /// assert!(e.to_string().contains("expected 4 bytes, but the column has 6"));
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn try_into_bytes(self) -> Result<[u8; N]> {
+        let len = self.len;
+
+        match self.into_bytes() {
+            Some(bytes) if len == N => Ok(bytes),
+            _ => Err(length_mismatch(N, len)),
+        }
+    }
+
     /// Coerce into the slice of initialized memory which is present.
     ///
     /// # Examples
@@ -463,7 +2627,7 @@ impl<const N: usize> FixedBytes<N> {
     /// use sqlite_ll::{Connection, State, FixedBytes};
     ///
     /// let c: Connection = todo!();
-    /// let stmt = unsafe { c.prepare("SELECT id FROM users")? };
+    /// let stmt = c.prepare("SELECT id FROM users")?;
     ///
     /// while let State::Row = stmt.step()? {
     ///     let id = stmt.read::<FixedBytes<16>>(0)?;
@@ -491,6 +2655,7 @@ impl<const N: usize> Readable for FixedBytes<N> {
             // SAFETY: this is safe as per `MaybeUninit::uninit_array`, which isn't stable (yet).
             data: unsafe { MaybeUninit::<[MaybeUninit<u8>; N]>::uninit().assume_init() },
             init: 0,
+            len: 0,
         };
 
         unsafe {
@@ -510,11 +2675,28 @@ impl<const N: usize> Readable for FixedBytes<N> {
             );
 
             bytes.init = copied;
+            bytes.len = count;
             Ok(bytes)
         }
     }
 }
 
+impl<const N: usize> Readable for [u8; N] {
+    /// Read a blob of exactly `N` bytes, erroring with [`Code::MISMATCH`] if
+    /// the column has any other length.
+    ///
+    /// [`Code::MISMATCH`]: crate::Code::MISMATCH
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let bytes = statement.read_blob(i)?;
+
+        match <[u8; N]>::try_from(bytes) {
+            Ok(bytes) => Ok(bytes),
+            Err(..) => Err(length_mismatch(N, bytes.len())),
+        }
+    }
+}
+
 impl<T> Readable for Option<T>
 where
     T: Readable,
@@ -528,3 +2710,40 @@ where
         }
     }
 }
+
+/// Convert column bytes into a string.
+#[inline]
+fn utf8(bytes: &[u8]) -> Result<&str> {
+    match std::str::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(..) => Err(Error::from_code(ffi::SQLITE_MISUSE)),
+    }
+}
+
+/// Construct an error for a column which doesn't have the expected length.
+fn length_mismatch(expected: usize, actual: usize) -> Error {
+    Error::new(
+        ffi::SQLITE_MISMATCH,
+        Some(format!("expected {expected} bytes, but the column has {actual}").into()),
+    )
+}
+
+/// Construct an error for a value which is out of range for the given type.
+fn out_of_range<T>(value: T, ty: &str) -> Error
+where
+    T: std::fmt::Display,
+{
+    Error::new(
+        ffi::SQLITE_RANGE,
+        Some(format!("value {value} is out of range for `{ty}`").into()),
+    )
+}
+
+/// Progress handler used to enforce the budget of a statement, which flags
+/// that the budget has been exceeded and interrupts evaluation.
+extern "C" fn budget_exceeded(exceeded: *mut c_void) -> c_int {
+    // SAFETY: The handler is only installed for the duration of a step, while
+    // the flag is alive.
+    unsafe { (*(exceeded as *const Cell<bool>)).set(true) };
+    1
+}
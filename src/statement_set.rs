@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::Statement;
+
+/// A set of named prepared statements which are all prepared up front.
+///
+/// This allows an application to register all the SQL it uses in one place,
+/// so that errors in it are caught when the set is prepared rather than when
+/// a statement is first used.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{State, StatementSet, Value};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+///
+/// let mut set = StatementSet::builder()
+///     .add("insert_user", "INSERT INTO users VALUES (?, ?)")
+///     .add("count_users", "SELECT COUNT(*) FROM users")
+///     .prepare(&c)?;
+///
+/// set["insert_user"].execute_batch([&[Value::Text("Alice".into()), Value::Integer(42)]])?;
+///
+/// let count = &mut set["count_users"];
+/// count.reset()?;
+/// assert_eq!(count.step()?, State::Row);
+/// assert_eq!(count.read::<i64>(0)?, 1);
+///
+/// let e = StatementSet::builder()
+///     .add("broken", "SELECT * FROM missing")
+///     .prepare(&c)
+///     .err()
+///     .expect("expected error");
+///
+/// assert!(e.to_string().contains("broken"));
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub struct StatementSet {
+    statements: HashMap<Box<str>, Statement>,
+}
+
+impl StatementSet {
+    /// Construct a builder for a statement set.
+    #[inline]
+    pub fn builder() -> StatementSetBuilder {
+        StatementSetBuilder {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Get the statement with the given name.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Statement> {
+        self.statements.get(name)
+    }
+
+    /// Get the statement with the given name mutably.
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Statement> {
+        self.statements.get_mut(name)
+    }
+
+    /// Return the number of statements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    /// Test if the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Iterate over the names of the statements in the set.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.statements.keys().map(|name| &**name)
+    }
+}
+
+impl std::ops::Index<&str> for StatementSet {
+    type Output = Statement;
+
+    /// Get the statement with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no statement with the given name.
+    #[inline]
+    fn index(&self, name: &str) -> &Self::Output {
+        match self.get(name) {
+            Some(statement) => statement,
+            None => panic!("no statement named `{name}` in set"),
+        }
+    }
+}
+
+impl std::ops::IndexMut<&str> for StatementSet {
+    /// Get the statement with the given name mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no statement with the given name.
+    #[inline]
+    fn index_mut(&mut self, name: &str) -> &mut Self::Output {
+        match self.get_mut(name) {
+            Some(statement) => statement,
+            None => panic!("no statement named `{name}` in set"),
+        }
+    }
+}
+
+/// A builder for a [`StatementSet`].
+///
+/// See [`StatementSet::builder`].
+pub struct StatementSetBuilder {
+    entries: Vec<(Box<str>, Box<str>)>,
+}
+
+impl StatementSetBuilder {
+    /// Register a statement under the given name.
+    pub fn add<N, S>(mut self, name: N, statement: S) -> Self
+    where
+        N: AsRef<str>,
+        S: AsRef<str>,
+    {
+        self.entries
+            .push((name.as_ref().into(), statement.as_ref().into()));
+        self
+    }
+
+    /// Prepare all registered statements against the given connection.
+    ///
+    /// Errors if any statement fails to prepare, in which case the error
+    /// names the failing statement, or if a name is registered more than
+    /// once.
+    pub fn prepare(self, connection: &Connection) -> Result<StatementSet> {
+        let mut statements = HashMap::with_capacity(self.entries.len());
+
+        for (name, sql) in self.entries {
+            if statements.contains_key(&name) {
+                return Err(Error::custom(format_args!(
+                    "statement `{name}` registered more than once"
+                )));
+            }
+
+            let statement = connection
+                .prepare_persistent(&sql)
+                .map_err(|e| e.with_context(format_args!("preparing statement `{name}`")))?;
+
+            statements.insert(name, statement);
+        }
+
+        Ok(StatementSet { statements })
+    }
+}
@@ -0,0 +1,266 @@
+//! Readers for the statistics gathered by `ANALYZE`.
+//!
+//! `ANALYZE` stores statistics about tables and indexes in the
+//! `sqlite_stat1` table, which the query planner uses to estimate the cost of
+//! different plans. If SQLite is compiled with `SQLITE_ENABLE_STAT4`, samples
+//! of the index contents are also stored in `sqlite_stat4`.
+//!
+//! These readers decode the statistics into typed structures, which can be
+//! used to understand why a particular plan was chosen. Since statistics are
+//! only updated when `ANALYZE` runs, [`stale`] can be used to find tables
+//! whose statistics no longer reflect their contents.
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::stats;
+//!
+//! let c = sqlite_ll::Connection::open(":memory:")?;
+//! c.execute("
+//!     CREATE TABLE users (name TEXT, age INTEGER);
+//!     CREATE INDEX users_age ON users (age);
+//!     INSERT INTO users VALUES ('Alice', 42), ('Bob', 42), ('Carol', 69);
+//!     ANALYZE;
+//! ")?;
+//!
+//! let stats = stats::stat1(&c)?;
+//! assert_eq!(stats.len(), 1);
+//! assert_eq!(stats[0].table, "users");
+//! assert_eq!(stats[0].index.as_deref(), Some("users_age"));
+//! assert_eq!(stats[0].rows, 3);
+//! assert_eq!(stats[0].rows_per_key, [2]);
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+
+use sqlite3_sys as ffi;
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::State;
+use crate::utils;
+
+/// A row in `sqlite_stat1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stat1 {
+    /// The table the statistics are for.
+    pub table: String,
+    /// The index the statistics are for, or `None` if they are for a table
+    /// without any indexes.
+    pub index: Option<String>,
+    /// The estimated number of rows in the table or index.
+    pub rows: u64,
+    /// The estimated number of rows which have the same values in the first
+    /// `n + 1` columns of the index, for each column in the index.
+    pub rows_per_key: Vec<u64>,
+    /// If the index is marked as `unordered`, in which case the planner won't
+    /// use it for range queries or sorting.
+    pub unordered: bool,
+    /// If the index is marked with `noskipscan`, in which case the planner
+    /// won't use it for skip-scans.
+    pub no_skip_scan: bool,
+    /// The estimated size of the rows, as given by `sz=N`.
+    pub size: Option<u64>,
+}
+
+/// A sample in `sqlite_stat4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stat4 {
+    /// The table the sample is for.
+    pub table: String,
+    /// The index the sample is for.
+    pub index: String,
+    /// The number of entries in the index which are equal to the sample in
+    /// the first `n + 1` columns, for each column in the index.
+    pub eq: Vec<u64>,
+    /// The number of entries in the index which are less than the sample in
+    /// the first `n + 1` columns, for each column in the index.
+    pub lt: Vec<u64>,
+    /// The number of distinct entries in the index which are less than the
+    /// sample in the first `n + 1` columns, for each column in the index.
+    pub distinct_lt: Vec<u64>,
+    /// The sampled index entry, in the SQLite record format.
+    pub sample: Vec<u8>,
+}
+
+/// A table whose statistics no longer match its contents, as returned by
+/// [`stale`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stale {
+    /// The table the statistics are for.
+    pub table: String,
+    /// The number of rows according to the statistics.
+    pub estimated: u64,
+    /// The current number of rows in the table.
+    pub actual: u64,
+}
+
+/// Read `sqlite_stat1` from the main database.
+///
+/// Returns an empty list if `ANALYZE` has never been run.
+pub fn stat1(c: &Connection) -> Result<Vec<Stat1>> {
+    let mut out = Vec::new();
+
+    if !exists(c, "sqlite_stat1")? {
+        return Ok(out);
+    }
+
+    let mut stmt = c.prepare("SELECT tbl, idx, stat FROM main.sqlite_stat1 ORDER BY tbl, idx")?;
+
+    while let State::Row = stmt.step()? {
+        let table = stmt.read::<String>(0)?;
+        let index = stmt.read::<Option<String>>(1)?;
+        let stat = stmt.read::<String>(2)?;
+        out.push(parse_stat1(table, index, &stat)?);
+    }
+
+    Ok(out)
+}
+
+/// Read `sqlite_stat4` from the main database.
+///
+/// Returns an empty list if `ANALYZE` has never been run, or if SQLite isn't
+/// compiled with `SQLITE_ENABLE_STAT4`.
+pub fn stat4(c: &Connection) -> Result<Vec<Stat4>> {
+    let mut out = Vec::new();
+
+    if !exists(c, "sqlite_stat4")? {
+        return Ok(out);
+    }
+
+    let mut stmt = c.prepare(
+        "SELECT tbl, idx, neq, nlt, ndlt, sample FROM main.sqlite_stat4 ORDER BY tbl, idx",
+    )?;
+
+    while let State::Row = stmt.step()? {
+        let table = stmt.read::<String>(0)?;
+        let index = stmt.read::<String>(1)?;
+
+        out.push(Stat4 {
+            eq: parse_counts(&stmt.read::<String>(2)?, &table)?,
+            lt: parse_counts(&stmt.read::<String>(3)?, &table)?,
+            distinct_lt: parse_counts(&stmt.read::<String>(4)?, &table)?,
+            sample: stmt.read::<Vec<u8>>(5)?,
+            table,
+            index,
+        });
+    }
+
+    Ok(out)
+}
+
+/// Find tables whose row count differs from the estimate in `sqlite_stat1` by
+/// more than the given `ratio` of the estimate.
+///
+/// For example, a ratio of `0.5` reports tables which have grown or shrunk by
+/// more than half since `ANALYZE` was last run. Tables without statistics are
+/// not reported.
+///
+/// Note that this counts the rows of every table with statistics, which can
+/// be slow for large databases.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::stats;
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1), (2); ANALYZE;")?;
+/// assert!(stats::stale(&c, 0.5)?.is_empty());
+///
+/// c.execute("INSERT INTO numbers VALUES (3), (4)")?;
+/// let stale = stats::stale(&c, 0.5)?;
+/// assert_eq!(stale.len(), 1);
+/// assert_eq!((stale[0].estimated, stale[0].actual), (2, 4));
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn stale(c: &Connection, ratio: f64) -> Result<Vec<Stale>> {
+    let mut out = Vec::new();
+    let mut sql = String::new();
+    let mut previous = None;
+
+    for stat in stat1(c)? {
+        // Every index of a table has the same row count.
+        if previous.as_ref() == Some(&stat.table) {
+            continue;
+        }
+
+        previous = Some(stat.table.clone());
+
+        sql.clear();
+        sql.push_str("SELECT COUNT(*) FROM main.");
+        utils::push_identifier(&mut sql, &stat.table);
+        let actual = c.query_value::<i64, _>(&sql, ())?.max(0) as u64;
+
+        let difference = stat.rows.abs_diff(actual) as f64;
+
+        if difference > stat.rows as f64 * ratio {
+            out.push(Stale {
+                table: stat.table,
+                estimated: stat.rows,
+                actual,
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+/// Test if a table exists in the main database.
+fn exists(c: &Connection, name: &str) -> Result<bool> {
+    c.query_value(
+        "SELECT COUNT(*) > 0 FROM main.sqlite_schema WHERE type = 'table' AND name = ?",
+        (name,),
+    )
+}
+
+fn parse_stat1(table: String, index: Option<String>, stat: &str) -> Result<Stat1> {
+    let mut parts = stat.split_ascii_whitespace();
+
+    let rows = match parts.next().map(str::parse) {
+        Some(Ok(rows)) => rows,
+        _ => return Err(invalid(&table, stat)),
+    };
+
+    let mut stat1 = Stat1 {
+        table,
+        index,
+        rows,
+        rows_per_key: Vec::new(),
+        unordered: false,
+        no_skip_scan: false,
+        size: None,
+    };
+
+    for part in parts {
+        if let Ok(n) = part.parse() {
+            stat1.rows_per_key.push(n);
+        } else if part == "unordered" {
+            stat1.unordered = true;
+        } else if part == "noskipscan" {
+            stat1.no_skip_scan = true;
+        } else if let Some(size) = part.strip_prefix("sz=") {
+            stat1.size = size.parse().ok();
+        }
+
+        // Other keywords are ignored by SQLite, and so are they here.
+    }
+
+    Ok(stat1)
+}
+
+fn parse_counts(counts: &str, table: &str) -> Result<Vec<u64>> {
+    counts
+        .split_ascii_whitespace()
+        .map(|n| n.parse().map_err(|_| invalid(table, counts)))
+        .collect()
+}
+
+fn invalid(table: &str, stat: &str) -> Error {
+    Error::new(
+        ffi::SQLITE_CORRUPT,
+        Some(format!("invalid statistics `{stat}` for table `{table}`").into()),
+    )
+}
@@ -0,0 +1,187 @@
+use sqlite3_sys as ffi;
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+
+/// Limits on the storage used by a database, which are applied coherently
+/// through the `max_page_count`, `journal_size_limit` and `cache_size`
+/// pragmas.
+///
+/// Sizes are specified in bytes and converted into pages according to the
+/// page size of the database when the limits are applied. Limits which
+/// haven't been set are left unchanged.
+///
+/// Once the database has grown to its maximum size, statements which need
+/// more space fail with [`Code::FULL`].
+///
+/// [`Code::FULL`]: crate::Code::FULL
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{Code, StorageLimits, StorageUsage};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE blobs (data BLOB)")?;
+///
+/// StorageLimits::new()
+///     .max_size(64 * 1024)
+///     .cache_size(16 * 1024)
+///     .apply(&c)?;
+///
+/// let usage = StorageUsage::read(&c)?;
+/// assert_eq!(usage.max_size(), 64 * 1024);
+///
+/// let e = c.execute("INSERT INTO blobs VALUES (zeroblob(128 * 1024))").unwrap_err();
+/// assert_eq!(e.code(), Code::FULL);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StorageLimits {
+    max_size: Option<u64>,
+    journal_size_limit: Option<u64>,
+    cache_size: Option<u64>,
+}
+
+impl StorageLimits {
+    /// Construct a set of limits where nothing is limited.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of the database in bytes.
+    ///
+    /// This is rounded down to a whole number of pages, and must fit at least
+    /// one page and the current contents of the database.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Set the maximum size in bytes of a journal or write-ahead log which is
+    /// left behind after a transaction or checkpoint.
+    pub fn journal_size_limit(mut self, bytes: u64) -> Self {
+        self.journal_size_limit = Some(bytes);
+        self
+    }
+
+    /// Set the size of the page cache of the connection in bytes.
+    ///
+    /// This is rounded down to a whole number of pages and must fit at least
+    /// one page. If a maximum size is set, the cache is capped to it since it
+    /// can never hold more pages than the database does.
+    pub fn cache_size(mut self, bytes: u64) -> Self {
+        self.cache_size = Some(bytes);
+        self
+    }
+
+    /// Apply the limits to the main database of the given connection.
+    ///
+    /// All limits are validated before any of them are applied, so this
+    /// errors with [`Code::RANGE`] without changing anything if a limit is
+    /// too small for the page size or current size of the database. The
+    /// limits are then applied one at a time, so if applying one of them
+    /// fails, the ones applied before it remain in effect.
+    ///
+    /// [`Code::RANGE`]: crate::Code::RANGE
+    pub fn apply(&self, c: &Connection) -> Result<()> {
+        let usage = StorageUsage::read(c)?;
+
+        let max_page_count = match self.max_size {
+            Some(bytes) => {
+                let pages = bytes / usage.page_size;
+
+                if pages == 0 || pages < usage.page_count {
+                    return Err(out_of_range(format!(
+                        "maximum size of {bytes} bytes is smaller than the current database size of {} bytes",
+                        usage.size().max(usage.page_size)
+                    )));
+                }
+
+                Some(pages)
+            }
+            None => None,
+        };
+
+        let cache_pages = match self.cache_size {
+            Some(bytes) => {
+                let pages = bytes / usage.page_size;
+
+                if pages == 0 {
+                    return Err(out_of_range(format!(
+                        "cache size of {bytes} bytes is smaller than the page size of {} bytes",
+                        usage.page_size
+                    )));
+                }
+
+                Some(match max_page_count {
+                    Some(max) => pages.min(max),
+                    None => pages,
+                })
+            }
+            None => None,
+        };
+
+        if let Some(pages) = max_page_count {
+            c.execute(format!("PRAGMA max_page_count = {pages}"))?;
+        }
+
+        if let Some(bytes) = self.journal_size_limit {
+            c.execute(format!("PRAGMA journal_size_limit = {bytes}"))?;
+        }
+
+        if let Some(pages) = cache_pages {
+            c.execute(format!("PRAGMA cache_size = {pages}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The storage used by a database.
+///
+/// See [`StorageLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StorageUsage {
+    /// The size of a page in bytes.
+    pub page_size: u64,
+    /// The number of pages in the database.
+    pub page_count: u64,
+    /// The number of unused pages in the database, which are reused before
+    /// the database grows.
+    pub freelist_count: u64,
+    /// The maximum number of pages the database may grow to.
+    pub max_page_count: u64,
+}
+
+impl StorageUsage {
+    /// Read the storage used by the main database of the given connection.
+    pub fn read(c: &Connection) -> Result<Self> {
+        Ok(Self {
+            page_size: pragma(c, "page_size")?,
+            page_count: pragma(c, "page_count")?,
+            freelist_count: pragma(c, "freelist_count")?,
+            max_page_count: pragma(c, "max_page_count")?,
+        })
+    }
+
+    /// The size of the database in bytes.
+    pub fn size(&self) -> u64 {
+        self.page_size * self.page_count
+    }
+
+    /// The maximum size of the database in bytes.
+    pub fn max_size(&self) -> u64 {
+        self.page_size.saturating_mul(self.max_page_count)
+    }
+}
+
+/// Read the numerical value of a pragma.
+fn pragma(c: &Connection, name: &str) -> Result<u64> {
+    c.query_value(format!("PRAGMA {name}"), ())
+}
+
+fn out_of_range(message: String) -> Error {
+    Error::new(ffi::SQLITE_RANGE, Some(message.into()))
+}
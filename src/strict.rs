@@ -0,0 +1,96 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+use crate::value::Type;
+
+/// A wrapper which reads a value only if the column has the matching type.
+///
+/// SQLite implicitly converts between types when reading a column, so that
+/// reading an integer column as a [`String`] produces its decimal text and
+/// reading a text column as an [`i64`] produces whatever number prefix it
+/// happens to have. Reading through this wrapper instead errors with
+/// [`Code::MISMATCH`] unless the type of the column is the one which the
+/// wrapped type is stored as:
+/// * Integers and [`bool`] require an integer column.
+/// * [`f32`] and [`f64`] require a float column.
+/// * Strings require a text column.
+/// * Byte vectors and byte arrays require a blob column.
+///
+/// `NULL` is a mismatch for every type, so nullable columns should be read as
+/// `Option<Strict<T>>`. Binding the wrapper binds the wrapped value as-is.
+///
+/// [`Code::MISMATCH`]: crate::Code::MISMATCH
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{Code, State, Strict};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// let mut stmt = c.prepare("SELECT 42, '42', NULL")?;
+/// assert_eq!(stmt.step()?, State::Row);
+///
+/// assert_eq!(stmt.read::<Strict<i64>>(0)?, Strict(42));
+/// assert_eq!(stmt.read::<i64>(1)?, 42);
+/// assert_eq!(stmt.read::<Strict<i64>>(1).unwrap_err().code(), Code::MISMATCH);
+/// assert_eq!(stmt.read::<Strict<String>>(0).unwrap_err().code(), Code::MISMATCH);
+/// assert_eq!(stmt.read::<Option<Strict<String>>>(2)?, None);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Strict<T>(pub T);
+
+impl<T> Bindable for Strict<T>
+where
+    T: Bindable,
+{
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.0.bind(statement, i)
+    }
+}
+
+macro_rules! strict {
+    ($($kind:ident => [$($ty:ty),* $(,)?]),* $(,)?) => {
+        $($(
+            impl Readable for Strict<$ty> {
+                #[inline]
+                fn read(statement: &Statement, i: usize) -> Result<Self> {
+                    expect(statement, i, Type::$kind)?;
+                    Ok(Strict(<$ty>::read(statement, i)?))
+                }
+            }
+        )*)*
+    };
+}
+
+strict! {
+    Integer => [i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, bool],
+    Float => [f32, f64],
+    Text => [String, Box<str>, Rc<str>, Arc<str>],
+    Blob => [Vec<u8>, Box<[u8]>, Rc<[u8]>, Arc<[u8]>],
+}
+
+impl<const N: usize> Readable for Strict<[u8; N]> {
+    #[inline]
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        expect(statement, i, Type::Blob)?;
+        Ok(Strict(<[u8; N]>::read(statement, i)?))
+    }
+}
+
+/// Check that the column has the expected type.
+fn expect(statement: &Statement, i: usize, expected: Type) -> Result<()> {
+    let actual = statement.column_type(i);
+
+    if actual != expected {
+        return Err(Error::new(
+            sqlite3_sys::SQLITE_MISMATCH,
+            Some(format!("expected {expected:?}, but the column has type {actual:?}").into()),
+        ));
+    }
+
+    Ok(())
+}
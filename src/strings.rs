@@ -0,0 +1,123 @@
+use core::cmp::Ordering;
+
+use libc::{c_int, c_uint};
+use sqlite3_sys as ffi;
+
+use crate::error::Result;
+use crate::utils;
+
+/// Test if a string matches a `GLOB` pattern, using `sqlite3_strglob`.
+///
+/// This has the same semantics as the `GLOB` operator, which is case
+/// sensitive and supports `*`, `?` and `[...]` wildcards.
+///
+/// Errors with [`Code::MISUSE`] if either string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// assert!(sqlite_ll::strglob("*.txt", "notes.txt")?);
+/// assert!(!sqlite_ll::strglob("*.txt", "notes.TXT")?);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn strglob(pattern: &str, string: &str) -> Result<bool> {
+    let pattern = utils::string_to_cstring(pattern)?;
+    let string = utils::string_to_cstring(string)?;
+    Ok(unsafe { ffi::sqlite3_strglob(pattern.as_ptr(), string.as_ptr()) } == 0)
+}
+
+/// Test if a string matches a `LIKE` pattern, using `sqlite3_strlike`.
+///
+/// This has the same semantics as the `LIKE` operator with the given `ESCAPE`
+/// character, which is case insensitive for ASCII characters and supports the
+/// `%` and `_` wildcards.
+///
+/// Errors with [`Code::MISUSE`] if either string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// assert!(sqlite_ll::strlike("a%", "ABC", None)?);
+/// assert!(sqlite_ll::strlike("100!%", "100%", Some('!'))?);
+/// assert!(!sqlite_ll::strlike("100!%", "1000", Some('!'))?);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn strlike(pattern: &str, string: &str, escape: Option<char>) -> Result<bool> {
+    let pattern = utils::string_to_cstring(pattern)?;
+    let string = utils::string_to_cstring(string)?;
+    let escape = escape.map_or(0, |c| c as c_uint);
+    Ok(unsafe { ffi::sqlite3_strlike(pattern.as_ptr(), string.as_ptr(), escape) } == 0)
+}
+
+/// Compare two strings case insensitively, using `sqlite3_stricmp`.
+///
+/// Like the `NOCASE` collation, only ASCII characters are folded.
+///
+/// Errors with [`Code::MISUSE`] if either string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(sqlite_ll::stricmp("Hello", "hELLO")?, Ordering::Equal);
+/// assert_eq!(sqlite_ll::stricmp("a", "B")?, Ordering::Less);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn stricmp(a: &str, b: &str) -> Result<Ordering> {
+    let a = utils::string_to_cstring(a)?;
+    let b = utils::string_to_cstring(b)?;
+    Ok(unsafe { ffi::sqlite3_stricmp(a.as_ptr(), b.as_ptr()) }.cmp(&0))
+}
+
+/// Compare at most the first `n` bytes of two strings case insensitively,
+/// using `sqlite3_strnicmp`.
+///
+/// See [`stricmp`].
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(sqlite_ll::strnicmp("Hello", "HELP", 3)?, Ordering::Equal);
+/// assert_eq!(sqlite_ll::strnicmp("Hello", "HELP", 4)?, Ordering::Less);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn strnicmp(a: &str, b: &str, n: usize) -> Result<Ordering> {
+    let a = utils::string_to_cstring(a)?;
+    let b = utils::string_to_cstring(b)?;
+    let n = c_int::try_from(n).unwrap_or(c_int::MAX);
+    Ok(unsafe { ffi::sqlite3_strnicmp(a.as_ptr(), b.as_ptr(), n) }.cmp(&0))
+}
+
+/// Test if the given SQL text ends with a complete statement, using
+/// `sqlite3_complete`.
+///
+/// A statement is complete if it ends with a semicolon which isn't part of a
+/// string literal, comment or trigger body. This doesn't check that the
+/// statement is valid, and is intended for tools such as shells which need to
+/// know if more input should be read before preparing it.
+///
+/// Errors with [`Code::MISUSE`] if the string contains a `NUL` character.
+///
+/// [`Code::MISUSE`]: crate::Code::MISUSE
+///
+/// # Examples
+///
+/// ```
+/// assert!(sqlite_ll::is_complete("SELECT 1;")?);
+/// assert!(!sqlite_ll::is_complete("SELECT 1")?);
+/// assert!(!sqlite_ll::is_complete("SELECT ';")?);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn is_complete(sql: &str) -> Result<bool> {
+    let sql = utils::string_to_cstring(sql)?;
+    Ok(unsafe { ffi::sqlite3_complete(sql.as_ptr()) } != 0)
+}
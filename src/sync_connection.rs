@@ -0,0 +1,226 @@
+use core::ptr::NonNull;
+
+use sqlite3_sys as ffi;
+
+use crate::connection::Connection;
+use crate::db_mutex::DbMutexGuard;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::row::FromRow;
+use crate::statement::{Readable, Statement};
+
+/// A connection in the serialized threading mode which can be shared across
+/// threads.
+///
+/// A [`Connection`] is only [`Send`], since by default SQLite might be
+/// configured to not protect a connection from being used by multiple threads
+/// at once. This wrapper can only be constructed from a connection which has
+/// a database mutex, meaning that it was opened in the serialized threading
+/// mode such as through [`OpenOptions::set_full_mutex`], and is therefore
+/// [`Sync`] so that it can be shared behind an [`Arc`].
+///
+/// Only operations which are safe to perform concurrently are exposed, and
+/// each of them holds the database mutex for its whole duration so that errors
+/// are reported for the operation which caused them. Statements prepared
+/// through it are regular [`Statement`]s which can be moved to other threads,
+/// and SQLite serializes their use against the connection.
+///
+/// Since every operation on the connection is serialized, this is only
+/// suitable for applications which perform few writes. Note that other threads
+/// might interleave between separate calls, so values such as
+/// [`change_count`] might reflect statements executed by other threads unless
+/// the mutex is held using [`lock`].
+///
+/// [`OpenOptions::set_full_mutex`]: crate::OpenOptions::set_full_mutex
+/// [`Arc`]: std::sync::Arc
+/// [`change_count`]: SyncConnection::change_count
+/// [`lock`]: SyncConnection::lock
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use sqlite_ll::{OpenOptions, State, SyncConnection};
+///
+/// let c = OpenOptions::new().set_create().set_read_write().set_full_mutex().open(":memory:")?;
+/// let c = Arc::new(SyncConnection::new(c).map_err(|(_, e)| e)?);
+/// c.execute("CREATE TABLE numbers (n INTEGER)")?;
+///
+/// let threads = (0..4).map(|n| {
+///     let c = c.clone();
+///
+///     thread::spawn(move || {
+///         let mut stmt = c.prepare("INSERT INTO numbers VALUES (?)")?;
+///         stmt.bind(1, n)?;
+///         assert_eq!(stmt.step()?, State::Done);
+///         Ok::<_, sqlite_ll::Error>(())
+///     })
+/// }).collect::<Vec<_>>();
+///
+/// for t in threads {
+///     t.join().unwrap()?;
+/// }
+///
+/// assert_eq!(c.query_value::<i64, _>("SELECT SUM(n) FROM numbers", ())?, 6);
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub struct SyncConnection {
+    inner: Connection,
+    mutex: NonNull<ffi::sqlite3_mutex>,
+}
+
+// SAFETY: Connections are only constructed if they are in serialized mode,
+// and only operations guarded by the database mutex are exposed.
+unsafe impl Sync for SyncConnection {}
+unsafe impl Send for SyncConnection {}
+
+impl SyncConnection {
+    /// Wrap a connection, or hand it back with an error with
+    /// [`Code::MISUSE`] if it isn't in the serialized threading mode.
+    ///
+    /// [`Code::MISUSE`]: crate::Code::MISUSE
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{Code, OpenOptions, SyncConnection};
+    ///
+    /// let c = OpenOptions::new().set_create().set_read_write().set_no_mutex().open(":memory:")?;
+    /// let (_, e) = SyncConnection::new(c).err().expect("expected error");
+    /// assert_eq!(e.code(), Code::MISUSE);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn new(connection: Connection) -> Result<Self, (Connection, Error)> {
+        let mutex = unsafe { ffi::sqlite3_db_mutex(connection.as_raw()) };
+
+        match NonNull::new(mutex) {
+            Some(mutex) => Ok(Self {
+                inner: connection,
+                mutex,
+            }),
+            None => Err((
+                connection,
+                Error::new(
+                    ffi::SQLITE_MISUSE,
+                    Some("connection is not in the serialized threading mode".into()),
+                ),
+            )),
+        }
+    }
+
+    /// Execute a statement without processing the resulting rows if any.
+    ///
+    /// See [`Connection::execute`].
+    pub fn execute<T>(&self, statement: T) -> Result<()>
+    where
+        T: AsRef<str>,
+    {
+        self.with(|c| c.execute(statement))
+    }
+
+    /// Create a prepared statement.
+    ///
+    /// See [`Connection::prepare`].
+    pub fn prepare<T>(&self, statement: T) -> Result<Statement>
+    where
+        T: AsRef<str>,
+    {
+        self.with(|c| c.prepare(statement))
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row.
+    ///
+    /// See [`Connection::query_row`].
+    pub fn query_row<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        self.with(|c| c.query_row(statement, params))
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row, and read its first column.
+    ///
+    /// See [`Connection::query_value`].
+    pub fn query_value<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: Readable,
+        P: Params,
+    {
+        self.with(|c| c.query_value(statement, params))
+    }
+
+    /// Return the number of rows changed by the most recent statement on any
+    /// thread.
+    ///
+    /// See [`Connection::change_count`].
+    pub fn change_count(&self) -> usize {
+        self.with(Connection::change_count)
+    }
+
+    /// Return the total number of rows changed since the connection was
+    /// opened.
+    ///
+    /// See [`Connection::total_change_count`].
+    pub fn total_change_count(&self) -> usize {
+        self.with(Connection::total_change_count)
+    }
+
+    /// Test if the connection is in autocommit mode.
+    ///
+    /// See [`Connection::is_autocommit`].
+    pub fn is_autocommit(&self) -> bool {
+        self.with(Connection::is_autocommit)
+    }
+
+    /// Acquire the database mutex, preventing other threads from using the
+    /// connection until the returned guard is dropped.
+    ///
+    /// This can be used to perform compound operations without other threads
+    /// interleaving with them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::{OpenOptions, SyncConnection};
+    ///
+    /// let c = OpenOptions::new().set_create().set_read_write().set_full_mutex().open(":memory:")?;
+    /// let c = SyncConnection::new(c).map_err(|(_, e)| e)?;
+    /// c.execute("CREATE TABLE numbers (n INTEGER)")?;
+    ///
+    /// let changed = {
+    ///     let _guard = c.lock();
+    ///     c.execute("INSERT INTO numbers VALUES (1), (2)")?;
+    ///     c.change_count()
+    /// };
+    ///
+    /// assert_eq!(changed, 2);
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    #[inline]
+    pub fn lock(&self) -> DbMutexGuard<'_> {
+        // SAFETY: The mutex is owned by the connection, which outlives the
+        // guard.
+        unsafe { DbMutexGuard::enter(self.mutex) }
+    }
+
+    /// Unwrap the underlying connection.
+    #[inline]
+    pub fn into_inner(self) -> Connection {
+        self.inner
+    }
+
+    /// Run the given operation while holding the database mutex.
+    fn with<F, O>(&self, f: F) -> O
+    where
+        F: FnOnce(&Connection) -> O,
+    {
+        let _guard = self.lock();
+        f(&self.inner)
+    }
+}
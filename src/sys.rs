@@ -0,0 +1,40 @@
+//! Declarations for SQLite functions and constants which are not provided by
+//! `sqlite3-sys`.
+
+use libc::{c_char, c_int, c_uint};
+use sqlite3_sys as ffi;
+
+/// Hint that the prepared statement will be retained for a long time and
+/// probably reused many times.
+pub(crate) const SQLITE_PREPARE_PERSISTENT: c_uint = 0x01;
+
+/// The number of times the prepared statement has been automatically
+/// regenerated due to schema changes.
+pub(crate) const SQLITE_STMTSTATUS_REPREPARE: c_int = 5;
+
+extern "C" {
+    pub(crate) fn sqlite3_keyword_count() -> c_int;
+
+    pub(crate) fn sqlite3_keyword_name(
+        index: c_int,
+        name: *mut *const c_char,
+        len: *mut c_int,
+    ) -> c_int;
+
+    pub(crate) fn sqlite3_keyword_check(name: *const c_char, len: c_int) -> c_int;
+
+    #[cfg(feature = "is_interrupted")]
+    pub(crate) fn sqlite3_is_interrupted(db: *mut ffi::sqlite3) -> c_int;
+
+    #[cfg(feature = "normalize")]
+    pub(crate) fn sqlite3_normalized_sql(stmt: *mut ffi::sqlite3_stmt) -> *const c_char;
+
+    pub(crate) fn sqlite3_prepare_v3(
+        db: *mut ffi::sqlite3,
+        sql: *const c_char,
+        n_byte: c_int,
+        prep_flags: c_uint,
+        stmt: *mut *mut ffi::sqlite3_stmt,
+        tail: *mut *const c_char,
+    ) -> c_int;
+}
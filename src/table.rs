@@ -0,0 +1,264 @@
+use std::fmt::Write;
+
+use crate::error::Result;
+use crate::statement::{State, Statement};
+use crate::value::ValueRef;
+
+/// The style used by [`TableFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TableStyle {
+    /// An ASCII table with borders, like `.mode table` in the SQLite shell.
+    #[default]
+    Ascii,
+    /// A GitHub flavored markdown table.
+    Markdown,
+}
+
+/// Format the results of a statement as an aligned table.
+///
+/// This is useful for debug logging and administrative tools. Text is rendered
+/// as-is, blobs are rendered as hex literals and `NULL` as an empty cell by
+/// default. Cells which are wider than [`max_width`] and rows beyond
+/// [`max_rows`] are truncated.
+///
+/// [`max_width`]: TableFormat::max_width
+/// [`max_rows`]: TableFormat::max_rows
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{TableFormat, TableStyle};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', NULL);")?;
+///
+/// let mut stmt = c.prepare("SELECT name, age FROM users")?;
+///
+/// assert_eq!(TableFormat::new().format(&mut stmt)?, "\
+/// +-------+-----+
+/// | name  | age |
+/// +-------+-----+
+/// | Alice | 42  |
+/// | Bob   |     |
+/// +-------+-----+
+/// ");
+///
+/// stmt.reset()?;
+///
+/// let table = TableFormat::new()
+///     .style(TableStyle::Markdown)
+///     .null("NULL")
+///     .format(&mut stmt)?;
+///
+/// assert_eq!(table, "\
+/// | name  | age  |
+/// |-------|------|
+/// | Alice | 42   |
+/// | Bob   | NULL |
+/// ");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableFormat {
+    style: TableStyle,
+    max_width: Option<usize>,
+    max_rows: Option<usize>,
+    null: String,
+}
+
+impl TableFormat {
+    /// Construct a new formatter with the default settings.
+    pub fn new() -> Self {
+        Self {
+            style: TableStyle::Ascii,
+            max_width: None,
+            max_rows: None,
+            null: String::new(),
+        }
+    }
+
+    /// Set the style of the table.
+    pub fn style(mut self, style: TableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Truncate cells which are wider than the given number of characters,
+    /// marking them with a trailing `...`.
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Only include up to the given number of rows, followed by a line with
+    /// the number of rows which were left out.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Set the text used for `NULL` values, which defaults to an empty string.
+    pub fn null(mut self, null: &str) -> Self {
+        self.null = null.to_owned();
+        self
+    }
+
+    /// Step the statement to completion and format its result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite_ll::TableFormat;
+    ///
+    /// let c = sqlite_ll::Connection::open(":memory:")?;
+    /// let mut stmt = c.prepare("SELECT 'a long piece of text' AS text UNION ALL SELECT 'short' UNION ALL SELECT 'more'")?;
+    ///
+    /// let table = TableFormat::new().max_width(10).max_rows(2).format(&mut stmt)?;
+    ///
+    /// assert_eq!(table, "\
+    /// +------------+
+    /// | text       |
+    /// +------------+
+    /// | a long ... |
+    /// | short      |
+    /// +------------+
+    /// (1 more row)
+    /// ");
+    /// # Ok::<_, sqlite_ll::Error>(())
+    /// ```
+    pub fn format(&self, statement: &mut Statement) -> Result<String> {
+        let count = statement.column_count();
+
+        let header = (0..count)
+            .map(|i| Ok(self.cell(statement.column_name(i)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows = Vec::new();
+        let mut skipped = 0usize;
+
+        while let State::Row = statement.step()? {
+            if self.max_rows.map_or(false, |max| rows.len() >= max) {
+                skipped += 1;
+                continue;
+            }
+
+            let mut row = Vec::with_capacity(count);
+
+            for i in 0..count {
+                let cell = match statement.read_value_ref(i)? {
+                    ValueRef::Null => self.cell(&self.null),
+                    ValueRef::Text(text) => self.cell(text),
+                    value => self.cell(&value.to_string()),
+                };
+
+                row.push(cell);
+            }
+
+            rows.push(row);
+        }
+
+        let mut widths = header.iter().map(|c| width(c)).collect::<Vec<_>>();
+
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(width(cell));
+            }
+        }
+
+        let mut out = String::new();
+
+        match self.style {
+            TableStyle::Ascii => {
+                separator(&mut out, &widths, '+');
+                line(&mut out, &widths, &header);
+                separator(&mut out, &widths, '+');
+
+                for row in &rows {
+                    line(&mut out, &widths, row);
+                }
+
+                separator(&mut out, &widths, '+');
+            }
+            TableStyle::Markdown => {
+                line(&mut out, &widths, &header);
+                separator(&mut out, &widths, '|');
+
+                for row in &rows {
+                    line(&mut out, &widths, row);
+                }
+            }
+        }
+
+        if skipped > 0 {
+            let rows = if skipped == 1 { "row" } else { "rows" };
+            let _ = writeln!(out, "({skipped} more {rows})");
+        }
+
+        Ok(out)
+    }
+
+    /// Escape and truncate the text of a cell.
+    fn cell(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+
+        for c in text.chars() {
+            match c {
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                '|' if self.style == TableStyle::Markdown => out.push_str("\\|"),
+                c if c.is_control() => {
+                    let _ = write!(out, "\\u{{{:x}}}", c as u32);
+                }
+                c => out.push(c),
+            }
+        }
+
+        if let Some(max) = self.max_width {
+            if width(&out) > max {
+                let keep = max.saturating_sub(3);
+                let end = out.char_indices().nth(keep).map_or(out.len(), |(i, _)| i);
+                out.truncate(end);
+                out.push_str("...");
+            }
+        }
+
+        out
+    }
+}
+
+impl Default for TableFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+fn separator(out: &mut String, widths: &[usize], corner: char) {
+    out.push(corner);
+
+    for w in widths {
+        out.extend(std::iter::repeat('-').take(w + 2));
+        out.push(corner);
+    }
+
+    out.push('\n');
+}
+
+fn line(out: &mut String, widths: &[usize], cells: &[String]) {
+    out.push('|');
+
+    for (w, cell) in widths.iter().zip(cells) {
+        out.push(' ');
+        out.push_str(cell);
+        out.extend(std::iter::repeat(' ').take(w - width(cell) + 1));
+        out.push('|');
+    }
+
+    out.push('\n');
+}
@@ -0,0 +1,293 @@
+//! Helpers for setting up databases in tests.
+//!
+//! The [`Fixture`] builder describes tables and the rows they should contain,
+//! and can then be opened any number of times to get fresh in-memory
+//! connections with the same deterministic data, so that tests don't have to
+//! repeat their setup or share state with each other.
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::testing::Fixture;
+//!
+//! let fixture = Fixture::new()
+//!     .table("users", ["id INTEGER PRIMARY KEY", "name TEXT", "age INTEGER"])
+//!     .rows([(1, "Alice", 42), (2, "Bob", 69)]);
+//!
+//! let a = fixture.open()?;
+//! let b = fixture.open()?;
+//!
+//! a.execute("DELETE FROM users")?;
+//!
+//! assert_eq!(a.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?, 0);
+//! assert_eq!(b.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?, 2);
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+//!
+//! The [`snapshot`] function renders the result of a query into a canonical
+//! text form, which can be compared against golden files.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::params::Params;
+use crate::statement::{State, Statement};
+use crate::utils;
+use crate::value::ValueRef;
+
+/// A row which binds itself to an insert statement.
+type Row = Arc<dyn Fn(&mut Statement) -> Result<()> + Send + Sync>;
+
+/// A builder describing the contents of a test database.
+///
+/// See the [module level documentation][self] for more.
+#[derive(Clone, Default)]
+pub struct Fixture {
+    steps: Vec<Step>,
+}
+
+#[derive(Clone)]
+enum Step {
+    Execute(String),
+    Table {
+        name: String,
+        columns: Vec<String>,
+        rows: Vec<Row>,
+    },
+}
+
+impl Fixture {
+    /// Construct a new empty fixture.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a table with the given column definitions.
+    ///
+    /// Each column is specified the same way as in a `CREATE TABLE`
+    /// statement, such as `"id INTEGER PRIMARY KEY"`.
+    pub fn table<I>(mut self, name: &str, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.steps.push(Step::Table {
+            name: name.to_owned(),
+            columns: columns.into_iter().map(|c| c.as_ref().to_owned()).collect(),
+            rows: Vec::new(),
+        });
+
+        self
+    }
+
+    /// Add a row to the most recently added table.
+    ///
+    /// The parameters are bound to the columns of the table in order, and
+    /// columns without a parameter are set to `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no table has been added yet.
+    pub fn row<P>(mut self, params: P) -> Self
+    where
+        P: Params + Clone + Send + Sync + 'static,
+    {
+        match self.steps.last_mut() {
+            Some(Step::Table { rows, .. }) => {
+                rows.push(Arc::new(move |s| params.clone().bind_params(s)));
+            }
+            _ => panic!("rows must be added after a table"),
+        }
+
+        self
+    }
+
+    /// Add rows to the most recently added table.
+    ///
+    /// See [`Fixture::row`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if no table has been added yet.
+    pub fn rows<I>(self, rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Params + Clone + Send + Sync + 'static,
+    {
+        rows.into_iter().fold(self, Fixture::row)
+    }
+
+    /// Execute the given statements while setting up the database, such as
+    /// to create indexes or views.
+    ///
+    /// Statements are executed in order with the tables added to the
+    /// fixture.
+    pub fn execute<T>(mut self, statement: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.steps
+            .push(Step::Execute(statement.as_ref().to_owned()));
+        self
+    }
+
+    /// Open a new in-memory connection containing the fixture.
+    pub fn open(&self) -> Result<Connection> {
+        let c = Connection::open(":memory:")?;
+        self.apply(&c)?;
+        Ok(c)
+    }
+
+    /// Apply the fixture to an existing connection.
+    ///
+    /// Everything is set up in a single transaction, which is rolled back if
+    /// an error occurs.
+    pub fn apply(&self, c: &Connection) -> Result<()> {
+        c.execute("BEGIN")?;
+
+        match self.apply_steps(c) {
+            Ok(()) => c.execute("COMMIT"),
+            Err(error) => {
+                let _ = c.execute("ROLLBACK");
+                Err(error)
+            }
+        }
+    }
+
+    fn apply_steps(&self, c: &Connection) -> Result<()> {
+        for step in &self.steps {
+            match step {
+                Step::Execute(statement) => {
+                    c.execute(statement)?;
+                }
+                Step::Table {
+                    name,
+                    columns,
+                    rows,
+                } => {
+                    let mut sql = String::from("CREATE TABLE ");
+                    utils::push_identifier(&mut sql, name);
+                    sql.push_str(" (");
+                    sql.push_str(&columns.join(", "));
+                    sql.push(')');
+                    c.execute(&sql)?;
+
+                    if rows.is_empty() {
+                        continue;
+                    }
+
+                    let mut sql = String::from("INSERT INTO ");
+                    utils::push_identifier(&mut sql, name);
+                    sql.push_str(" VALUES (");
+
+                    for i in 0..columns.len() {
+                        if i > 0 {
+                            sql.push_str(", ");
+                        }
+
+                        sql.push('?');
+                    }
+
+                    sql.push(')');
+
+                    let mut insert = c.prepare(&sql)?;
+
+                    for row in rows {
+                        insert.reset()?;
+                        insert.clear_bindings()?;
+                        row(&mut insert)?;
+                        while let State::Row = insert.step()? {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Render the full result of a query into a canonical text form, suitable for
+/// comparing against golden files in snapshot tests.
+///
+/// See [`snapshot_statement`] for the format used.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::testing::{self, Fixture};
+///
+/// let c = Fixture::new()
+///     .table("users", ["id INTEGER", "name TEXT", "photo BLOB", "score REAL"])
+///     .rows([(1, "Alice", &b"\x42\x69"[..], 4.5)])
+///     .row((2, "Bob"))
+///     .open()?;
+///
+/// let snapshot = testing::snapshot(&c, "SELECT * FROM users ORDER BY id")?;
+///
+/// assert_eq!(snapshot, "\
+/// \"id\" | \"name\" | \"photo\" | \"score\"
+/// INTEGER 1 | TEXT 'Alice' | BLOB X'4269' | REAL 4.5
+/// INTEGER 2 | TEXT 'Bob' | NULL | NULL
+/// (2 rows)
+/// ");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+pub fn snapshot(c: &Connection, statement: &str) -> Result<String> {
+    let mut statement = c.prepare(statement)?;
+    snapshot_statement(&mut statement)
+}
+
+/// Step the statement to completion and render its result into a canonical
+/// text form, suitable for comparing against golden files in snapshot tests.
+///
+/// The first line contains the quoted names of the columns in order. Each row
+/// is rendered on its own line, where every value is prefixed with its storage
+/// class and rendered as an SQL literal, such as `TEXT 'Alice'` or `BLOB
+/// X'4269'`. The last line contains the number of rows. Since the rendering
+/// only depends on the values, rows should be ordered by the query for the
+/// snapshot to be stable.
+pub fn snapshot_statement(statement: &mut Statement) -> Result<String> {
+    let mut out = String::new();
+    let count = statement.column_count();
+
+    for i in 0..count {
+        if i > 0 {
+            out.push_str(" | ");
+        }
+
+        utils::push_identifier(&mut out, statement.column_name(i)?);
+    }
+
+    out.push('\n');
+
+    let mut rows = 0usize;
+
+    while let State::Row = statement.step()? {
+        for i in 0..count {
+            if i > 0 {
+                out.push_str(" | ");
+            }
+
+            let value = statement.read_value_ref(i)?;
+
+            let ty = match value {
+                ValueRef::Blob(..) => "BLOB ",
+                ValueRef::Float(..) => "REAL ",
+                ValueRef::Integer(..) => "INTEGER ",
+                ValueRef::Text(..) => "TEXT ",
+                ValueRef::Null => "",
+            };
+
+            let _ = write!(out, "{ty}{value}");
+        }
+
+        out.push('\n');
+        rows += 1;
+    }
+
+    let _ = writeln!(out, "({rows} {})", if rows == 1 { "row" } else { "rows" });
+    Ok(out)
+}
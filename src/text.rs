@@ -0,0 +1,60 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, Readable, Statement};
+
+/// A wrapper which binds and reads a value through its textual
+/// representation.
+///
+/// The value is bound as text using its [`Display`] implementation, and read
+/// by parsing the text of a column using its [`FromStr`] implementation. This
+/// can for example be used to store `i128` values as decimal text rather than
+/// as blobs.
+///
+/// [`Display`]: fmt::Display
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::{AsText, State};
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// let mut stmt = c.prepare("SELECT ?, typeof(?1)")?;
+/// stmt.bind(1, AsText(i128::MAX))?;
+///
+/// assert_eq!(stmt.step()?, State::Row);
+/// assert_eq!(stmt.read::<AsText<i128>>(0)?.0, i128::MAX);
+/// assert_eq!(stmt.read::<String>(1)?, "text");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct AsText<T>(pub T);
+
+impl<T> Bindable for AsText<T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn bind(self, statement: &mut Statement, i: usize) -> Result<()> {
+        self.0.to_string().bind(statement, i)
+    }
+}
+
+impl<T> Readable for AsText<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn read(statement: &Statement, i: usize) -> Result<Self> {
+        let text = statement.column_text(i)?;
+
+        match text.parse() {
+            Ok(value) => Ok(AsText(value)),
+            Err(error) => Err(Error::new(
+                sqlite3_sys::SQLITE_MISMATCH,
+                Some(format!("failed to parse `{text}`: {error}").into()),
+            )),
+        }
+    }
+}
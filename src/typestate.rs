@@ -0,0 +1,182 @@
+//! A typestate wrapper around [`Statement`].
+//!
+//! This models the life cycle of a statement in the type system, so that
+//! misuse such as stepping a statement before its parameters have been bound
+//! or reading from a statement which has no current row is a compile-time
+//! error:
+//!
+//! ```text
+//! Prepared --bind--> Bound --step--> Executing --step--> Done --reset--> Prepared
+//!                      |                ^     |
+//!                      |                +-----+
+//!                      +------------------step------------> Done
+//! ```
+//!
+//! [`Statement`]: crate::Statement
+//!
+//! # Examples
+//!
+//! ```
+//! use sqlite_ll::typestate::{Prepared, Step};
+//! use sqlite_ll::Value;
+//!
+//! let c = sqlite_ll::Connection::open(":memory:")?;
+//! c.execute("CREATE TABLE users (name TEXT, age INTEGER); INSERT INTO users VALUES ('Alice', 42), ('Bob', 69);")?;
+//!
+//! let prepared = Prepared::new(c.prepare("SELECT name FROM users WHERE age > ?")?)?;
+//! let mut step = prepared.bind(&[Value::Integer(50)])?.step()?;
+//! let mut names = Vec::new();
+//!
+//! let done = loop {
+//!     match step {
+//!         Step::Row(row) => {
+//!             names.push(row.read::<String>(0)?);
+//!             step = row.step()?;
+//!         }
+//!         Step::Done(done) => break done,
+//!     }
+//! };
+//!
+//! assert_eq!(names, ["Bob"]);
+//!
+//! // The statement can be reused once it has been reset.
+//! let prepared = done.reset()?;
+//! # let _ = prepared;
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+//!
+//! Reading from a statement which has finished is rejected by the compiler:
+//!
+//! ```compile_fail
+//! use sqlite_ll::typestate::{Prepared, Step};
+//!
+//! let c = sqlite_ll::Connection::open(":memory:")?;
+//! let prepared = Prepared::new(c.prepare("SELECT 1 WHERE 0")?)?;
+//!
+//! if let Step::Done(done) = prepared.bind(())?.step()? {
+//!     done.read::<i64>(0)?;
+//! }
+//! # Ok::<_, sqlite_ll::Error>(())
+//! ```
+
+use crate::error::Result;
+use crate::params::Params;
+use crate::row::Row;
+use crate::statement::{Readable, State, Statement};
+
+/// A statement which has been reset and has no parameters bound.
+pub struct Prepared {
+    statement: Statement,
+}
+
+impl Prepared {
+    /// Wrap a statement, resetting it and clearing its bindings.
+    pub fn new(mut statement: Statement) -> Result<Self> {
+        statement.reset()?;
+        statement.clear_bindings()?;
+        Ok(Self { statement })
+    }
+
+    /// Bind parameters to the statement.
+    ///
+    /// Statements without parameters are bound with `()`.
+    pub fn bind<P>(mut self, params: P) -> Result<Bound>
+    where
+        P: Params,
+    {
+        params.bind_params(&mut self.statement)?;
+
+        Ok(Bound {
+            statement: self.statement,
+        })
+    }
+
+    /// Unwrap the underlying statement.
+    #[inline]
+    pub fn into_statement(self) -> Statement {
+        self.statement
+    }
+}
+
+/// A statement which has its parameters bound and is ready to be stepped.
+pub struct Bound {
+    statement: Statement,
+}
+
+impl Bound {
+    /// Step the statement.
+    #[inline]
+    pub fn step(self) -> Result<Step> {
+        step(self.statement)
+    }
+}
+
+/// The result of stepping a statement.
+pub enum Step {
+    /// A row is available for reading.
+    Row(Executing),
+    /// The statement has been entirely evaluated.
+    Done(Done),
+}
+
+/// A statement which has a row available for reading.
+pub struct Executing {
+    statement: Statement,
+}
+
+impl Executing {
+    /// Read a value from the current row.
+    ///
+    /// The first column has index 0.
+    #[inline]
+    pub fn read<T>(&self, i: usize) -> Result<T>
+    where
+        T: Readable,
+    {
+        self.statement.read(i)
+    }
+
+    /// Access the current row.
+    #[inline]
+    pub fn row(&self) -> Row<'_> {
+        Row::new(&self.statement)
+    }
+
+    /// Step to the next row.
+    #[inline]
+    pub fn step(self) -> Result<Step> {
+        step(self.statement)
+    }
+
+    /// Stop executing the statement, resetting it.
+    #[inline]
+    pub fn reset(self) -> Result<Prepared> {
+        Prepared::new(self.statement)
+    }
+}
+
+/// A statement which has been entirely evaluated.
+pub struct Done {
+    statement: Statement,
+}
+
+impl Done {
+    /// Reset the statement so that it can be executed again.
+    #[inline]
+    pub fn reset(self) -> Result<Prepared> {
+        Prepared::new(self.statement)
+    }
+
+    /// Unwrap the underlying statement.
+    #[inline]
+    pub fn into_statement(self) -> Statement {
+        self.statement
+    }
+}
+
+fn step(mut statement: Statement) -> Result<Step> {
+    Ok(match statement.step()? {
+        State::Row => Step::Row(Executing { statement }),
+        State::Done => Step::Done(Done { statement }),
+    })
+}
@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use sqlite3_sys as ffi;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::function::{self, Output};
+use crate::like;
+
+/// A bundle of SQL functions with full Unicode support, which can be
+/// registered on a connection as an alternative to the ICU extension.
+///
+/// By default every group of functions is registered:
+/// * [`upper`] - overrides `upper(X)` and `lower(X)` to convert the case of all
+///   characters rather than only ASCII characters.
+/// * [`nocase`] - overrides the `NOCASE` collating sequence to compare text
+///   after folding the case of all characters.
+/// * [`normalize`] - adds `nfc(X)`, `nfd(X)`, `nfkc(X)` and `nfkd(X)` which
+///   convert text to the corresponding Unicode normalization form.
+///
+/// Functions return `NULL` if their argument is `NULL`, and other values are
+/// converted to text first.
+///
+/// [`upper`]: UnicodeFunctions::upper
+/// [`nocase`]: UnicodeFunctions::nocase
+/// [`normalize`]: UnicodeFunctions::normalize
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::UnicodeFunctions;
+///
+/// let c = sqlite_ll::Connection::open(":memory:")?;
+/// UnicodeFunctions::new().register(&c)?;
+///
+/// assert_eq!(c.query_value::<String, _>("SELECT upper('smörgåsbord')", ())?, "SMÖRGÅSBORD");
+/// assert!(c.query_value::<bool, _>("SELECT 'ÅSA' = 'åsa' COLLATE NOCASE", ())?);
+/// assert_eq!(c.query_value::<String, _>("SELECT nfc('A\u{030a}')", ())?, "Å");
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct UnicodeFunctions {
+    upper: bool,
+    nocase: bool,
+    normalize: bool,
+}
+
+impl UnicodeFunctions {
+    /// Construct a bundle where every group of functions is enabled.
+    pub fn new() -> Self {
+        Self {
+            upper: true,
+            nocase: true,
+            normalize: true,
+        }
+    }
+
+    /// Set whether `upper(X)` and `lower(X)` should be overridden.
+    pub fn upper(mut self, upper: bool) -> Self {
+        self.upper = upper;
+        self
+    }
+
+    /// Set whether the `NOCASE` collating sequence should be overridden.
+    ///
+    /// Note that indexes which use `NOCASE` are ordered according to the
+    /// collating sequence which was in effect when they were built. If the
+    /// database is used both with and without the override, such indexes
+    /// should be rebuilt with `REINDEX NOCASE` after registering it.
+    pub fn nocase(mut self, nocase: bool) -> Self {
+        self.nocase = nocase;
+        self
+    }
+
+    /// Set whether the normalization functions should be registered.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Register the enabled functions on the given connection.
+    pub fn register(&self, c: &Connection) -> Result<()> {
+        let db = c.as_raw();
+
+        unsafe {
+            if self.upper {
+                function::create_scalar::<1>(db, b"upper\0", |args| {
+                    map_text(args, |s| s.to_uppercase())
+                })?;
+                function::create_scalar::<1>(db, b"lower\0", |args| {
+                    map_text(args, |s| s.to_lowercase())
+                })?;
+            }
+
+            if self.nocase {
+                function::create_collation(db, b"NOCASE\0", nocase)?;
+            }
+
+            if self.normalize {
+                function::create_scalar::<1>(db, b"nfc\0", |args| {
+                    map_text(args, |s| s.nfc().collect())
+                })?;
+                function::create_scalar::<1>(db, b"nfd\0", |args| {
+                    map_text(args, |s| s.nfd().collect())
+                })?;
+                function::create_scalar::<1>(db, b"nfkc\0", |args| {
+                    map_text(args, |s| s.nfkc().collect())
+                })?;
+                function::create_scalar::<1>(db, b"nfkd\0", |args| {
+                    map_text(args, |s| s.nfkd().collect())
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for UnicodeFunctions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply a conversion to the text of the only argument.
+fn map_text(
+    args: &[*mut ffi::sqlite3_value],
+    f: fn(&str) -> String,
+) -> Result<Output, Cow<'static, str>> {
+    // SAFETY: The argument is only used for the duration of the call.
+    match unsafe { function::text(args[0]) } {
+        Some(text) => Ok(Output::Text(f(&text))),
+        None => Ok(Output::Null),
+    }
+}
+
+/// Compare text after folding the case of every character.
+fn nocase(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(like::fold)
+        .cmp(b.chars().flat_map(like::fold))
+}
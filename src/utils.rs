@@ -1,9 +1,21 @@
+use std::any::Any;
+use std::cell::Cell;
 use std::ffi::{CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
 
 use crate::error::Result;
 use libc::c_char;
 
+// https://sqlite.org/c3ref/c_static.html
+macro_rules! transient(
+    () => {
+        ::core::mem::transmute::<*const libc::c_void, Option<::sqlite3_sys::sqlite3_callback>>(
+            !0 as *const libc::c_void
+        )
+    };
+);
+
 /// Helper to run sqlite3 statement.
 macro_rules! sqlite3_try {
     ($c:expr, $expr:expr) => {
@@ -45,6 +57,22 @@ pub(crate) fn string_to_cstring(s: &str) -> Result<CString> {
     }
 }
 
+/// Append an identifier to `out`, quoted so that it can be safely
+/// interpolated into SQL.
+pub(crate) fn push_identifier(out: &mut String, identifier: &str) {
+    out.push('"');
+
+    for c in identifier.chars() {
+        if c == '"' {
+            out.push('"');
+        }
+
+        out.push(c);
+    }
+
+    out.push('"');
+}
+
 #[cfg(unix)]
 pub(crate) fn path_to_cstring(p: &Path) -> Result<CString> {
     use std::ffi::OsStr;
@@ -69,3 +97,34 @@ pub(crate) fn path_to_cstring(p: &Path) -> Result<CString> {
         Err(..) => Err(crate::error::Error::from_code(sqlite3_sys::SQLITE_MISUSE)),
     }
 }
+
+thread_local! {
+    /// A panic which was caught in a callback invoked by SQLite, waiting to be
+    /// resumed once control has been returned to Rust.
+    static PANIC: Cell<Option<Box<dyn Any + Send>>> = Cell::new(None);
+}
+
+/// Call a closure which is invoked from SQLite, catching any panics since
+/// unwinding across the FFI boundary is undefined behavior.
+///
+/// If the closure panics, `default` is returned to SQLite and the panic is
+/// stored so that it can be resumed with [`resume_unwind`] once the SQLite call
+/// which invoked the callback has returned.
+pub(crate) fn catch_unwind<T>(default: T, f: impl FnOnce() -> T) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            PANIC.with(|panic| panic.set(Some(payload)));
+            default
+        }
+    }
+}
+
+/// Resume a panic which was caught in a callback invoked by SQLite.
+///
+/// This must be called after any SQLite function which can invoke callbacks.
+pub(crate) fn resume_unwind() {
+    if let Some(payload) = PANIC.with(|panic| panic.take()) {
+        panic::resume_unwind(payload);
+    }
+}
@@ -1,3 +1,10 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::quote;
+
 /// The type of a value.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Type {
@@ -9,6 +16,28 @@ pub enum Type {
 }
 
 /// A dynamic value.
+///
+/// The [`Display`] implementation renders the value as an SQL literal which
+/// evaluates to the same value, with text quoted and escaped and blobs written
+/// as hexadecimal `X'..'` literals. Such literals can be parsed back into a
+/// value through [`FromStr`].
+///
+/// [`Display`]: fmt::Display
+///
+/// # Examples
+///
+/// ```
+/// use sqlite_ll::Value;
+///
+/// let value = Value::Text(String::from("it's"));
+/// assert_eq!(value.to_sql_literal(), "'it''s'");
+/// assert_eq!(value.to_sql_literal().parse::<Value>()?, value);
+///
+/// assert_eq!(Value::Blob(vec![0xca, 0xfe]).to_string(), "X'CAFE'");
+/// assert_eq!("NULL".parse::<Value>()?, Value::Null);
+/// assert_eq!("-1.5".parse::<Value>()?, Value::Float(-1.5));
+/// # Ok::<_, sqlite_ll::Error>(())
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Blob(Vec<u8>),
@@ -55,6 +84,61 @@ impl Value {
         None
     }
 
+    /// Compare to another value the way SQLite does when sorting.
+    ///
+    /// This produces the same order as an `ORDER BY` clause using the default
+    /// `BINARY` collation, which differs from comparing values in Rust:
+    /// * `NULL` sorts before numbers, which sort before text, which sorts
+    ///   before blobs.
+    /// * Integers and floats are compared by their numeric values, so
+    ///   `Integer(1)` is equal to `Float(1.0)`.
+    /// * Text and blobs are compared byte by byte.
+    ///
+    /// `NULL` values are equal to each other, and since SQLite stores `NaN` as
+    /// `NULL` it is treated as one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use sqlite_ll::Value;
+    ///
+    /// let mut values = vec![
+    ///     Value::Blob(vec![0]),
+    ///     Value::Text(String::from("a")),
+    ///     Value::Float(1.5),
+    ///     Value::Integer(1),
+    ///     Value::Null,
+    /// ];
+    ///
+    /// values.sort_by(Value::cmp_sqlite);
+    ///
+    /// assert_eq!(values, [
+    ///     Value::Null,
+    ///     Value::Integer(1),
+    ///     Value::Float(1.5),
+    ///     Value::Text(String::from("a")),
+    ///     Value::Blob(vec![0]),
+    /// ]);
+    ///
+    /// assert_eq!(Value::Integer(2).cmp_sqlite(&Value::Float(2.0)), Ordering::Equal);
+    /// ```
+    #[inline]
+    pub fn cmp_sqlite(&self, other: &Self) -> Ordering {
+        self.as_value_ref().cmp_sqlite(&other.as_value_ref())
+    }
+
+    /// Render the value as an SQL literal.
+    ///
+    /// This is the same as its [`Display`] implementation.
+    ///
+    /// [`Display`]: fmt::Display
+    #[inline]
+    pub fn to_sql_literal(&self) -> String {
+        self.to_string()
+    }
+
     /// Return the type.
     pub fn kind(&self) -> Type {
         match self {
@@ -66,3 +150,253 @@ impl Value {
         }
     }
 }
+
+/// A dynamic value which borrows text and blobs.
+///
+/// This mirrors [`Value`], but can be read from a statement through
+/// [`Statement::read_value_ref`] without allocating.
+///
+/// [`Statement::read_value_ref`]: crate::Statement::read_value_ref
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueRef<'a> {
+    Blob(&'a [u8]),
+    Float(f64),
+    Integer(i64),
+    Text(&'a str),
+    Null,
+}
+
+impl<'a> ValueRef<'a> {
+    /// Return the binary data if the value is `Binary`.
+    #[inline]
+    pub fn as_blob(&self) -> Option<&'a [u8]> {
+        if let ValueRef::Blob(value) = *self {
+            return Some(value);
+        }
+        None
+    }
+
+    /// Return the floating-point number if the value is `Float`.
+    #[inline]
+    pub fn as_float(&self) -> Option<f64> {
+        if let ValueRef::Float(value) = *self {
+            return Some(value);
+        }
+        None
+    }
+
+    /// Return the integer number if the value is `Integer`.
+    #[inline]
+    pub fn as_integer(&self) -> Option<i64> {
+        if let ValueRef::Integer(value) = *self {
+            return Some(value);
+        }
+        None
+    }
+
+    /// Return the string if the value is `String`.
+    #[inline]
+    pub fn as_string(&self) -> Option<&'a str> {
+        if let ValueRef::Text(value) = *self {
+            return Some(value);
+        }
+        None
+    }
+
+    /// Return the type.
+    pub fn kind(&self) -> Type {
+        match self {
+            ValueRef::Blob(_) => Type::Blob,
+            ValueRef::Float(_) => Type::Float,
+            ValueRef::Integer(_) => Type::Integer,
+            ValueRef::Text(_) => Type::Text,
+            ValueRef::Null => Type::Null,
+        }
+    }
+
+    /// Compare to another value the way SQLite does when sorting.
+    ///
+    /// See [`Value::cmp_sqlite`].
+    pub fn cmp_sqlite(&self, other: &Self) -> Ordering {
+        match (*self, *other) {
+            (ValueRef::Integer(a), ValueRef::Integer(b)) => a.cmp(&b),
+            (ValueRef::Integer(a), ValueRef::Float(b)) if !b.is_nan() => cmp_int_float(a, b),
+            (ValueRef::Float(a), ValueRef::Integer(b)) if !a.is_nan() => {
+                cmp_int_float(b, a).reverse()
+            }
+            (ValueRef::Float(a), ValueRef::Float(b)) if !a.is_nan() && !b.is_nan() => {
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            }
+            (ValueRef::Text(a), ValueRef::Text(b)) => a.as_bytes().cmp(b.as_bytes()),
+            (ValueRef::Blob(a), ValueRef::Blob(b)) => a.cmp(b),
+            (a, b) => a.class().cmp(&b.class()),
+        }
+    }
+
+    /// The position of the value in the order of types used by SQLite.
+    fn class(&self) -> u8 {
+        match *self {
+            ValueRef::Null => 0,
+            ValueRef::Float(value) if value.is_nan() => 0,
+            ValueRef::Integer(..) | ValueRef::Float(..) => 1,
+            ValueRef::Text(..) => 2,
+            ValueRef::Blob(..) => 3,
+        }
+    }
+
+    /// Convert into an owned [`Value`].
+    pub fn to_value(&self) -> Value {
+        match *self {
+            ValueRef::Blob(value) => Value::Blob(value.to_vec()),
+            ValueRef::Float(value) => Value::Float(value),
+            ValueRef::Integer(value) => Value::Integer(value),
+            ValueRef::Text(value) => Value::Text(value.to_owned()),
+            ValueRef::Null => Value::Null,
+        }
+    }
+}
+
+impl Value {
+    /// Borrow as a [`ValueRef`].
+    pub fn as_value_ref(&self) -> ValueRef<'_> {
+        match self {
+            Value::Blob(value) => ValueRef::Blob(value),
+            Value::Float(value) => ValueRef::Float(*value),
+            Value::Integer(value) => ValueRef::Integer(*value),
+            Value::Text(value) => ValueRef::Text(value),
+            Value::Null => ValueRef::Null,
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    #[inline]
+    fn from(value: &'a Value) -> Self {
+        value.as_value_ref()
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    #[inline]
+    fn from(value: ValueRef<'_>) -> Self {
+        value.to_value()
+    }
+}
+
+impl fmt::Display for Value {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value_ref().fmt(f)
+    }
+}
+
+impl fmt::Display for ValueRef<'_> {
+    /// Render the value as an SQL literal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ValueRef::Blob(value) => quote::write_blob(f, value),
+            // SQLite has no literals for infinities, but parses out of range
+            // numbers as them. `NaN` can't be stored and is bound as `NULL`.
+            ValueRef::Float(value) if value.is_nan() => f.write_str("NULL"),
+            ValueRef::Float(value) if value.is_infinite() => {
+                f.write_str(if value > 0.0 { "1e999" } else { "-1e999" })
+            }
+            // The debug representation always includes a fraction or an
+            // exponent, so the value is parsed back as a float.
+            ValueRef::Float(value) => write!(f, "{value:?}"),
+            ValueRef::Integer(value) => write!(f, "{value}"),
+            ValueRef::Text(value) => {
+                f.write_str("'")?;
+
+                for (n, part) in value.split('\'').enumerate() {
+                    if n > 0 {
+                        f.write_str("''")?;
+                    }
+
+                    f.write_str(part)?;
+                }
+
+                f.write_str("'")
+            }
+            ValueRef::Null => f.write_str("NULL"),
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = Error;
+
+    /// Parse an SQL literal.
+    ///
+    /// This supports `NULL`, integers and floats with an optional sign, quoted
+    /// text and hexadecimal blob literals. Like in SQLite, integers which
+    /// don't fit in an `i64` are parsed as floats.
+    fn from_str(s: &str) -> Result<Self> {
+        let literal = s.trim();
+
+        if literal.eq_ignore_ascii_case("NULL") {
+            return Ok(Value::Null);
+        }
+
+        if let Some(text) = literal.strip_prefix('\'') {
+            if let Some(text) = text.strip_suffix('\'') {
+                if !text.replace("''", "").contains('\'') {
+                    return Ok(Value::Text(text.replace("''", "'")));
+                }
+            }
+
+            return Err(invalid_literal(s));
+        }
+
+        if let Some(hex) = literal
+            .strip_prefix("X'")
+            .or_else(|| literal.strip_prefix("x'"))
+        {
+            return match hex.strip_suffix('\'').and_then(quote::parse_hex) {
+                Some(blob) => Ok(Value::Blob(blob)),
+                None => Err(invalid_literal(s)),
+            };
+        }
+
+        let digits = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+
+        if !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return Err(invalid_literal(s));
+        }
+
+        if digits.bytes().all(|b| b.is_ascii_digit()) {
+            if let Ok(value) = literal.parse::<i64>() {
+                return Ok(Value::Integer(value));
+            }
+        }
+
+        match literal.parse::<f64>() {
+            Ok(value) => Ok(Value::Float(value)),
+            Err(..) => Err(invalid_literal(s)),
+        }
+    }
+}
+
+/// Compare an integer to a float exactly, like SQLite does.
+fn cmp_int_float(i: i64, r: f64) -> Ordering {
+    if r < -9223372036854775808.0 {
+        return Ordering::Greater;
+    }
+
+    if r >= 9223372036854775808.0 {
+        return Ordering::Less;
+    }
+
+    match i.cmp(&(r as i64)) {
+        Ordering::Equal => (i as f64).partial_cmp(&r).unwrap_or(Ordering::Equal),
+        ordering => ordering,
+    }
+}
+
+/// Construct the error for a string which isn't a valid SQL literal.
+fn invalid_literal(literal: &str) -> Error {
+    Error::new(
+        sqlite3_sys::SQLITE_ERROR,
+        Some(format!("`{literal}` is not a valid SQL literal").into()),
+    )
+}
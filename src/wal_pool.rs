@@ -0,0 +1,243 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use sqlite3_sys as ffi;
+
+use crate::connection::{self, Connection, OpenOptions};
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::row::FromRow;
+use crate::statement::Readable;
+
+/// The busy timeout in milliseconds used by connections in a [`WalPool`].
+const BUSY_TIMEOUT: usize = 5000;
+
+/// A pool of connections to a database in write-ahead logging mode, with a
+/// single writer and any number of readers.
+///
+/// In WAL mode readers don't block the writer and the writer doesn't block
+/// readers, but only one connection can write at a time. The pool therefore
+/// owns one read-write connection which is used for all writes, and a number
+/// of read-only connections which are used concurrently for reads. This is the
+/// recommended way to use SQLite in a server.
+///
+/// [`query_row`] and [`query_value`] route statements to a reader if they are
+/// read-only according to `sqlite3_stmt_readonly`, and to the writer
+/// otherwise. [`execute`] always uses the writer. The connections can also be
+/// used directly through [`writer`] and [`reader`].
+///
+/// The pool can be shared across threads, since each connection is protected
+/// by a mutex.
+///
+/// [`query_row`]: WalPool::query_row
+/// [`query_value`]: WalPool::query_value
+/// [`execute`]: WalPool::execute
+/// [`writer`]: WalPool::writer
+/// [`reader`]: WalPool::reader
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use sqlite_ll::WalPool;
+///
+/// let directory = temporary::Directory::new("sqlite")?;
+/// let pool = Arc::new(WalPool::open(directory.path().join("app.db"), 4)?);
+/// pool.execute("CREATE TABLE numbers (n INTEGER)")?;
+///
+/// let threads = (0..4).map(|n| {
+///     let pool = pool.clone();
+///
+///     thread::spawn(move || {
+///         pool.query_value::<i64, _>("INSERT INTO numbers VALUES (?) RETURNING n", (n,))?;
+///         pool.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())
+///     })
+/// }).collect::<Vec<_>>();
+///
+/// for t in threads {
+///     assert!(t.join().unwrap()? >= 1);
+/// }
+///
+/// assert_eq!(pool.query_value::<i64, _>("SELECT SUM(n) FROM numbers", ())?, 6);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct WalPool {
+    writer: Mutex<Connection>,
+    readers: Box<[Mutex<Connection>]>,
+    /// The reader to try first when acquiring one.
+    next: AtomicUsize,
+}
+
+impl WalPool {
+    /// Open a pool with the given number of readers to a new or existing
+    /// database, switching it to write-ahead logging mode.
+    ///
+    /// Every connection is configured with a busy timeout of five seconds. If
+    /// no readers are requested, reads use the writer.
+    ///
+    /// Errors with [`Code::CANTOPEN`] if the database doesn't support
+    /// write-ahead logging, such as in-memory databases.
+    ///
+    /// [`Code::CANTOPEN`]: crate::Code::CANTOPEN
+    pub fn open<T>(path: T, readers: usize) -> Result<Self>
+    where
+        T: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let mut writer = Connection::open(path)?;
+        writer.set_busy_timeout(BUSY_TIMEOUT)?;
+
+        let mode = writer.query_value::<String, _>("PRAGMA journal_mode = WAL", ())?;
+
+        if !mode.eq_ignore_ascii_case("wal") {
+            return Err(Error::new(
+                ffi::SQLITE_CANTOPEN,
+                Some(format!("database uses journal mode `{mode}` instead of `wal`").into()),
+            ));
+        }
+
+        let readers = (0..readers)
+            .map(|_| {
+                let mut reader = OpenOptions::new().set_read_only().open(path)?;
+                reader.set_busy_timeout(BUSY_TIMEOUT)?;
+                Ok(Mutex::new(reader))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            writer: Mutex::new(writer),
+            readers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Acquire the writer, waiting until no other thread is using it.
+    pub fn writer(&self) -> MutexGuard<'_, Connection> {
+        lock(&self.writer)
+    }
+
+    /// Acquire a reader, preferring one which isn't in use by another thread.
+    ///
+    /// Readers are handed out in turn. If all of them are in use, this waits
+    /// for the next one in turn to become available.
+    pub fn reader(&self) -> MutexGuard<'_, Connection> {
+        if self.readers.is_empty() {
+            return self.writer();
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        for n in 0..self.readers.len() {
+            let reader = &self.readers[(start + n) % self.readers.len()];
+
+            match reader.try_lock() {
+                Ok(guard) => return guard,
+                Err(TryLockError::Poisoned(error)) => return error.into_inner(),
+                Err(TryLockError::WouldBlock) => {}
+            }
+        }
+
+        lock(&self.readers[start % self.readers.len()])
+    }
+
+    /// Execute a statement on the writer without processing the resulting
+    /// rows if any.
+    ///
+    /// See [`Connection::execute`].
+    pub fn execute<T>(&self, statement: T) -> Result<()>
+    where
+        T: AsRef<str>,
+    {
+        self.writer().execute(statement)
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row on a reader if it's read-only, or on the writer otherwise.
+    ///
+    /// Statements are prepared through [`Connection::prepare_cached`] on the
+    /// connection they run on, so statements which write are only cached by
+    /// the writer.
+    ///
+    /// See [`Connection::query_row`].
+    pub fn query_row<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        let statement = statement.as_ref();
+
+        let reader = self.reader();
+        let mut stmt = reader.prepare_cached(statement)?;
+
+        if unsafe { ffi::sqlite3_stmt_readonly(stmt.as_raw()) != 0 } {
+            return reader.observe(connection::query_one(&mut stmt, params));
+        }
+
+        stmt.discard();
+        drop(reader);
+
+        let writer = self.writer();
+        let mut stmt = writer.prepare_cached(statement)?;
+        writer.observe(connection::query_one(&mut stmt, params))
+    }
+
+    /// Prepare and execute a statement which is expected to produce exactly
+    /// one row, and read its first column.
+    ///
+    /// This is routed like [`WalPool::query_row`].
+    ///
+    /// See [`Connection::query_value`].
+    pub fn query_value<T, P>(&self, statement: impl AsRef<str>, params: P) -> Result<T>
+    where
+        T: Readable,
+        P: Params,
+    {
+        let (value,) = self.query_row::<(T,), P>(statement, params)?;
+        Ok(value)
+    }
+
+    /// Checkpoint the write-ahead log into the database and truncate it.
+    ///
+    /// SQLite automatically checkpoints the log once it grows large, but these
+    /// checkpoints can't complete while readers are using the part of the log
+    /// being checkpointed, so the log can grow without bounds under a constant
+    /// load of reads. This acquires every reader and the writer before
+    /// checkpointing, so that the checkpoint isn't held up by the pool.
+    ///
+    /// Errors with [`Code::BUSY`] if the checkpoint couldn't complete due to
+    /// connections outside of the pool.
+    ///
+    /// This must not be called by a thread which holds a connection acquired
+    /// from the pool, since it would wait for itself.
+    ///
+    /// [`Code::BUSY`]: crate::Code::BUSY
+    pub fn checkpoint(&self) -> Result<()> {
+        let _readers = self.readers.iter().map(lock).collect::<Vec<_>>();
+        let writer = self.writer();
+
+        let (busy, _, _) =
+            writer.query_row::<(bool, i64, i64), _>("PRAGMA wal_checkpoint(TRUNCATE)", ())?;
+
+        if busy {
+            return Err(Error::new(
+                ffi::SQLITE_BUSY,
+                Some("checkpoint was blocked by another connection".into()),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Lock a connection, ignoring poisoning since a connection can't be left in
+/// an inconsistent state by a panic.
+fn lock(connection: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    match connection.lock() {
+        Ok(guard) => guard,
+        Err(error) => error.into_inner(),
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use sqlite_ll::{Connection, State, Value};
+
+#[test]
+fn arbitrary_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?")?;
+
+    let data = (0..=255u8).cycle().take(4096).collect::<Vec<_>>();
+    let mut u = Unstructured::new(&data);
+
+    while !u.is_empty() {
+        let values = Vec::<Value>::arbitrary(&mut u)?;
+
+        for value in values {
+            stmt.reset()?;
+            stmt.bind(1, &value)?;
+            assert_eq!(stmt.step()?, State::Row);
+            assert_eq!(stmt.read::<Value>(0)?, value);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "arrayvec")]
+
+use arrayvec::{ArrayString, ArrayVec};
+use sqlite_ll::{Code, Connection, State};
+
+#[test]
+fn arrayvec_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let key = ArrayVec::from([1u8, 2, 3, 4]);
+    let name = ArrayString::<8>::from("Alice").unwrap();
+
+    let mut stmt = c.prepare("SELECT ?, ?")?;
+    stmt.bind_all((&key, name))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<ArrayVec<u8, 4>>(0)?, key);
+    assert_eq!(stmt.read::<ArrayVec<u8, 16>>(0)?.as_slice(), [1, 2, 3, 4]);
+    assert_eq!(stmt.read::<ArrayString<8>>(1)?, name);
+
+    let e = stmt.read::<ArrayVec<u8, 2>>(0).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    let e = stmt.read::<ArrayString<2>>(1).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    Ok(())
+}
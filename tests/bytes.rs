@@ -0,0 +1,23 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+use sqlite_ll::{Connection, State, Type};
+
+#[test]
+fn bytes_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let payload = Bytes::from_static(b"\x00payload\xff");
+    let mut buffer = BytesMut::new();
+    buffer.extend_from_slice(b"buffer");
+
+    let mut stmt = c.prepare("SELECT ?, ?, NULL")?;
+    stmt.bind_all((&payload, buffer.clone()))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.column_type(0), Type::Blob);
+    assert_eq!(stmt.read::<Bytes>(0)?, payload);
+    assert_eq!(stmt.read::<BytesMut>(1)?, buffer);
+    assert!(stmt.read::<Bytes>(2)?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use sqlite_ll::{Connection, State};
+
+#[test]
+fn chrono_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let date = NaiveDate::from_ymd_opt(2023, 4, 1).unwrap();
+    let time = NaiveTime::from_hms_milli_opt(12, 30, 5, 250).unwrap();
+    let date_time = date.and_time(time);
+    let utc = Utc.from_utc_datetime(&date_time);
+
+    let mut stmt = c.prepare("SELECT ?, ?, ?, ?, date(?1), datetime(?3), julianday(?3)")?;
+    stmt.bind_all((date, time, date_time, utc))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<String>(0)?, "2023-04-01");
+    assert_eq!(stmt.read::<String>(1)?, "12:30:05.250");
+    assert_eq!(stmt.read::<String>(2)?, "2023-04-01 12:30:05.250");
+    assert_eq!(stmt.read::<String>(3)?, "2023-04-01 12:30:05.250+00:00");
+
+    assert_eq!(stmt.read::<NaiveDate>(0)?, date);
+    assert_eq!(stmt.read::<NaiveTime>(1)?, time);
+    assert_eq!(stmt.read::<NaiveDateTime>(2)?, date_time);
+    assert_eq!(stmt.read::<DateTime<Utc>>(3)?, utc);
+
+    // Values produced by SQLite's date functions.
+    assert_eq!(stmt.read::<NaiveDate>(4)?, date);
+    assert_eq!(
+        stmt.read::<NaiveDateTime>(5)?,
+        date.and_hms_opt(12, 30, 5).unwrap()
+    );
+    assert_eq!(stmt.read::<NaiveDateTime>(6)?, date_time);
+    Ok(())
+}
+
+#[test]
+fn chrono_numeric() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let mut stmt = c.prepare("SELECT 1680352205, '2023-04-01T12:30:05+02:00', 'nope'")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let expected = Utc.with_ymd_and_hms(2023, 4, 1, 12, 30, 5).unwrap();
+    assert_eq!(stmt.read::<DateTime<Utc>>(0)?, expected);
+    assert_eq!(
+        stmt.read::<DateTime<Utc>>(1)?,
+        Utc.with_ymd_and_hms(2023, 4, 1, 10, 30, 5).unwrap()
+    );
+    assert!(stmt.read::<NaiveDateTime>(2).is_err());
+    Ok(())
+}
@@ -0,0 +1,187 @@
+#![cfg(feature = "derive")]
+
+use sqlite_ll::testing::Fixture;
+use sqlite_ll::{Connection, FromRow};
+
+#[derive(Debug, PartialEq, FromRow)]
+struct User {
+    id: i64,
+    #[sqlite(rename = "name")]
+    user_name: String,
+    #[sqlite(index = 2)]
+    age: f64,
+    email: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromRow)]
+struct Pair(i64, String);
+
+#[derive(Debug, PartialEq, FromRow)]
+struct Item {
+    r#type: String,
+}
+
+#[test]
+fn derive_from_row() -> sqlite_ll::Result<()> {
+    let c = setup_users()?;
+
+    let mut s = c.prepare("SELECT id, name, age, email FROM users")?;
+    let users = s.iter::<User>().collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        users,
+        [User {
+            id: 1,
+            user_name: String::from("Alice"),
+            age: 42.69,
+            email: None,
+        }]
+    );
+
+    let mut s = c.prepare("SELECT id, name FROM users")?;
+    let pairs = s.iter::<Pair>().collect::<sqlite_ll::Result<Vec<_>>>()?;
+    assert_eq!(pairs, [Pair(1, String::from("Alice"))]);
+
+    let mut s = c.prepare("SELECT id, name FROM users")?;
+    assert!(s.iter::<User>().next().unwrap().is_err());
+
+    let item = c.query_row::<Item, _>("SELECT 'book' AS type", ())?;
+    assert_eq!(item.r#type, "book");
+    Ok(())
+}
+
+fn setup_users() -> sqlite_ll::Result<Connection> {
+    Fixture::new()
+        .table(
+            "users",
+            [
+                "id INTEGER",
+                "name TEXT",
+                "age REAL",
+                "photo BLOB",
+                "email TEXT",
+            ],
+        )
+        .row((1, "Alice", 42.69, &[0x42u8, 0x69][..]))
+        .open()
+}
+
+#[derive(sqlite_ll::Params)]
+struct NewUser<'a> {
+    id: i64,
+    name: &'a str,
+    #[sqlite(rename = "@age")]
+    age: Option<f64>,
+    #[sqlite(index = 4)]
+    photo: Vec<u8>,
+    email: String,
+}
+
+#[derive(sqlite_ll::Params)]
+struct Positional(i64, String);
+
+#[derive(sqlite_ll::Params)]
+struct Filter<'a> {
+    r#type: &'a str,
+}
+
+#[test]
+fn derive_params() -> sqlite_ll::Result<()> {
+    let c = setup_users()?;
+
+    let mut s = c.prepare("INSERT INTO users VALUES (:id, :name, @age, ?4, :email)")?;
+
+    s.cursor(NewUser {
+        id: 2,
+        name: "Bob",
+        age: None,
+        photo: vec![0x69, 0x42],
+        email: String::from("bob@example.com"),
+    })?
+    .next_row()?;
+
+    let mut s = c.prepare("INSERT INTO users (id, name) VALUES (?, ?)")?;
+    s.cursor(Positional(3, String::from("Carol")))?.next_row()?;
+
+    let mut s = c.prepare("SELECT id, name, age, photo, email FROM users WHERE id > 1")?;
+
+    let rows = s
+        .iter::<(i64, String, Option<f64>, Option<Vec<u8>>, Option<String>)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        rows,
+        [
+            (
+                2,
+                String::from("Bob"),
+                None,
+                Some(vec![0x69, 0x42]),
+                Some(String::from("bob@example.com"))
+            ),
+            (3, String::from("Carol"), None, None, None),
+        ]
+    );
+
+    let kind = c.query_value::<String, _>("SELECT :type", Filter { r#type: "book" })?;
+    assert_eq!(kind, "book");
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, sqlite_ll::Bindable, sqlite_ll::Readable)]
+enum Level {
+    Low = 1,
+    High = 10,
+}
+
+#[derive(Debug, PartialEq, sqlite_ll::Bindable, sqlite_ll::Readable)]
+#[sqlite(text)]
+enum Color {
+    Red,
+    #[sqlite(rename = "green")]
+    Green,
+}
+
+#[derive(Debug, PartialEq, sqlite_ll::Bindable, sqlite_ll::Readable)]
+#[sqlite(text)]
+#[allow(non_camel_case_types)]
+enum Keyword {
+    r#match,
+}
+
+#[test]
+fn derive_enums() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let mut s = c.prepare("SELECT ?, ?, ?")?;
+    s.bind(1, Level::High)?;
+    s.bind(2, &Color::Red)?;
+    s.bind(3, Color::Green)?;
+    assert_eq!(s.step()?, sqlite_ll::State::Row);
+
+    assert_eq!(s.read::<i64>(0)?, 10);
+    assert_eq!(s.read::<Level>(0)?, Level::High);
+    assert_eq!(s.read::<String>(1)?, "Red");
+    assert_eq!(s.read::<Color>(1)?, Color::Red);
+    assert_eq!(s.read::<String>(2)?, "green");
+    assert_eq!(s.read::<Color>(2)?, Color::Green);
+
+    let mut s = c.prepare("SELECT 1, 2, 'Green'")?;
+    assert_eq!(s.step()?, sqlite_ll::State::Row);
+
+    assert_eq!(s.read::<Level>(0)?, Level::Low);
+
+    let e = s.read::<Level>(1).unwrap_err();
+    assert!(e.to_string().contains("unknown value `2` for `Level`"));
+
+    let e = s.read::<Color>(2).unwrap_err();
+    assert!(e.to_string().contains("unknown value `Green` for `Color`"));
+
+    let keyword = c.query_value::<String, _>("SELECT ?", (Keyword::r#match,))?;
+    assert_eq!(keyword, "match");
+    assert_eq!(
+        c.query_value::<Keyword, _>("SELECT 'match'", ())?,
+        Keyword::r#match
+    );
+    Ok(())
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "is_interrupted")]
+
+use sqlite_ll::{ffi, Code, Connection, State};
+
+#[test]
+fn is_interrupted() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    assert!(!c.is_interrupted());
+
+    let mut stmt = c.prepare("SELECT 1 UNION ALL SELECT 2")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    unsafe { ffi::sqlite3_interrupt(c.as_raw()) };
+    assert!(c.is_interrupted());
+
+    assert_eq!(stmt.step().unwrap_err().code(), Code::INTERRUPT);
+    Ok(())
+}
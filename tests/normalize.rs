@@ -0,0 +1,18 @@
+#![cfg(feature = "normalize")]
+
+use sqlite_ll::Connection;
+
+#[test]
+fn normalized_sql() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+
+    let a = c.prepare("SELECT name FROM users WHERE age > 42")?;
+    let b = c.prepare("select name from users where age > 69")?;
+
+    let normalized = a.normalized_sql()?.expect("normalized sql");
+    assert!(!normalized.contains("42"));
+    assert!(normalized.contains('?'));
+    assert_eq!(b.normalized_sql()?, Some(normalized));
+    Ok(())
+}
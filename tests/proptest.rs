@@ -0,0 +1,25 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use sqlite_ll::proptest::{round_trip, schema, value, Schema};
+use sqlite_ll::{Connection, Value};
+
+fn schema_with_rows() -> impl Strategy<Value = (Schema, Vec<Vec<Value>>)> {
+    schema().prop_flat_map(|schema| {
+        let rows = schema.rows(0..8);
+        (Just(schema), rows)
+    })
+}
+
+proptest! {
+    #[test]
+    fn proptest_value_round_trip(value in value()) {
+        prop_assert_eq!(round_trip(value.clone())?, value);
+    }
+
+    #[test]
+    fn proptest_schema_round_trip((schema, rows) in schema_with_rows()) {
+        let c = Connection::open(":memory:")?;
+        prop_assert_eq!(schema.round_trip(&c, &rows)?, rows);
+    }
+}
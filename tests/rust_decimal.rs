@@ -0,0 +1,28 @@
+#![cfg(feature = "rust_decimal")]
+
+use rust_decimal::Decimal;
+use sqlite_ll::{Connection, State};
+
+#[test]
+fn rust_decimal_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE payments (amount TEXT)")?;
+
+    let amount = "12345678901234567890.0123456789"
+        .parse::<Decimal>()
+        .unwrap();
+
+    let mut stmt = c.prepare("INSERT INTO payments VALUES (?)")?;
+    stmt.bind(1, amount)?;
+    assert_eq!(stmt.step()?, State::Done);
+
+    let mut stmt = c.prepare("SELECT amount, 42, 0.5, '1e3', 'nope' FROM payments")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<Decimal>(0)?, amount);
+    assert_eq!(stmt.read::<Decimal>(1)?, Decimal::from(42));
+    assert_eq!(stmt.read::<Decimal>(2)?, Decimal::new(5, 1));
+    assert_eq!(stmt.read::<Decimal>(3)?, Decimal::from(1000));
+    assert!(stmt.read::<Decimal>(4).is_err());
+    Ok(())
+}
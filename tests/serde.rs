@@ -0,0 +1,217 @@
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use sqlite_ll::testing::Fixture;
+use sqlite_ll::{Connection, State};
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Role {
+    Admin,
+    User,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct User<'a> {
+    id: u32,
+    name: &'a str,
+    age: f64,
+    #[serde(with = "serde_bytes_compat")]
+    photo: Vec<u8>,
+    email: Option<String>,
+    admin: bool,
+    role: Role,
+}
+
+mod serde_bytes_compat {
+    use serde::de::{Deserializer, Error, Visitor};
+    use std::fmt;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(v.to_vec())
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[test]
+fn statement_de() -> sqlite_ll::Result<()> {
+    let c = setup_users()?;
+    let mut s = c.prepare("SELECT * FROM users")?;
+
+    assert_eq!(s.step()?, State::Row);
+
+    assert_eq!(
+        s.de::<User<'_>>()?,
+        User {
+            id: 1,
+            name: "Alice",
+            age: 42.69,
+            photo: vec![0x42, 0x69],
+            email: None,
+            admin: true,
+            role: Role::Admin,
+        }
+    );
+
+    let (id, name) = s.de::<(i64, String)>()?;
+    assert_eq!((id, name.as_str()), (1, "Alice"));
+
+    let mut s = c.prepare("SELECT id, name FROM users")?;
+    let row = s.next_row()?.unwrap();
+    assert!(row.de::<User<'_>>().is_err());
+    Ok(())
+}
+
+fn setup_users() -> sqlite_ll::Result<Connection> {
+    Fixture::new()
+        .table(
+            "users",
+            [
+                "id INTEGER",
+                "name TEXT",
+                "age REAL",
+                "photo BLOB",
+                "email TEXT",
+                "admin INTEGER",
+                "role TEXT",
+            ],
+        )
+        .row((
+            1,
+            "Alice",
+            42.69,
+            &[0x42u8, 0x69][..],
+            None::<&str>,
+            1,
+            "Admin",
+        ))
+        .open()
+}
+
+#[derive(serde::Serialize)]
+struct NewUser<'a> {
+    id: u64,
+    name: &'a str,
+    age: Option<f32>,
+    #[serde(rename = "@email")]
+    email: Option<String>,
+}
+
+#[test]
+fn statement_bind_serialize() -> sqlite_ll::Result<()> {
+    let c = setup_users()?;
+
+    let mut s =
+        c.prepare("INSERT INTO users (id, name, age, email) VALUES (:id, :name, :age, @email)")?;
+
+    s.bind_serialize(&NewUser {
+        id: 2,
+        name: "Bob",
+        age: Some(69.5),
+        email: None,
+    })?;
+
+    assert_eq!(s.step()?, State::Done);
+
+    let mut map = std::collections::BTreeMap::new();
+    map.insert("id", 3);
+    map.insert("name", 4);
+    map.insert("age", 5);
+    map.insert("@email", 6);
+
+    s.reset()?;
+    s.bind_serialize(&map)?;
+    assert_eq!(s.step()?, State::Done);
+
+    let mut s = c.prepare("SELECT id, name, age, email FROM users WHERE id > 1")?;
+    let rows = s
+        .iter::<(i64, String, f64, Option<String>)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        rows,
+        [
+            (2, String::from("Bob"), 69.5, None),
+            (3, String::from("4"), 5.0, Some(String::from("6")))
+        ]
+    );
+
+    let mut s = c.prepare("INSERT INTO users (id) VALUES (:id)")?;
+    assert!(s
+        .bind_serialize(&NewUser {
+            id: 1,
+            name: "",
+            age: None,
+            email: None
+        })
+        .is_err());
+    assert!(s.bind_serialize(&[1, 2, 3]).is_err());
+    assert!(s.bind_serialize(&u64::MAX).is_err());
+
+    let mut nested = std::collections::BTreeMap::new();
+    nested.insert("id", vec![1, 2]);
+    assert!(s.bind_serialize(&nested).is_err());
+    Ok(())
+}
+
+#[test]
+fn value_serde() -> Result<(), Box<dyn std::error::Error>> {
+    use sqlite_ll::{Type, Value};
+
+    let values = vec![
+        Value::Blob(vec![1, 2]),
+        Value::Float(1.5),
+        Value::Integer(-42),
+        Value::Text(String::from("text")),
+        Value::Null,
+    ];
+
+    let json = serde_json::to_string(&values)?;
+    assert_eq!(json, r#"[[1,2],1.5,-42,"text",null]"#);
+    assert_eq!(serde_json::from_str::<Vec<Value>>(&json)?, values);
+
+    let types = values.iter().map(Value::kind).collect::<Vec<_>>();
+    let json = serde_json::to_string(&types)?;
+    assert_eq!(json, r#"["Blob","Float","Integer","Text","Null"]"#);
+    assert_eq!(serde_json::from_str::<Vec<Type>>(&json)?, types);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Row {
+        name: Value,
+        photo: Value,
+        email: Value,
+    }
+
+    let c = setup_users()?;
+    let mut s = c.prepare("SELECT name, photo, email FROM users")?;
+    assert_eq!(s.step()?, State::Row);
+
+    assert_eq!(
+        s.de::<Row>()?,
+        Row {
+            name: Value::Text(String::from("Alice")),
+            photo: Value::Blob(vec![0x42, 0x69]),
+            email: Value::Null,
+        }
+    );
+
+    Ok(())
+}
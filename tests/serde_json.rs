@@ -0,0 +1,36 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::{json, Value};
+use sqlite_ll::{Code, Connection, State};
+
+#[test]
+fn serde_json_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE documents (body TEXT)")?;
+
+    let document = json!({"name": "Alice", "tags": ["a", "b"], "age": 42, "score": 1.5});
+
+    let mut stmt = c.prepare("INSERT INTO documents VALUES (?)")?;
+    stmt.bind(1, &document)?;
+    assert_eq!(stmt.step()?, State::Done);
+
+    let mut stmt = c.prepare(
+        "SELECT body, json_extract(body, '$.age'), json_extract(body, '$.score'), json_extract(body, '$.tags'), json_extract(body, '$.missing'), 'nope' FROM documents",
+    )?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<Value>(0)?, document);
+    assert_eq!(stmt.read::<Value>(1)?, json!(42));
+    assert_eq!(stmt.read::<Value>(2)?, json!(1.5));
+    assert_eq!(stmt.read::<Value>(3)?, json!(["a", "b"]));
+    assert_eq!(stmt.read::<Value>(4)?, Value::Null);
+    assert!(stmt.read::<Value>(5).is_err());
+
+    let mut stmt = c.prepare("SELECT CAST(body AS BLOB) FROM documents")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let e = stmt.read::<Value>(0).unwrap_err();
+    assert_eq!(e.code(), Code::MISMATCH);
+    assert!(e.to_string().contains("JSONB is not supported"));
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "shell")]
+
+use sqlite_ll::shell::Shell;
+use sqlite_ll::{Code, Connection};
+
+#[test]
+fn shell() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut shell = Shell::new(&c);
+
+    assert_eq!(shell.prompt(), "sqlite> ");
+    assert_eq!(
+        shell.input("CREATE TABLE users (name TEXT, age INTEGER);")?,
+        ""
+    );
+    assert_eq!(shell.input("CREATE INDEX users_age ON users (age);")?, "");
+    assert_eq!(shell.input("INSERT INTO users VALUES")?, "");
+    assert!(shell.is_pending());
+    assert_eq!(shell.prompt(), "   ...> ");
+    assert_eq!(shell.input("('Alice', 42), (';', NULL);")?, "");
+    assert!(!shell.is_pending());
+
+    assert_eq!(shell.input(".tables")?, "users\n");
+    assert_eq!(shell.input(".indexes users")?, "users_age\n");
+    assert_eq!(shell.input(".indexes other")?, "");
+    assert_eq!(
+        shell.input(".schema")?,
+        "CREATE TABLE users (name TEXT, age INTEGER);\nCREATE INDEX users_age ON users (age);\n"
+    );
+
+    shell.input(".mode markdown")?;
+    shell.input(".nullvalue -")?;
+
+    assert_eq!(
+        shell.input("SELECT name, age FROM users ORDER BY name; SELECT 1 AS one;")?,
+        "\
+| name  | age |
+|-------|-----|
+| ;     | -   |
+| Alice | 42  |
+| one |
+|-----|
+| 1   |
+"
+    );
+
+    let e = shell.input(".frobnicate").unwrap_err();
+    assert!(e.to_string().contains("unknown command"));
+
+    assert_eq!(shell.input("SELECT * FROM")?, "");
+    let e = shell.input("missing;").unwrap_err();
+    assert_eq!(e.code(), Code::ERROR);
+    assert!(!shell.is_pending());
+    Ok(())
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "smallvec")]
+
+use smallvec::SmallVec;
+use sqlite_ll::{Connection, State};
+
+#[test]
+fn smallvec_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let hash = SmallVec::<[u8; 4]>::from_slice(&[1, 2, 3, 4]);
+
+    let mut stmt = c.prepare("SELECT ?, X'0102030405'")?;
+    stmt.bind(1, &hash)?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let read = stmt.read::<SmallVec<[u8; 4]>>(0)?;
+    assert_eq!(read, hash);
+    assert!(!read.spilled());
+
+    let read = stmt.read::<SmallVec<[u8; 4]>>(1)?;
+    assert_eq!(&read[..], [1, 2, 3, 4, 5]);
+    assert!(read.spilled());
+    Ok(())
+}
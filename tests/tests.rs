@@ -1,4 +1,9 @@
-use sqlite_ll::{Code, Connection, OpenOptions, State, Type, Value};
+use sqlite_ll::testing::Fixture;
+use sqlite_ll::{
+    Code, Connection, ErrorKind, OpenOptions, OwnedStatement, RowBuffer, State, Statement,
+    StatementSet, SyncConnection, Type, Value, WalPool,
+};
+use std::panic::{self, AssertUnwindSafe};
 use std::{path::Path, thread};
 use temporary::Directory;
 
@@ -25,6 +30,31 @@ fn connection_change_count() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn connection_close() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = Directory::new("sqlite")?;
+    let path = directory.path().join("database.sqlite3");
+
+    let c = setup_users(&path)?;
+    let stmt = c.prepare("SELECT * FROM users")?;
+    drop(c.prepare_cached("SELECT 1")?);
+
+    let (c, e) = match c.close() {
+        Ok(()) => panic!("expected close to fail"),
+        Err(error) => error,
+    };
+
+    assert_eq!(e.code(), Code::BUSY);
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?,
+        1
+    );
+
+    drop(stmt);
+    c.close().map_err(|(_, e)| e)?;
+    Ok(())
+}
+
 #[test]
 fn connection_error() -> sqlite_ll::Result<()> {
     let connection = setup_users(":memory:")?;
@@ -33,6 +63,133 @@ fn connection_error() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn connection_execute_multiple() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    c.execute(
+        "
+        CREATE TABLE numbers (value INTEGER);;
+        INSERT INTO numbers VALUES (1), (2);
+        -- trailing comment
+        ",
+    )?;
+
+    let mut values = Vec::new();
+
+    c.iterate(
+        "SELECT value FROM numbers; SELECT value * 10 FROM numbers;",
+        |pairs| {
+            values.push(pairs[0].1.map(str::to_owned));
+            true
+        },
+    )?;
+
+    assert_eq!(
+        values,
+        [Some("1"), Some("2"), Some("10"), Some("20")].map(|v| v.map(String::from))
+    );
+
+    assert!(c.prepare("  -- nothing here").is_err());
+    Ok(())
+}
+
+#[test]
+fn connection_insert_rows() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE \"odd \"\"table\"\"\" (a INTEGER, b TEXT)")?;
+
+    let rows = (0..40000)
+        .map(|n| vec![Value::Integer(n), Value::Text(n.to_string())])
+        .collect::<Vec<_>>();
+
+    assert_eq!(c.insert_rows("odd \"table\"", &["a", "b"], &rows)?, 40000);
+
+    let (count, sum) = c.query_row::<(i64, i64), _>(
+        "SELECT COUNT(*), SUM(CAST(b AS INTEGER)) FROM \"odd \"\"table\"\"\"",
+        (),
+    )?;
+
+    assert_eq!(count, 40000);
+    assert_eq!(sum, (0..40000).sum::<i64>());
+
+    let e = c
+        .insert_rows("odd \"table\"", &["a", "b"], &[vec![Value::Null]])
+        .unwrap_err();
+    assert_eq!(e.code(), Code::ERROR);
+    Ok(())
+}
+
+#[test]
+fn connection_prepare_borrowed() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+
+    let mut stmt = c.prepare_borrowed("SELECT name FROM users WHERE id = ?")?;
+    stmt.bind(1, 1)?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "Alice");
+
+    let mut stmt = stmt.into_inner();
+    drop(c);
+    assert_eq!(stmt.step()?, State::Done);
+    Ok(())
+}
+
+#[test]
+fn connection_prepare_cached() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    {
+        let mut stmt = c.prepare_cached("SELECT ?")?;
+        stmt.bind(1, 42)?;
+        assert_eq!(stmt.step()?, State::Row);
+        assert_eq!(stmt.read::<i64>(0)?, 42);
+
+        // The first statement is in use, so a second one is prepared.
+        let mut other = c.prepare_cached("SELECT ?")?;
+        assert_eq!(other.step()?, State::Row);
+        assert_eq!(other.read::<Value>(0)?, Value::Null);
+    }
+
+    // Reused statements are reset and have their bindings cleared.
+    let mut stmt = c.prepare_cached("SELECT ?")?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<Value>(0)?, Value::Null);
+    drop(stmt);
+
+    c.set_statement_cache_capacity(0);
+    let mut stmt = c.prepare_cached("SELECT 1")?;
+    assert_eq!(stmt.step()?, State::Row);
+    drop(stmt);
+    c.clear_statement_cache();
+
+    assert!(c.prepare_cached("SELECT * FROM missing").is_err());
+    Ok(())
+}
+
+#[test]
+fn connection_statements() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    assert!(c.statements().is_empty());
+
+    let a = c.prepare("SELECT 1")?;
+    let b = c.prepare_cached("SELECT 2")?;
+
+    let mut statements = c.statements();
+    statements.sort();
+    assert_eq!(statements, ["SELECT 1", "SELECT 2"]);
+
+    drop(b);
+    std::mem::forget(a);
+
+    unsafe {
+        c.finalize_all();
+    }
+
+    assert!(c.statements().is_empty());
+    Ok(())
+}
+
 #[test]
 fn connection_iterate() -> sqlite_ll::Result<()> {
     macro_rules! pair(
@@ -57,6 +214,44 @@ fn connection_iterate() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn connection_iterate_stop() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+
+    let mut count = 0;
+
+    c.iterate("SELECT * FROM english", |_| {
+        count += 1;
+        count < 3
+    })?;
+
+    assert_eq!(count, 3);
+    Ok(())
+}
+
+#[test]
+fn connection_try_iterate() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+
+    let mut count = 0;
+
+    let e = c
+        .try_iterate("SELECT * FROM english", |_| {
+            count += 1;
+
+            if count == 2 {
+                return Err(sqlite_ll::Error::custom("stop"));
+            }
+
+            Ok(true)
+        })
+        .unwrap_err();
+
+    assert_eq!(e.code(), Code::ERROR);
+    assert_eq!(count, 2);
+    Ok(())
+}
+
 #[test]
 fn connection_open_with_flags() -> Result<(), Box<dyn std::error::Error>> {
     let directory = Directory::new("sqlite")?;
@@ -74,6 +269,36 @@ fn connection_open_with_flags() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn connection_query_row() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+
+    let (value,) = c.query_row::<(String,), _>(
+        "SELECT value FROM english WHERE value LIKE ?",
+        &[Value::Text(String::from("ceroty%"))],
+    )?;
+
+    assert_eq!(value, "cerotype");
+
+    let count = c.query_value::<i64, _>("SELECT COUNT(*) FROM english", ())?;
+    assert_eq!(count, 7);
+
+    let e = c
+        .query_value::<String, _>("SELECT value FROM english WHERE value = 'missing'", ())
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::NoRows);
+    assert_eq!(e.to_string(), "query returned no rows");
+
+    let e = c
+        .query_value::<String, _>("SELECT value FROM english", ())
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::TooManyRows);
+
+    let e = c.query_value::<i64, _>("SELECT nope", ()).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::Sqlite);
+    Ok(())
+}
+
 #[test]
 fn connection_set_busy_handler() -> Result<(), Box<dyn std::error::Error>> {
     let directory = Directory::new("sqlite")?;
@@ -106,6 +331,139 @@ fn connection_set_busy_handler() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn connection_busy_handler_panic() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = Directory::new("sqlite")?;
+    let path = directory.path().join("database.sqlite3");
+    let a = setup_users(&path)?;
+    a.execute("BEGIN EXCLUSIVE")?;
+
+    let mut b = Connection::open(&path)?;
+    b.set_busy_handler(|_| panic!("busy handler panicked"))?;
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)")
+    }));
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"busy handler panicked")
+    );
+
+    a.execute("COMMIT")?;
+    b.remove_busy_handler()?;
+    b.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)")?;
+    Ok(())
+}
+
+#[test]
+fn quote() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let table = sqlite_ll::quote_identifier("my \"table\"")?;
+    c.execute(format!("CREATE TABLE {table} (value TEXT)"))?;
+
+    let value = sqlite_ll::quote_string("it's 'quoted'")?;
+    let null = sqlite_ll::quote_nullable(None)?;
+    c.execute(format!("INSERT INTO {table} VALUES ({value}), ({null})"))?;
+
+    let mut stmt = c.prepare(format!("SELECT value FROM {table}"))?;
+    let values = stmt
+        .iter::<(Option<String>,)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+    assert_eq!(values, [(Some(String::from("it's 'quoted'")),), (None,)]);
+
+    assert_eq!(
+        sqlite_ll::quote_string("a\0b").unwrap_err().code(),
+        Code::MISUSE
+    );
+    Ok(())
+}
+
+#[test]
+fn string_matching() -> sqlite_ll::Result<()> {
+    use std::cmp::Ordering;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?1 GLOB ?2, ?1 LIKE ?2 ESCAPE '\\'")?;
+
+    let cases = [
+        ("abc", "a*"),
+        ("abc", "A%"),
+        ("a_c", "a\\_c"),
+        ("abc", "a\\_c"),
+        ("abc", "[a-c]?c"),
+        ("ÅÄÖ", "åäö"),
+    ];
+
+    for (string, pattern) in cases {
+        stmt.reset()?;
+        stmt.bind_all((string, pattern))?;
+        assert_eq!(stmt.step()?, State::Row);
+
+        let glob = stmt.read::<bool>(0)?;
+        let like = stmt.read::<bool>(1)?;
+        assert_eq!(
+            sqlite_ll::strglob(pattern, string)?,
+            glob,
+            "{string} GLOB {pattern}"
+        );
+        assert_eq!(
+            sqlite_ll::strlike(pattern, string, Some('\\'))?,
+            like,
+            "{string} LIKE {pattern}"
+        );
+    }
+
+    assert_eq!(sqlite_ll::stricmp("ABC", "abd")?, Ordering::Less);
+    assert_eq!(sqlite_ll::strnicmp("ABC", "abd", 2)?, Ordering::Equal);
+    assert_eq!(sqlite_ll::stricmp("Å", "å")?, Ordering::Less);
+    assert!(sqlite_ll::strglob("*", "a\0b").is_err());
+    Ok(())
+}
+
+#[test]
+fn keywords() -> sqlite_ll::Result<()> {
+    let keywords = (0..sqlite_ll::keyword_count())
+        .map(|i| sqlite_ll::keyword_name(i).expect("keyword in range"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(sqlite_ll::keyword_name(keywords.len()), None);
+    assert!(keywords.iter().all(|k| sqlite_ll::is_keyword(k)));
+    assert!(sqlite_ll::is_keyword("Transaction"));
+    assert!(!sqlite_ll::is_keyword("transactions"));
+
+    // Keywords which aren't quoted fail to parse as a table name.
+    let c = Connection::open(":memory:")?;
+    assert!(c.execute("CREATE TABLE order (id)").is_err());
+    c.execute(format!(
+        "CREATE TABLE {} (id)",
+        sqlite_ll::quote_identifier("order")?
+    ))?;
+    Ok(())
+}
+
+#[test]
+fn randomness() {
+    let mut a = vec![0u8; 1024];
+    let mut b = vec![0u8; 1024];
+    sqlite_ll::randomness(&mut a);
+    sqlite_ll::randomness(&mut b);
+    assert_ne!(a, b);
+    sqlite_ll::randomness(&mut []);
+}
+
+#[test]
+fn sleep() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let slept = sqlite_ll::sleep(Duration::from_micros(1500));
+    assert!(slept >= Duration::from_millis(2));
+    assert!(start.elapsed() >= Duration::from_micros(1500));
+}
+
 #[test]
 fn statement_bind() -> sqlite_ll::Result<()> {
     let c = setup_users(":memory:")?;
@@ -121,6 +479,43 @@ fn statement_bind() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn statement_execute_batch() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    c.execute("CREATE TABLE numbers (value INTEGER UNIQUE)")?;
+
+    let mut s = c.prepare("INSERT INTO numbers VALUES (?)")?;
+
+    let params = (0..100).map(|n| [Value::Integer(n)]).collect::<Vec<_>>();
+    assert_eq!(s.execute_batch(params.iter())?, 100);
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        100
+    );
+
+    // A duplicate in the second chunk rolls back only that chunk.
+    let params = [100, 101, 102, 103, 100]
+        .map(|n| [Value::Integer(n)])
+        .to_vec();
+    let e = s.execute_batch_chunked(2, params.iter()).unwrap_err();
+    assert_eq!(e.code(), Code::CONSTRAINT);
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        104
+    );
+
+    // Inside of an existing transaction nothing is committed.
+    c.execute("BEGIN")?;
+    let params = [200, 201].map(|n| [Value::Integer(n)]).to_vec();
+    s.execute_batch_chunked(1, params.iter())?;
+    c.execute("ROLLBACK")?;
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        104
+    );
+    Ok(())
+}
+
 #[test]
 fn statement_bind_with_nullable() -> sqlite_ll::Result<()> {
     let connection = setup_users(":memory:")?;
@@ -186,6 +581,18 @@ fn statement_column_name() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn statement_column_index() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let s = c.prepare("SELECT id, name, age, name AS id FROM users")?;
+
+    assert_eq!(s.column_index("id")?, Some(0));
+    assert_eq!(s.column_index("age")?, Some(2));
+    assert_eq!(s.column_index("photo")?, None);
+    assert_eq!(s.column_index("name")?, Some(1));
+    Ok(())
+}
+
 #[test]
 fn statement_column_type() -> sqlite_ll::Result<()> {
     let connection = setup_users(":memory:")?;
@@ -206,6 +613,104 @@ fn statement_column_type() -> sqlite_ll::Result<()> {
     Ok(())
 }
 
+#[test]
+fn statement_column_len() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT X'010203', 'héllo', 1234, NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.column_len(0), 3);
+    assert_eq!(stmt.column_len(1), 6);
+    assert_eq!(stmt.column_len(2), 4);
+    assert_eq!(stmt.column_len(3), 0);
+    assert_eq!(stmt.read_str(1)?.len(), stmt.column_len(1));
+    Ok(())
+}
+
+#[test]
+fn statement_read_declared() -> sqlite_ll::Result<()> {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use sqlite_ll::{Affinity, DeclaredValue};
+
+    let c = Connection::open(":memory:")?;
+    c.execute(
+        "
+        CREATE TABLE events (flag BOOL, at TIMESTAMP, day DATE, note DATETIME, raw);
+        INSERT INTO events VALUES (0, 1680352205, '2023-04-01', 'soon', X'01');
+        INSERT INTO events VALUES (NULL, 2460035.5, '1969-12-31T23:00:00.5+01:00', NULL, 1);
+        ",
+    )?;
+
+    let mut stmt = c.prepare("SELECT flag, at, day, note, raw FROM events")?;
+    assert_eq!(stmt.column_decltype(4)?, None);
+    assert_eq!(
+        Affinity::from_decltype(stmt.column_decltype(1)?.unwrap()),
+        Affinity::Numeric
+    );
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read_declared(0)?, DeclaredValue::Boolean(false));
+    assert_eq!(
+        stmt.read_declared(1)?,
+        DeclaredValue::Timestamp(UNIX_EPOCH + Duration::from_secs(1680352205))
+    );
+    assert_eq!(
+        stmt.read_declared(2)?,
+        DeclaredValue::Timestamp(UNIX_EPOCH + Duration::from_secs(1680307200))
+    );
+    assert_eq!(
+        stmt.read_declared(3)?,
+        DeclaredValue::Text(String::from("soon"))
+    );
+    assert_eq!(stmt.read_declared(4)?, DeclaredValue::Blob(vec![1]));
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read_declared(0)?, DeclaredValue::Null);
+    assert_eq!(
+        stmt.read_declared(1)?,
+        DeclaredValue::Timestamp(UNIX_EPOCH + Duration::from_secs(1680307200))
+    );
+    assert_eq!(
+        stmt.read_declared(2)?,
+        DeclaredValue::Timestamp(UNIX_EPOCH - Duration::from_millis(7199500))
+    );
+    assert_eq!(stmt.read_declared(4)?, DeclaredValue::Integer(1));
+    Ok(())
+}
+
+#[test]
+fn statement_column_value() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut select = c.prepare("SELECT 'text', 1.5, X'01', NULL")?;
+    assert_eq!(select.step()?, State::Row);
+
+    let values = (0..4)
+        .map(|i| select.column_value(i).dup())
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(select.column_value(0).value_type(), Type::Text);
+    assert_eq!(select.column_value(3).to_value()?, Value::Null);
+    assert_eq!(select.step()?, State::Done);
+
+    let mut echo = c.prepare("SELECT ?, ?, ?, ?")?;
+
+    for (i, value) in values.iter().enumerate() {
+        echo.bind(i + 1, value)?;
+    }
+
+    assert_eq!(echo.step()?, State::Row);
+    assert_eq!(echo.read::<String>(0)?, "text");
+    assert_eq!(echo.read::<f64>(1)?, 1.5);
+    assert_eq!(echo.read::<Vec<u8>>(2)?, [1]);
+    assert_eq!(echo.column_type(3), Type::Null);
+
+    let copy = values[0].try_clone()?;
+    drop(values);
+    assert_eq!(copy.to_value()?, Value::Text(String::from("text")));
+    Ok(())
+}
+
 #[test]
 fn statement_parameter_index() -> sqlite_ll::Result<()> {
     let connection = setup_users(":memory:")?;
@@ -242,61 +747,1966 @@ fn statement_read() -> sqlite_ll::Result<()> {
 }
 
 #[test]
-fn statement_read_with_nullable() -> sqlite_ll::Result<()> {
-    let c = setup_users(":memory:")?;
-    let s = "SELECT * FROM users";
-    let mut s = c.prepare(s)?;
+fn statement_primitives() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?, ?, ?, ?, ?, ?, ?, ?")?;
 
-    assert_eq!(s.step()?, State::Row);
-    assert_eq!(s.read::<Option<i64>>(0)?, Some(1));
-    assert_eq!(s.read::<Option<String>>(1)?, Some(String::from("Alice")));
-    assert_eq!(s.read::<Option<f64>>(2)?, Some(42.69));
-    assert_eq!(s.read::<Option<Vec<u8>>>(3)?, Some(vec![0x42, 0x69]));
-    assert_eq!(s.read::<Option<String>>(4)?, None);
-    assert_eq!(s.step()?, State::Done);
+    stmt.bind_all((
+        1u8,
+        -2i16,
+        3u32,
+        4usize,
+        -5isize,
+        1.5f32,
+        true,
+        u64::MAX / 2,
+    ))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<u8>(0)?, 1);
+    assert_eq!(stmt.read::<i16>(1)?, -2);
+    assert_eq!(stmt.read::<u32>(2)?, 3);
+    assert_eq!(stmt.read::<usize>(3)?, 4);
+    assert_eq!(stmt.read::<isize>(4)?, -5);
+    assert_eq!(stmt.read::<f32>(5)?, 1.5);
+    assert!(stmt.read::<bool>(6)?);
+    assert_eq!(stmt.read::<u64>(7)?, u64::MAX / 2);
+
+    assert_eq!(stmt.read::<u8>(1).unwrap_err().code(), Code::RANGE);
+    assert_eq!(stmt.read::<i8>(7).unwrap_err().code(), Code::RANGE);
+
+    stmt.reset()?;
+    let e = stmt.bind(1, u64::MAX).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+
+    let mut stmt = c.prepare("SELECT 1e300, 0")?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<f32>(0).unwrap_err().code(), Code::RANGE);
+    assert!(!stmt.read::<bool>(1)?);
     Ok(())
 }
 
 #[test]
-fn statement_wildcard() -> sqlite_ll::Result<()> {
-    let c = setup_english(":memory:")?;
-    let s = "SELECT value FROM english WHERE value LIKE '%type'";
-    let mut s = c.prepare(s)?;
+fn statement_durations() -> sqlite_ll::Result<()> {
+    use sqlite_ll::Seconds;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    let mut count = 0;
+    let c = Connection::open(":memory:")?;
 
-    while let State::Row = s.step()? {
-        count += 1;
-    }
+    let duration = Duration::new(3, 250_000_001);
+    let after = UNIX_EPOCH + Duration::from_millis(1_680_352_205_123);
+    let before = UNIX_EPOCH - Duration::from_millis(1500);
 
-    assert_eq!(count, 6);
+    let mut stmt = c.prepare("SELECT ?, ?, ?, ?, -1")?;
+    stmt.bind_all((duration, after, before, Seconds(before)))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<i64>(0)?, 3_250_000_001);
+    assert_eq!(stmt.read::<i64>(1)?, 1_680_352_205_123);
+    assert_eq!(stmt.read::<i64>(2)?, -1500);
+    assert_eq!(stmt.read::<f64>(3)?, -1.5);
+
+    assert_eq!(stmt.read::<Duration>(0)?, duration);
+    assert_eq!(stmt.read::<SystemTime>(1)?, after);
+    assert_eq!(stmt.read::<SystemTime>(2)?, before);
+    assert_eq!(stmt.read::<SystemTime>(3)?, before);
+    assert_eq!(
+        stmt.read::<Seconds<SystemTime>>(4)?,
+        Seconds(UNIX_EPOCH - Duration::from_secs(1))
+    );
+
+    assert_eq!(stmt.read::<Duration>(4).unwrap_err().code(), Code::RANGE);
+    assert_eq!(stmt.read::<Duration>(3).unwrap_err().code(), Code::RANGE);
+
+    // The smallest number of seconds which doesn't fit in a duration.
+    let mut stmt = c.prepare("SELECT 18446744073709551616.0")?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<Duration>(0).unwrap_err().code(), Code::RANGE);
+    assert_eq!(
+        stmt.read::<Seconds<Duration>>(0).unwrap_err().code(),
+        Code::RANGE
+    );
+    assert_eq!(stmt.read::<SystemTime>(0).unwrap_err().code(), Code::RANGE);
     Ok(())
 }
 
 #[test]
-fn statement_wildcard_with_binding() -> sqlite_ll::Result<()> {
-    let c = setup_english(":memory:")?;
-    let s = "SELECT value FROM english WHERE value LIKE ?";
-    let mut s = c.prepare(s)?;
-    s.bind(1, "%type")?;
+fn statement_paths() -> sqlite_ll::Result<()> {
+    use std::path::{Path, PathBuf};
 
-    let mut count = 0;
-    while let State::Row = s.step()? {
-        count += 1;
+    let c = Connection::open(":memory:")?;
+    let path = Path::new("/var/log/syslog");
+
+    let mut stmt = c.prepare("SELECT ?, ?")?;
+    stmt.bind_all((path, path.to_path_buf()))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.column_type(0), Type::Text);
+    assert_eq!(stmt.read::<PathBuf>(0)?, path);
+    assert_eq!(stmt.read::<PathBuf>(1)?, path);
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/tmp/\xff\xfe"));
+
+        let mut stmt = c.prepare("SELECT ?")?;
+        stmt.bind(1, path)?;
+        assert_eq!(stmt.step()?, State::Row);
+
+        assert_eq!(stmt.column_type(0), Type::Blob);
+        assert_eq!(stmt.read::<PathBuf>(0)?, path);
     }
-    assert_eq!(count, 6);
+
     Ok(())
 }
 
 #[test]
-fn test_dropped_connection() -> sqlite_ll::Result<()> {
-    let c = setup_users(":memory:")?;
-    let s = "SELECT id, name, age, photo AS user_photo FROM users";
-    let s = c.prepare(s)?;
-    drop(c);
+fn statement_network_addresses() -> sqlite_ll::Result<()> {
+    use sqlite_ll::Packed;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
-    let names = s.column_names()?;
-    assert_eq!(names, vec!["id", "name", "age", "user_photo"]);
+    let c = Connection::open(":memory:")?;
+
+    let v4 = Ipv4Addr::new(10, 0, 0, 1);
+    let v6 = "2001:db8::1".parse::<Ipv6Addr>().unwrap();
+    let socket = "[2001:db8::1]:8080".parse::<SocketAddr>().unwrap();
+
+    let mut stmt = c.prepare("SELECT ?, ?, ?, ?, ?, ?, 'nope'")?;
+    stmt.bind_all((
+        v4,
+        IpAddr::V6(v6),
+        socket,
+        Packed(v4),
+        Packed(IpAddr::V6(v6)),
+        Packed(socket),
+    ))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<String>(0)?, "10.0.0.1");
+    assert_eq!(stmt.read::<String>(1)?, "2001:db8::1");
+    assert_eq!(stmt.read::<String>(2)?, "[2001:db8::1]:8080");
+    assert_eq!(stmt.read::<Vec<u8>>(5)?.len(), 18);
+
+    assert_eq!(stmt.read::<Ipv4Addr>(0)?, v4);
+    assert_eq!(stmt.read::<IpAddr>(1)?, IpAddr::V6(v6));
+    assert_eq!(stmt.read::<SocketAddr>(2)?, socket);
+    assert_eq!(stmt.read::<Ipv4Addr>(3)?, v4);
+    assert_eq!(stmt.read::<Packed<Ipv6Addr>>(4)?, Packed(v6));
+    assert_eq!(stmt.read::<SocketAddr>(5)?, socket);
+
+    assert_eq!(stmt.read::<Ipv4Addr>(4).unwrap_err().code(), Code::MISMATCH);
+    assert_eq!(stmt.read::<IpAddr>(6).unwrap_err().code(), Code::MISMATCH);
+    Ok(())
+}
+
+#[test]
+fn statement_read_owned() -> sqlite_ll::Result<()> {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 'text', X'0102', 42")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(&*stmt.read::<Box<str>>(0)?, "text");
+    assert_eq!(&*stmt.read::<Rc<str>>(0)?, "text");
+    assert_eq!(&*stmt.read::<Arc<str>>(0)?, "text");
+    assert_eq!(stmt.read::<Cow<'static, str>>(0)?, "text");
+    assert_eq!(&*stmt.read::<Box<[u8]>>(1)?, [1, 2]);
+    assert_eq!(&*stmt.read::<Rc<[u8]>>(1)?, [1, 2]);
+    assert_eq!(&*stmt.read::<Arc<[u8]>>(1)?, [1, 2]);
+    assert_eq!(&*stmt.read::<Cow<'static, [u8]>>(1)?, [1, 2]);
+    assert_eq!(&*stmt.read::<Arc<str>>(2)?, "42");
+    Ok(())
+}
+
+#[test]
+fn statement_read_str() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 'text', 42, X'6869', X'ff', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read_str(0)?, "text");
+    assert_eq!(stmt.read_str(1)?, "42");
+    assert_eq!(stmt.read_str(2)?, "hi");
+    assert_eq!(stmt.column_type(2), Type::Blob);
+    assert!(stmt.read_str(3).is_err());
+    assert_eq!(stmt.read_str(4).unwrap_err().code(), Code::MISMATCH);
+    Ok(())
+}
+
+#[test]
+fn statement_read_text_unchecked() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 'text', 42, X'6869', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    unsafe {
+        assert_eq!(stmt.read_text_unchecked(0), "text");
+        assert_eq!(stmt.read_text_unchecked(1), "42");
+        assert_eq!(stmt.read_text_unchecked(2), "hi");
+        assert_eq!(stmt.read_text_unchecked(3), "");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn statement_read_blob() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT X'0102', 'hi', 42, NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read_blob(0)?, [1, 2]);
+    assert_eq!(stmt.read_blob(1)?, b"hi");
+    assert_eq!(stmt.read_blob(2)?, b"42");
+    assert_eq!(stmt.read_blob(3)?, b"");
+
+    let blob = stmt.read_blob(1)?;
+    assert_eq!(stmt.read_str(1)?, "hi");
+    assert_eq!(blob, b"hi");
+    Ok(())
+}
+
+#[test]
+fn statement_read_into() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT X'0102', 'hi', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let mut buf = vec![9, 9, 9];
+    stmt.read_into(0, &mut buf)?;
+    assert_eq!(buf, [1, 2]);
+    stmt.read_into(1, &mut buf)?;
+    assert_eq!(buf, b"hi");
+
+    let mut text = String::from("previous");
+    stmt.read_str_into(1, &mut text)?;
+    assert_eq!(text, "hi");
+    assert!(stmt.read_str_into(2, &mut text).is_err());
+    assert_eq!(text, "hi");
+    Ok(())
+}
+
+#[test]
+fn statement_fixed_bytes() -> sqlite_ll::Result<()> {
+    use sqlite_ll::FixedBytes;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT X'010203', X'0102', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<[u8; 3]>(0)?, [1, 2, 3]);
+    assert_eq!(stmt.read::<FixedBytes<3>>(0)?.try_into_bytes()?, [1, 2, 3]);
+    assert_eq!(stmt.read::<Option<[u8; 3]>>(2)?, None);
+
+    let e = stmt.read::<[u8; 3]>(1).unwrap_err();
+    assert_eq!(e.code(), Code::MISMATCH);
+    assert!(e
+        .to_string()
+        .contains("expected 3 bytes, but the column has 2"));
+
+    let e = stmt.read::<FixedBytes<2>>(0)?.try_into_bytes().unwrap_err();
+    assert_eq!(e.code(), Code::MISMATCH);
+    assert!(e
+        .to_string()
+        .contains("expected 2 bytes, but the column has 3"));
+
+    let e = stmt.read::<[u8; 3]>(2).unwrap_err();
+    assert!(e
+        .to_string()
+        .contains("expected 3 bytes, but the column has 0"));
+    Ok(())
+}
+
+#[test]
+fn statement_strict() -> sqlite_ll::Result<()> {
+    use sqlite_ll::Strict;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 1, 1.5, 'text', X'0102', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<Strict<u8>>(0)?, Strict(1));
+    assert_eq!(stmt.read::<Strict<bool>>(0)?, Strict(true));
+    assert_eq!(stmt.read::<Strict<f64>>(1)?, Strict(1.5));
+    assert_eq!(stmt.read::<Strict<String>>(2)?.0, "text");
+    assert_eq!(stmt.read::<Strict<[u8; 2]>>(3)?, Strict([1, 2]));
+    assert_eq!(stmt.read::<Option<Strict<i64>>>(4)?, None);
+
+    let e = stmt.read::<Strict<f64>>(0).unwrap_err();
+    assert_eq!(e.code(), Code::MISMATCH);
+    assert!(e
+        .to_string()
+        .contains("expected Float, but the column has type Integer"));
+
+    assert!(stmt.read::<Strict<i64>>(2).is_err());
+    assert!(stmt.read::<Strict<String>>(3).is_err());
+    assert!(stmt.read::<Strict<Vec<u8>>>(2).is_err());
+    assert!(stmt.read::<Strict<i64>>(4).is_err());
+    Ok(())
+}
+
+#[test]
+fn statement_read_value_ref() -> sqlite_ll::Result<()> {
+    use sqlite_ll::ValueRef;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT X'0102', 1.5, 42, 'text', NULL")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let values = (0..5)
+        .map(|i| stmt.read_value_ref(i))
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        values,
+        [
+            ValueRef::Blob(&[1, 2]),
+            ValueRef::Float(1.5),
+            ValueRef::Integer(42),
+            ValueRef::Text("text"),
+            ValueRef::Null,
+        ]
+    );
+
+    let owned = values.iter().map(|v| v.to_value()).collect::<Vec<_>>();
+    assert_eq!(owned[3], Value::Text(String::from("text")));
+    assert_eq!(ValueRef::from(&owned[0]), values[0]);
+    assert_eq!(values[2].kind(), Type::Integer);
+
+    let mut echo = c.prepare("SELECT ?, ?")?;
+    echo.bind(1, values[0])?;
+    echo.bind(2, values[3])?;
+    assert_eq!(echo.step()?, State::Row);
+    assert_eq!(echo.read::<Vec<u8>>(0)?, [1, 2]);
+    assert_eq!(echo.read::<String>(1)?, "text");
+    Ok(())
+}
+
+#[test]
+fn value_sql_literal() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let values = [
+        Value::Blob(vec![0x00, 0xab]),
+        Value::Float(1.0),
+        Value::Float(-2.5e-300),
+        Value::Float(f64::INFINITY),
+        Value::Integer(i64::MIN),
+        Value::Text(String::from("it's a 'quote'")),
+        Value::Text(String::new()),
+        Value::Null,
+    ];
+
+    for value in &values {
+        let literal = value.to_sql_literal();
+        assert_eq!(&literal.parse::<Value>()?, value, "{literal}");
+
+        let mut stmt = c.prepare(format!("SELECT {literal}"))?;
+        assert_eq!(stmt.step()?, State::Row);
+        assert_eq!(&stmt.read::<Value>(0)?, value, "{literal}");
+    }
+
+    assert_eq!(" x'0A' ".parse::<Value>()?, Value::Blob(vec![0x0a]));
+    assert_eq!("null".parse::<Value>()?, Value::Null);
+    assert_eq!(
+        "9223372036854775808".parse::<Value>()?,
+        Value::Float(9223372036854775808.0)
+    );
+
+    for invalid in [
+        "", "'open", "'a'b'", "X'0'", "X'zz'", "X'+a'", "X'+f+f'", "abc", "1 2", "inf",
+    ] {
+        assert!(invalid.parse::<Value>().is_err(), "{invalid}");
+    }
+
+    for invalid in ["X'+a'", "X'+f+f'", "X'-1'"] {
+        assert!(sqlite_ll::unquote_blob(invalid).is_err(), "{invalid}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn value_cmp_sqlite() -> sqlite_ll::Result<()> {
+    use std::cmp::Ordering;
+
+    let values = [
+        Value::Blob(vec![1]),
+        Value::Blob(vec![]),
+        Value::Text(String::from("b")),
+        Value::Text(String::from("B")),
+        Value::Float(9.5e18),
+        Value::Integer(i64::MAX),
+        Value::Float(-0.5),
+        Value::Integer(0),
+        Value::Float(2.0),
+        Value::Integer(2),
+        Value::Null,
+    ];
+
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE t (v)")?;
+    c.insert_rows(
+        "t",
+        &["v"],
+        values
+            .iter()
+            .map(|v| [v.clone()])
+            .collect::<Vec<_>>()
+            .as_slice(),
+    )?;
+
+    let mut stmt = c.prepare("SELECT v FROM t ORDER BY v")?;
+    let expected = stmt
+        .iter::<(Value,)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(Value::cmp_sqlite);
+
+    assert_eq!(sorted.len(), expected.len());
+
+    for (a, (b,)) in sorted.iter().zip(&expected) {
+        assert_eq!(a.cmp_sqlite(b), Ordering::Equal, "{a} != {b}");
+    }
+
+    assert_eq!(
+        Value::Integer(i64::MAX).cmp_sqlite(&Value::Float(9223372036854775807.0)),
+        Ordering::Less
+    );
+    assert_eq!(
+        Value::Float(f64::NAN).cmp_sqlite(&Value::Null),
+        Ordering::Equal
+    );
+    Ok(())
+}
+
+#[test]
+fn sync_connection() -> sqlite_ll::Result<()> {
+    use std::sync::Arc;
+
+    let c = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .set_full_mutex()
+        .open(":memory:")?;
+
+    let c = Arc::new(SyncConnection::new(c).map_err(|(_, e)| e)?);
+    c.execute("CREATE TABLE numbers (n INTEGER)")?;
+
+    let threads = (0..8i64)
+        .map(|n| {
+            let c = c.clone();
+
+            thread::spawn(move || {
+                let mut stmt = c.prepare("INSERT INTO numbers VALUES (?)")?;
+
+                for i in 0..10 {
+                    stmt.reset()?;
+                    stmt.bind(1, n * 10 + i)?;
+                    assert_eq!(stmt.step()?, State::Done);
+                }
+
+                Ok::<_, sqlite_ll::Error>(())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for t in threads {
+        t.join().unwrap()?;
+    }
+
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        80
+    );
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT SUM(n) FROM numbers", ())?,
+        3160
+    );
+    assert_eq!(c.total_change_count(), 80);
+
+    let c = Arc::try_unwrap(c).ok().expect("connection is still shared");
+    let c = c.into_inner();
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        80
+    );
+    Ok(())
+}
+
+#[test]
+fn sync_connection_no_mutex() -> sqlite_ll::Result<()> {
+    let c = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .set_no_mutex()
+        .open(":memory:")?;
+
+    let (c, e) = SyncConnection::new(c).err().expect("expected error");
+    assert_eq!(e.code(), Code::MISUSE);
+    assert!(e.to_string().contains("serialized"));
+    c.execute("SELECT 1")?;
+    Ok(())
+}
+
+#[test]
+fn open_shared_memory() -> sqlite_ll::Result<()> {
+    let a = Connection::open_shared_memory("tests/open shared?memory#&")?;
+    let b = Connection::open_shared_memory("tests/open shared?memory#&")?;
+    let other = Connection::open_shared_memory("tests/open shared")?;
+
+    a.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice')")?;
+    let name = b.query_value::<String, _>("SELECT name FROM users", ())?;
+    assert_eq!(name, "Alice");
+    assert!(other.execute("SELECT name FROM users").is_err());
+
+    drop((a, b));
+
+    let a = Connection::open_shared_memory("tests/open shared?memory#&")?;
+    assert!(a.execute("SELECT name FROM users").is_err());
+
+    let e = Connection::open_shared_memory("a\0b")
+        .err()
+        .expect("expected error");
+    assert_eq!(e.code(), Code::MISUSE);
+    Ok(())
+}
+
+#[test]
+fn statement_step_batch() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE values_ (n INTEGER, s TEXT)")?;
+
+    let mut insert = c.prepare("INSERT INTO values_ VALUES (?, ?)")?;
+
+    for n in 0..10i64 {
+        insert.reset()?;
+        insert.bind(1, n)?;
+        insert.bind(2, format!("row {n}"))?;
+        assert_eq!(insert.step()?, State::Done);
+    }
+
+    let mut stmt = c.prepare("SELECT n, s FROM values_ ORDER BY n")?;
+    let mut buf = RowBuffer::new();
+    let mut batches = Vec::new();
+    let mut rows = Vec::new();
+
+    loop {
+        let count = stmt.step_batch(4, &mut buf)?;
+        assert_eq!(count, buf.len());
+        assert_eq!(buf.column_count(), 2);
+        batches.push(count);
+
+        for row in &buf {
+            rows.push(row.to_vec());
+        }
+
+        if count < 4 {
+            break;
+        }
+    }
+
+    assert_eq!(batches, [4, 4, 2]);
+    assert_eq!(rows.len(), 10);
+    assert_eq!(
+        rows[9],
+        [Value::Integer(9), Value::Text(String::from("row 9"))]
+    );
+
+    let mut stmt = c.prepare("SELECT n, s FROM values_ WHERE n > 100")?;
+    assert_eq!(stmt.step_batch(4, &mut buf)?, 0);
+    assert!(buf.is_empty());
+    assert!(buf.get(0).is_none());
+    Ok(())
+}
+
+#[test]
+fn statement_columns() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE users (name TEXT, email TEXT, age INTEGER)")?;
+
+    let mut stmt = c.prepare("SELECT name, email, age, age AS name FROM users")?;
+    let columns = stmt.columns()?;
+
+    assert_eq!(columns.len(), 4);
+    assert_eq!(
+        columns.names().collect::<Vec<_>>(),
+        ["name", "email", "age", "name"]
+    );
+    assert_eq!(columns.index("name"), Some(0));
+    assert_eq!(columns.index("age"), Some(2));
+    assert_eq!(columns.index("missing"), None);
+    assert_eq!(
+        columns.iter().map(|c| c.decltype()).collect::<Vec<_>>(),
+        [Some("TEXT"), Some("TEXT"), Some("INTEGER"), Some("INTEGER")]
+    );
+    assert!(columns.get(4).is_none());
+
+    stmt.reset()?;
+    assert_eq!(stmt.columns()?.len(), 4);
+
+    let stmt = c.prepare("SELECT 1 + 1")?;
+    let columns = stmt.columns()?;
+    assert_eq!(columns[0].name(), "1 + 1");
+    assert_eq!(columns[0].decltype(), None);
+    Ok(())
+}
+
+#[test]
+fn db_mutex() -> sqlite_ll::Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let c = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .set_full_mutex()
+        .open(":memory:")?;
+
+    c.execute("CREATE TABLE numbers (n INTEGER)")?;
+
+    let mut stmt = c.prepare("INSERT INTO numbers VALUES (1)")?;
+    let done = Arc::new(AtomicBool::new(false));
+
+    let guard = c.db_mutex().expect("connection is serialized");
+
+    let t = thread::spawn({
+        let done = done.clone();
+
+        move || {
+            let state = stmt.step();
+            done.store(true, Ordering::SeqCst);
+            state
+        }
+    });
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!done.load(Ordering::SeqCst));
+
+    // The mutex is recursive, so the connection can still be used.
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        0
+    );
+    drop(guard);
+
+    assert_eq!(t.join().unwrap()?, State::Done);
+    assert!(done.load(Ordering::SeqCst));
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())?,
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn sql_interceptor() -> sqlite_ll::Result<()> {
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+
+    let mut c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE users (name TEXT); CREATE TABLE test_users (name TEXT)")?;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    c.set_sql_interceptor({
+        let seen = seen.clone();
+
+        move |sql| {
+            seen.lock().unwrap().push(sql.to_owned());
+            Cow::Owned(sql.replace("users", "test_users"))
+        }
+    });
+
+    c.execute("INSERT INTO users VALUES ('Alice')")?;
+
+    {
+        let mut stmt = c.prepare_cached("INSERT INTO users VALUES (?)")?;
+        stmt.bind(1, "Bob")?;
+        assert_eq!(stmt.step()?, State::Done);
+    }
+
+    let mut names = Vec::new();
+
+    c.iterate("SELECT name FROM users ORDER BY name", |pairs| {
+        names.push(pairs[0].1.unwrap().to_owned());
+        true
+    })?;
+
+    assert_eq!(names, ["Alice", "Bob"]);
+    assert_eq!(seen.lock().unwrap().len(), 3);
+
+    c.remove_sql_interceptor();
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?,
+        0
+    );
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM test_users", ())?,
+        2
+    );
+    assert_eq!(seen.lock().unwrap().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn observer() -> sqlite_ll::Result<()> {
+    use sqlite_ll::Event;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let observer = {
+        let events = events.clone();
+
+        move |event: Event<'_>| {
+            let event = match event {
+                Event::Open { path } => format!("open {}", path.display()),
+                Event::Close => String::from("close"),
+                Event::Begin => String::from("begin"),
+                Event::Commit => String::from("commit"),
+                Event::Rollback => String::from("rollback"),
+                Event::Checkpoint => String::from("checkpoint"),
+                Event::Error(e) if e.code() == Code::BUSY => String::from("error busy"),
+                Event::Error(..) => String::from("error"),
+                _ => String::from("other"),
+            };
+
+            events.lock().unwrap().push(event);
+        }
+    };
+
+    let c = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .open_observed(":memory:", observer)?;
+
+    c.execute("CREATE TABLE users (name TEXT)")?;
+    c.execute("/* comment */ begin; INSERT INTO users VALUES ('Alice'); COMMIT")?;
+    c.execute("SAVEPOINT a; INSERT INTO users VALUES ('Bob'); ROLLBACK TO a; RELEASE a")?;
+    c.execute("PRAGMA wal_checkpoint(PASSIVE)")?;
+    assert!(c.prepare("SELECT * FROM missing").is_err());
+    assert!(c.query_value::<i64, _>("SELECT 1 WHERE 0", ()).is_err());
+
+    let stmt = c.prepare("SELECT 1")?;
+    let (c, _) = c.close().unwrap_err();
+    drop(stmt);
+    c.close().map_err(|(_, e)| e)?;
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "open :memory:",
+            "commit",
+            "begin",
+            "commit",
+            "begin",
+            "commit",
+            "checkpoint",
+            "error",
+            "error",
+            "error busy",
+            "close",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn fixture() -> sqlite_ll::Result<()> {
+    let fixture = Fixture::new()
+        .table(
+            "users",
+            ["id INTEGER PRIMARY KEY", "name TEXT", "age INTEGER"],
+        )
+        .rows([(1, "Alice", 42), (2, "Bob", 69)])
+        .row((3, "Carol"))
+        .execute("CREATE VIEW adults AS SELECT name FROM users WHERE age > 50")
+        .table("empty", ["value BLOB"]);
+
+    let a = fixture.open()?;
+    let b = fixture.clone().open()?;
+
+    a.execute("DELETE FROM users WHERE id = 1")?;
+
+    assert_eq!(
+        a.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?,
+        2
+    );
+    assert_eq!(
+        b.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?,
+        3
+    );
+    assert_eq!(
+        b.query_value::<String, _>("SELECT name FROM adults", ())?,
+        "Bob"
+    );
+    assert_eq!(
+        b.query_value::<Option<i64>, _>("SELECT age FROM users WHERE id = 3", ())?,
+        None
+    );
+    assert_eq!(
+        b.query_value::<i64, _>("SELECT COUNT(*) FROM empty", ())?,
+        0
+    );
+
+    // Errors roll back the whole fixture.
+    let broken = fixture.clone().row(("too", "many", "values", "here"));
+    let c = Connection::open(":memory:")?;
+    assert!(broken.apply(&c).is_err());
+    assert!(c.execute("SELECT * FROM users").is_err());
+    Ok(())
+}
+
+#[test]
+fn snapshot() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+
+    assert_eq!(
+        sqlite_ll::testing::snapshot(&c, "SELECT *, 1 AS \"odd \"\"name\"\"\" FROM users")?,
+        "\"id\" | \"name\" | \"age\" | \"photo\" | \"email\" | \"odd \"\"name\"\"\"\n\
+         INTEGER 1 | TEXT 'Alice' | REAL 42.69 | BLOB X'4269' | NULL | INTEGER 1\n\
+         (1 row)\n"
+    );
+
+    assert_eq!(
+        sqlite_ll::testing::snapshot(&c, "SELECT 'it''s', 1e999 WHERE 0")?,
+        "\"'it''s'\" | \"1e999\"\n(0 rows)\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn table_format() -> sqlite_ll::Result<()> {
+    use sqlite_ll::{TableFormat, TableStyle};
+
+    let c = setup_users(":memory:")?;
+    c.execute("INSERT INTO users VALUES (2, 'Bob|Builder\nJr', 1.0, NULL, 'bob@example.com')")?;
+
+    let mut stmt = c.prepare("SELECT * FROM users ORDER BY id")?;
+
+    let table = TableFormat::new()
+        .style(TableStyle::Markdown)
+        .max_width(8)
+        .format(&mut stmt)?;
+
+    assert_eq!(
+        table,
+        "| id | name     | age   | photo   | email    |\n\
+         |----|----------|-------|---------|----------|\n\
+         | 1  | Alice    | 42.69 | X'4269' |          |\n\
+         | 2  | Bob\\|... | 1.0   |         | bob@e... |\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unicode_like() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.register_unicode_like()?;
+
+    let like = |text: &str, pattern: &str| -> sqlite_ll::Result<Option<bool>> {
+        c.query_value("SELECT ? LIKE ?", (text, pattern))
+    };
+
+    assert_eq!(like("Ärlig", "ärLIG")?, Some(true));
+    assert_eq!(like("ΌΣΟΣ", "όσος")?, Some(true));
+    assert_eq!(like("ärlig", "_rl%")?, Some(true));
+    assert_eq!(like("ärlig", "%L_G")?, Some(true));
+    assert_eq!(like("ärlig", "%%")?, Some(true));
+    assert_eq!(like("", "%")?, Some(true));
+    assert_eq!(like("abcabd", "%ab_")?, Some(true));
+    assert_eq!(like("ärlig", "arlig")?, Some(false));
+    assert_eq!(like("ärlig", "ärli")?, Some(false));
+    assert_eq!(like("ärlig", "_")?, Some(false));
+    assert_eq!(like("ärlig", "ärligt%")?, Some(false));
+
+    assert_eq!(
+        c.query_value::<Option<bool>, _>("SELECT NULL LIKE 'a'", ())?,
+        None
+    );
+    assert_eq!(
+        c.query_value::<Option<bool>, _>("SELECT 'a' LIKE NULL", ())?,
+        None
+    );
+
+    // The built-in implementation is still used with an escape character.
+    assert!(c.query_value::<bool, _>("SELECT 'A%' LIKE 'a^%' ESCAPE '^'", ())?);
+    assert!(!c.query_value::<bool, _>("SELECT 'Ä%' LIKE 'ä^%' ESCAPE '^'", ())?);
+    Ok(())
+}
+
+#[test]
+fn geopoly_polygon() -> sqlite_ll::Result<()> {
+    use sqlite_ll::geopoly::Polygon;
+
+    let triangle = Polygon::new([[0.0, 0.0], [2.5, 0.0], [0.0, -1.5]]);
+
+    assert_eq!(triangle.to_json(), "[[0,0],[2.5,0],[0,-1.5],[0,0]]");
+    assert_eq!(
+        Polygon::from_json(" [ [0, 0], [2.5, 0.0] , [0,-1.5e0] ] ")?,
+        triangle
+    );
+
+    let blob = triangle.to_blob();
+    assert_eq!(&blob[..4], &[1, 0, 0, 3]);
+    assert_eq!(Polygon::from_blob(&blob)?, triangle);
+
+    let mut big_endian = vec![0, 0, 0, 3];
+
+    for [x, y] in triangle.vertices() {
+        big_endian.extend_from_slice(&x.to_be_bytes());
+        big_endian.extend_from_slice(&y.to_be_bytes());
+    }
+
+    assert_eq!(Polygon::from_blob(&big_endian)?, triangle);
+
+    for invalid in [
+        "",
+        "[]",
+        "[[0,0],[1,1]]",
+        "[[0,0],[1,1],[0,0]]",
+        "[[0,0],[1,1],[2]]",
+        "[[0,0],[1,1],[2,2]] x",
+    ] {
+        let e = Polygon::from_json(invalid).unwrap_err();
+        assert_eq!(e.code(), Code::ERROR);
+    }
+
+    assert!(Polygon::from_blob(&blob[..blob.len() - 1]).is_err());
+    assert!(Polygon::from_blob(&[2, 0, 0, 0]).is_err());
+
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE shapes (shape)")?;
+
+    let mut insert = c.prepare("INSERT INTO shapes VALUES (?)")?;
+    insert.bind(1, &triangle)?;
+    assert_eq!(insert.step()?, State::Done);
+    insert.reset()?;
+    insert.bind(1, triangle.to_json().as_str())?;
+    assert_eq!(insert.step()?, State::Done);
+
+    let shapes = c
+        .prepare("SELECT shape FROM shapes ORDER BY rowid")?
+        .iter::<(Polygon,)>()
+        .map(|row| Ok(row?.0))
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(shapes, [triangle.clone(), triangle]);
+    Ok(())
+}
+
+#[test]
+fn planner_statistics() -> sqlite_ll::Result<()> {
+    use sqlite_ll::stats;
+
+    let c = Connection::open(":memory:")?;
+    assert!(stats::stat1(&c)?.is_empty());
+    assert!(stats::stat4(&c)?.is_empty());
+
+    c.execute(
+        "
+        CREATE TABLE users (name TEXT, age INTEGER, city TEXT);
+        CREATE INDEX users_age_city ON users (age, city);
+        CREATE TABLE log (line TEXT);
+        INSERT INTO users VALUES ('Alice', 42, 'A'), ('Bob', 42, 'B'), ('Carol', 69, 'A'), ('Dave', 42, 'C');
+        INSERT INTO log VALUES ('a'), ('b');
+        ANALYZE;
+        ",
+    )?;
+
+    let stat1 = stats::stat1(&c)?;
+    assert_eq!(stat1.len(), 2);
+
+    assert_eq!(stat1[0].table, "log");
+    assert_eq!(stat1[0].index, None);
+    assert_eq!(stat1[0].rows, 2);
+    assert!(stat1[0].rows_per_key.is_empty());
+
+    assert_eq!(stat1[1].table, "users");
+    assert_eq!(stat1[1].index.as_deref(), Some("users_age_city"));
+    assert_eq!(stat1[1].rows, 4);
+    assert_eq!(stat1[1].rows_per_key, [2, 1]);
+    assert!(!stat1[1].unordered);
+
+    c.execute(
+        "UPDATE sqlite_stat1 SET stat = '4 2 1 unordered sz=12 noskipscan' WHERE idx = 'users_age_city'",
+    )?;
+
+    let stat1 = stats::stat1(&c)?;
+    assert!(stat1[1].unordered);
+    assert!(stat1[1].no_skip_scan);
+    assert_eq!(stat1[1].size, Some(12));
+    assert_eq!(stat1[1].rows_per_key, [2, 1]);
+
+    assert!(stats::stale(&c, 0.5)?.is_empty());
+    c.execute("DELETE FROM users WHERE age = 42")?;
+
+    let stale = stats::stale(&c, 0.5)?;
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].table, "users");
+    assert_eq!((stale[0].estimated, stale[0].actual), (4, 1));
+
+    c.execute("UPDATE sqlite_stat1 SET stat = 'many' WHERE tbl = 'log'")?;
+    let e = stats::stat1(&c).unwrap_err();
+    assert_eq!(e.code(), Code::CORRUPT);
+    Ok(())
+}
+
+#[test]
+fn index_advisor() -> sqlite_ll::Result<()> {
+    use sqlite_ll::advisor;
+
+    let c = Connection::open(":memory:")?;
+
+    c.execute(
+        "
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total REAL);
+        CREATE INDEX users_name ON users (name);
+        CREATE VIEW adults AS SELECT * FROM users WHERE age >= 18;
+        INSERT INTO users (name, age) VALUES ('Alice', 42);
+        ",
+    )?;
+
+    let report = advisor::analyze(
+        &c,
+        [
+            "SELECT * FROM users WHERE name = ?",
+            "SELECT * FROM orders o JOIN users u ON o.user_id = u.id WHERE u.name = ?",
+            "SELECT total FROM orders ORDER BY total",
+            "SELECT name FROM adults",
+            "SELECT 1",
+        ],
+    )?;
+
+    assert_eq!(report.statements.len(), 5);
+
+    let by_name = &report.statements[0];
+    assert!(by_name.full_scans.is_empty());
+    assert!(by_name.indexes.is_empty());
+    assert!(by_name.plan[0].contains("users_name"));
+
+    let join = &report.statements[1];
+    assert_eq!(join.full_scans, ["o"]);
+    assert_eq!(
+        join.indexes,
+        ["CREATE INDEX \"orders_user_id\" ON \"orders\" (\"user_id\")"]
+    );
+
+    let sorted = &report.statements[2];
+    assert_eq!(sorted.full_scans, ["orders"]);
+    assert!(sorted.plan.iter().any(|d| d.starts_with("USE TEMP B-TREE")));
+    assert_eq!(
+        sorted.indexes,
+        ["CREATE INDEX \"orders_total\" ON \"orders\" (\"total\")"]
+    );
+
+    let view = &report.statements[3];
+    assert_eq!(view.full_scans, ["users"]);
+    assert_eq!(
+        view.indexes,
+        ["CREATE INDEX \"users_age\" ON \"users\" (\"age\")"]
+    );
+
+    assert!(report.statements[4].full_scans.is_empty());
+    assert_eq!(report.indexes.len(), 3);
+
+    // The analyzed database is left untouched.
+    assert_eq!(
+        c.query_value::<i64, _>(
+            "SELECT COUNT(*) FROM sqlite_schema WHERE type = 'index'",
+            ()
+        )?,
+        1
+    );
+
+    let e = advisor::analyze(&c, ["SELECT * FROM missing"]).unwrap_err();
+    assert_eq!(e.code(), Code::ERROR);
+    Ok(())
+}
+
+#[test]
+fn bind_owned() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE documents (body TEXT, data BLOB)")?;
+
+    let mut insert = c.prepare("INSERT INTO documents VALUES (?, ?)")?;
+
+    for n in 0..3 {
+        insert.reset()?;
+        insert.bind_owned(1, format!("document {n}"))?;
+        insert.bind_owned_blob(2, vec![n; 4])?;
+        assert_eq!(insert.step()?, State::Done);
+    }
+
+    // Rebinding an owned parameter with a borrowed value.
+    insert.reset()?;
+    insert.bind(1, "borrowed")?;
+    insert.bind(2, ())?;
+    assert_eq!(insert.step()?, State::Done);
+
+    insert.reset()?;
+    insert.bind_owned(1, String::from("cleared"))?;
+    insert.clear_bindings()?;
+    assert_eq!(insert.step()?, State::Done);
+
+    let rows = c
+        .prepare("SELECT body, data FROM documents ORDER BY rowid")?
+        .iter::<(Option<String>, Option<Vec<u8>>)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        rows,
+        [
+            (Some(String::from("document 0")), Some(vec![0; 4])),
+            (Some(String::from("document 1")), Some(vec![1; 4])),
+            (Some(String::from("document 2")), Some(vec![2; 4])),
+            (Some(String::from("borrowed")), None),
+            (None, None),
+        ]
+    );
+
+    insert.reset()?;
+    let e = insert.bind_owned(3, String::new()).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    Ok(())
+}
+
+#[test]
+fn statement_raw_handle() -> sqlite_ll::Result<()> {
+    use std::ffi::CStr;
+
+    use sqlite_ll::{ffi, Statement};
+
+    let c = Connection::open(":memory:")?;
+    let stmt = c.prepare("SELECT ?")?;
+
+    let sql = unsafe { CStr::from_ptr(ffi::sqlite3_sql(stmt.as_raw())) };
+    assert_eq!(sql.to_str().ok(), Some("SELECT ?"));
+
+    // Hand the handle over to a new statement without finalizing it.
+    let raw = stmt.as_raw();
+    std::mem::forget(stmt);
+
+    let mut stmt = unsafe { Statement::from_raw(raw) };
+    stmt.bind(1, "adopted")?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "adopted");
+    Ok(())
+}
+
+#[test]
+fn connection_raw_handle() -> sqlite_ll::Result<()> {
+    use std::ptr;
+
+    use sqlite_ll::ffi;
+
+    // A handle opened by other code.
+    let mut raw = ptr::null_mut();
+
+    let code = unsafe {
+        ffi::sqlite3_open_v2(
+            b":memory:\0".as_ptr().cast(),
+            &mut raw,
+            ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE,
+            ptr::null(),
+        )
+    };
+
+    assert_eq!(code, ffi::SQLITE_OK);
+
+    let mut c = unsafe { Connection::from_raw(raw) };
+    assert_eq!(c.as_raw(), raw);
+    c.set_busy_handler(|_| false)?;
+    c.execute("CREATE TABLE numbers (n INTEGER); INSERT INTO numbers VALUES (1), (2);")?;
+    c.prepare_cached("SELECT n FROM numbers")?;
+
+    // A statement which outlives the connection wrapper.
+    let mut stmt = c.prepare("SELECT SUM(n) FROM numbers")?;
+
+    assert_eq!(c.into_raw(), raw);
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<i64>(0)?, 3);
+    drop(stmt);
+
+    // Ownership has been handed back, so the handle can be closed.
+    assert_eq!(unsafe { ffi::sqlite3_close(raw) }, ffi::SQLITE_OK);
+    Ok(())
+}
+
+#[test]
+fn autocommit() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE numbers (n INTEGER)")?;
+    assert!(c.is_autocommit());
+
+    c.execute("BEGIN; INSERT INTO numbers VALUES (1);")?;
+    assert!(!c.is_autocommit());
+    c.execute("ROLLBACK")?;
+    assert!(c.is_autocommit());
+
+    c.execute("SAVEPOINT a")?;
+    assert!(!c.is_autocommit());
+    c.execute("RELEASE a")?;
+    assert!(c.is_autocommit());
+
+    let c = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .set_full_mutex()
+        .open(":memory:")?;
+    let c = SyncConnection::new(c).map_err(|(_, e)| e)?;
+    c.execute("BEGIN")?;
+    assert!(!c.is_autocommit());
+    Ok(())
+}
+
+#[test]
+fn statement_reprepare_on_schema_change() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+    c.execute("INSERT INTO users VALUES ('Alice', 42), ('Bob', 69)")?;
+
+    // Statements prepared through the legacy interface are not re-prepared
+    // by SQLite when the schema changes.
+    let sql = "SELECT name FROM users WHERE age = ?";
+    let mut raw = std::ptr::null_mut();
+
+    let code = unsafe {
+        sqlite_ll::ffi::sqlite3_prepare(
+            c.as_raw(),
+            sql.as_ptr().cast(),
+            sql.len() as _,
+            &mut raw,
+            std::ptr::null_mut(),
+        )
+    };
+
+    assert_eq!(code, sqlite_ll::ffi::SQLITE_OK);
+
+    let mut stmt = unsafe { Statement::from_raw(raw) };
+    stmt.bind(1, 69)?;
+
+    c.execute("CREATE INDEX users_age ON users (age)")?;
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "Bob");
+    assert_eq!(stmt.step()?, State::Done);
+
+    c.execute("DROP INDEX users_age")?;
+    stmt.reset()?;
+    stmt.bind(1, 42)?;
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "Alice");
+    Ok(())
+}
+
+#[test]
+fn statement_run_resets() -> sqlite_ll::Result<()> {
+    fn first_adult(stmt: &mut Statement) -> sqlite_ll::Result<String> {
+        let mut stmt = stmt.run();
+
+        while let Some(row) = stmt.next_row()? {
+            if row.get::<i64>(1)? >= 18 {
+                return row.get(0);
+            }
+        }
+
+        Err(sqlite_ll::Error::custom("no adults"))
+    }
+
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+    c.execute("INSERT INTO users VALUES ('Carol', 12), ('Alice', 42), ('Bob', 69)")?;
+
+    let mut stmt = c.prepare("SELECT name, age FROM users ORDER BY rowid")?;
+    assert_eq!(first_adult(&mut stmt)?, "Alice");
+    assert_eq!(first_adult(&mut stmt)?, "Alice");
+
+    // The connection is back in autocommit mode with no pending reads, so
+    // the table can be dropped.
+    c.execute("DROP TABLE users")?;
+    Ok(())
+}
+
+#[test]
+fn bind_after_step_is_described() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?")?;
+    stmt.bind(1, 1)?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let e = stmt.bind(1, 2).unwrap_err();
+    assert_eq!(e.code(), Code::MISUSE);
+    assert!(e.to_string().contains("it must be reset first"));
+
+    let e = stmt.bind_owned(1, String::from("Alice")).unwrap_err();
+    assert_eq!(e.code(), Code::MISUSE);
+
+    stmt.reset()?;
+    stmt.bind(1, 2)?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<i64>(0)?, 2);
+    Ok(())
+}
+
+#[test]
+fn bind_misuse_from_value_is_kept() -> sqlite_ll::Result<()> {
+    struct FromEmptyStatement;
+
+    impl sqlite_ll::Bindable for FromEmptyStatement {
+        fn bind(self, statement: &mut Statement, i: usize) -> sqlite_ll::Result<()> {
+            let c = Connection::open(":memory:")?;
+            let other = c.prepare("SELECT 1")?;
+            other.read::<i64>(0)?.bind(statement, i)
+        }
+    }
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?")?;
+
+    let e = stmt.bind(1, FromEmptyStatement).unwrap_err();
+    assert_eq!(e.code(), Code::MISUSE);
+    assert!(e.to_string().contains("no row is available"));
+
+    stmt.bind(1, 1)?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    let e = stmt.bind(1, FromEmptyStatement).unwrap_err();
+    assert!(e.to_string().contains("it must be reset first"));
+    Ok(())
+}
+
+#[test]
+fn read_without_row_is_described() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 42")?;
+
+    let e = stmt.read::<i64>(0).unwrap_err();
+    assert_eq!(e.code(), Code::MISUSE);
+    assert!(e.to_string().contains("no row is available"));
+    assert_eq!(
+        stmt.read_by_name::<i64>("42").unwrap_err().code(),
+        Code::MISUSE
+    );
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<i64>(0)?, 42);
+    assert_eq!(stmt.step()?, State::Done);
+    assert_eq!(stmt.read_str(0).unwrap_err().code(), Code::MISUSE);
+    assert_eq!(stmt.read_blob(0).unwrap_err().code(), Code::MISUSE);
+    assert_eq!(stmt.read_value_ref(0).unwrap_err().code(), Code::MISUSE);
+
+    stmt.reset()?;
+    assert_eq!(
+        stmt.read_by_name::<i64>("42").unwrap_err().code(),
+        Code::MISUSE
+    );
+    Ok(())
+}
+
+#[test]
+fn read_out_of_range_is_described() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 42")?;
+    assert_eq!(stmt.column_name(1).unwrap_err().code(), Code::RANGE);
+    assert_eq!(stmt.column_decltype(1).unwrap_err().code(), Code::RANGE);
+
+    assert_eq!(stmt.step()?, State::Row);
+
+    let e = stmt.read::<i64>(1).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    assert!(e
+        .to_string()
+        .contains("column 1 is out of range for a statement with 1 columns"));
+
+    assert_eq!(stmt.read_declared(1).unwrap_err().code(), Code::RANGE);
+    Ok(())
+}
+
+#[test]
+fn wal_pool_caches_writes_on_writer() -> Result<(), Box<dyn std::error::Error>> {
+    fn cached(c: &Connection) -> Vec<String> {
+        let mut sql = Vec::new();
+
+        unsafe {
+            let mut stmt = sqlite_ll::ffi::sqlite3_next_stmt(c.as_raw(), std::ptr::null_mut());
+
+            while !stmt.is_null() {
+                let s = std::ffi::CStr::from_ptr(sqlite_ll::ffi::sqlite3_sql(stmt));
+                sql.push(s.to_string_lossy().into_owned());
+                stmt = sqlite_ll::ffi::sqlite3_next_stmt(c.as_raw(), stmt);
+            }
+        }
+
+        sql
+    }
+
+    let directory = Directory::new("sqlite")?;
+    let pool = WalPool::open(directory.path().join("database.sqlite3"), 1)?;
+    pool.execute("CREATE TABLE numbers (n INTEGER)")?;
+
+    let insert = "INSERT INTO numbers VALUES (?) RETURNING n";
+    let select = "SELECT COUNT(*) FROM numbers";
+    assert_eq!(pool.query_value::<i64, _>(insert, (42,))?, 42);
+    assert_eq!(pool.query_value::<i64, _>(select, ())?, 1);
+
+    assert_eq!(cached(&pool.reader()), [select]);
+    assert_eq!(cached(&pool.writer()), [insert]);
+    Ok(())
+}
+
+#[test]
+fn statement_column_index_after_reprepare() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE t (a INTEGER, b INTEGER); INSERT INTO t VALUES (1, 2);")?;
+
+    let mut stmt = c.prepare("SELECT * FROM t")?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.column_index("a")?, Some(0));
+    assert_eq!(stmt.read_by_name::<i64>("a")?, 1);
+    stmt.reset()?;
+
+    c.execute("ALTER TABLE t RENAME a TO x; ALTER TABLE t RENAME b TO a;")?;
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.column_index("x")?, Some(0));
+    assert_eq!(stmt.column_index("a")?, Some(1));
+    assert_eq!(stmt.read_by_name::<i64>("a")?, 2);
+    Ok(())
+}
+
+#[test]
+fn statement_columns_after_reprepare() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    c.execute("CREATE TABLE t (a INTEGER, b TEXT)")?;
+
+    let mut stmt = c.prepare("SELECT * FROM t")?;
+    let columns = stmt.columns()?;
+    assert_eq!(columns.names().collect::<Vec<_>>(), ["a", "b"]);
+
+    c.execute("ALTER TABLE t RENAME a TO x; ALTER TABLE t RENAME b TO a;")?;
+    assert_eq!(stmt.step()?, State::Done);
+
+    let columns = stmt.columns()?;
+    assert_eq!(columns.names().collect::<Vec<_>>(), ["x", "a"]);
+    assert_eq!(columns.get(1).and_then(|c| c.decltype()), Some("TEXT"));
+    Ok(())
+}
+
+#[test]
+fn prepare_cached_clears_op_budget() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let sql = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n LIMIT 1000) SELECT COUNT(*) FROM n";
+
+    let mut stmt = c.prepare_cached(sql)?;
+    stmt.set_op_budget(100);
+    drop(stmt);
+
+    let mut stmt = c.prepare_cached(sql)?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<i64>(0)?, 1000);
+    Ok(())
+}
+
+#[test]
+fn statement_non_zero() -> sqlite_ll::Result<()> {
+    use std::num::{NonZeroI64, NonZeroU32};
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?, 0, NULL")?;
+    stmt.bind(1, NonZeroU32::new(7))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<NonZeroU32>(0)?.get(), 7);
+    assert_eq!(
+        stmt.read::<NonZeroI64>(1).unwrap_err().code(),
+        Code::MISMATCH
+    );
+    assert_eq!(stmt.read::<Option<NonZeroI64>>(2)?, None);
+    Ok(())
+}
+
+#[test]
+fn statement_wide_integers() -> sqlite_ll::Result<()> {
+    use sqlite_ll::AsText;
+
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?, ?, ?, 42, X'00', 'nope'")?;
+    stmt.bind_all((i128::MIN, u128::MAX, AsText(-7i128)))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.column_type(0), Type::Blob);
+    assert_eq!(stmt.read::<Vec<u8>>(1)?, vec![0xff; 16]);
+    assert_eq!(stmt.read::<i128>(0)?, i128::MIN);
+    assert_eq!(stmt.read::<u128>(1)?, u128::MAX);
+    assert_eq!(stmt.read::<AsText<i128>>(2)?, AsText(-7));
+    assert_eq!(stmt.read::<u128>(3)?, 42);
+
+    assert_eq!(stmt.read::<i128>(4).unwrap_err().code(), Code::MISMATCH);
+    assert_eq!(
+        stmt.read::<AsText<i128>>(5).unwrap_err().code(),
+        Code::MISMATCH
+    );
+    Ok(())
+}
+
+#[test]
+fn statement_read_by_name() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let mut s = c.prepare("SELECT id, name FROM users")?;
+
+    assert_eq!(s.step()?, State::Row);
+    assert_eq!(s.read_by_name::<i64>("id")?, 1);
+    assert_eq!(s.read_by_name::<String>("name")?, "Alice");
+
+    let e = s.read_by_name::<String>("email").unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    assert!(e
+        .to_string()
+        .ends_with("no column named `email`, available columns: `id`, `name`"));
+    Ok(())
+}
+
+#[test]
+fn statement_read_row() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    c.execute("INSERT INTO users VALUES (2, 'Bob', 69.42, NULL, 'bob@example.com')")?;
+    let mut s = c.prepare("SELECT * FROM users")?;
+
+    let mut row = vec![Value::Null; 8];
+
+    assert_eq!(s.step()?, State::Row);
+    s.read_row(&mut row)?;
+
+    assert_eq!(
+        row,
+        [
+            Value::Integer(1),
+            Value::Text(String::from("Alice")),
+            Value::Float(42.69),
+            Value::Blob(vec![0x42, 0x69]),
+            Value::Null,
+        ]
+    );
+
+    assert_eq!(s.step()?, State::Row);
+    s.read_row(&mut row)?;
+
+    assert_eq!(
+        row,
+        [
+            Value::Integer(2),
+            Value::Text(String::from("Bob")),
+            Value::Float(69.42),
+            Value::Null,
+            Value::Text(String::from("bob@example.com")),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn statement_read_with_nullable() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let s = "SELECT * FROM users";
+    let mut s = c.prepare(s)?;
+
+    assert_eq!(s.step()?, State::Row);
+    assert_eq!(s.read::<Option<i64>>(0)?, Some(1));
+    assert_eq!(s.read::<Option<String>>(1)?, Some(String::from("Alice")));
+    assert_eq!(s.read::<Option<f64>>(2)?, Some(42.69));
+    assert_eq!(s.read::<Option<Vec<u8>>>(3)?, Some(vec![0x42, 0x69]));
+    assert_eq!(s.read::<Option<String>>(4)?, None);
+    assert_eq!(s.step()?, State::Done);
+    Ok(())
+}
+
+#[test]
+fn statement_next_row() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let mut s = c.prepare("SELECT id, name, email FROM users")?;
+
+    let row = s.next_row()?.unwrap();
+    assert_eq!(row.len(), 3);
+    assert_eq!(row.column_name(1)?, "name");
+    assert_eq!(row.get::<i64>(0)?, 1);
+    assert_eq!(row.get_by_name::<String>("name")?, "Alice");
+    assert_eq!(row.get_by_name::<Option<String>>("email")?, None);
+    assert_eq!(row.get::<i64>(3).unwrap_err().code(), Code::RANGE);
+    assert_eq!(
+        row.get_by_name::<i64>("missing").unwrap_err().code(),
+        Code::RANGE
+    );
+
+    assert!(s.next_row()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn statement_column_iter() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    let mut s = c.prepare("SELECT rowid, value FROM english WHERE value LIKE '%type'")?;
+
+    let ids = s
+        .column_iter::<i64>(0)
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+    assert_eq!(ids, [1, 2, 3, 4, 5, 6]);
+
+    s.reset()?;
+    let mut it = s.column_iter::<String>(2);
+    assert_eq!(it.next().unwrap().unwrap_err().code(), Code::RANGE);
+    assert!(it.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn statement_cursor() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    let mut s = c.prepare("SELECT value FROM english WHERE value LIKE ?")?;
+
+    let mut cursor = s.cursor(&[Value::Text(String::from("%type"))])?;
+    let mut count = 0;
+
+    while cursor.next_row()?.is_some() {
+        count += 1;
+    }
+
+    assert_eq!(count, 6);
+
+    let rows = s
+        .cursor(&[Value::Text(String::from("non%"))])?
+        .iter::<(String,)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(rows, [(String::from("nonsence"),)]);
+    Ok(())
+}
+
+#[test]
+fn statement_row_to_map() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let mut s = c.prepare("SELECT id, name, email, id * 2 AS id FROM users")?;
+
+    let row = s.next_row()?.unwrap();
+
+    let map = row.to_map()?;
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["id"], Value::Integer(2));
+    assert_eq!(map["name"], Value::Text(String::from("Alice")));
+    assert_eq!(map["email"], Value::Null);
+
+    let pairs = row.to_pairs()?;
+    assert_eq!(
+        pairs,
+        [
+            (String::from("id"), Value::Integer(1)),
+            (String::from("name"), Value::Text(String::from("Alice"))),
+            (String::from("email"), Value::Null),
+            (String::from("id"), Value::Integer(2)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn statement_bind_all() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+
+    let mut stmt =
+        c.prepare("INSERT INTO users (id, name, age, photo, email) VALUES (?, ?, ?, ?, ?)")?;
+    stmt.bind_all((2, "Bob", 69.42, &b"\x01"[..], None::<&str>))?;
+    assert_eq!(stmt.step_checked()?, State::Done);
+
+    let row = c.query_row::<(i64, String, f64, Vec<u8>, Option<String>), _>(
+        "SELECT * FROM users WHERE id = ?",
+        (2,),
+    )?;
+
+    assert_eq!(row, (2, "Bob".to_string(), 69.42, vec![1], None));
+    Ok(())
+}
+
+#[test]
+fn statement_bind_values() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT ?, ?")?;
+
+    stmt.bind_values(&[Value::Integer(1), Value::Text("a".into())])?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(1)?, "a");
+
+    stmt.reset()?;
+    let e = stmt.bind_values(&[Value::Integer(1)]).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+
+    let e = stmt.bind_iter(["a", "b", "c"]).unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+
+    stmt.bind_iter(vec![String::from("b"), String::from("c")])?;
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "b");
+    Ok(())
+}
+
+#[test]
+fn statement_bind_named() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let mut stmt = c.prepare("SELECT name FROM users WHERE id = :id AND name = @name")?;
+
+    let e = stmt
+        .bind_named([
+            (":id", Value::Integer(1)),
+            (":nope", Value::Null),
+            ("$x", Value::Null),
+        ])
+        .unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    assert!(e.to_string().contains("unknown parameters: `:nope`, `$x`"));
+
+    // Nothing was bound by the failed call.
+    assert!(stmt.step_checked().is_err());
+
+    let params = vec![
+        (String::from(":id"), Value::Integer(1)),
+        (String::from("@name"), Value::Text("Alice".into())),
+    ];
+    stmt.bind_named(params)?;
+    assert_eq!(stmt.step_checked()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "Alice");
+    Ok(())
+}
+
+#[test]
+fn statement_parameters() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let stmt = c.prepare("SELECT * FROM users WHERE id = ? AND name = :name OR age > @age")?;
+
+    assert_eq!(stmt.parameter_count(), 3);
+
+    let names = (1..=stmt.parameter_count())
+        .map(|i| stmt.parameter_name(i))
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(names, [None, Some(":name"), Some("@age")]);
+    assert_eq!(stmt.parameter_name(0)?, None);
+    Ok(())
+}
+
+#[test]
+fn statement_step_checked() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let mut stmt = c.prepare("INSERT INTO users (id, name, age) VALUES (?, :name, ?3)")?;
+
+    let e = stmt.step_checked().unwrap_err();
+    assert_eq!(e.code(), Code::RANGE);
+    assert!(e.to_string().contains("parameter 1 is not bound"));
+
+    stmt.bind(1, 2)?;
+    stmt.bind(3, 69.0)?;
+    let e = stmt.step_checked().unwrap_err();
+    assert!(e.to_string().contains("parameter 2 (`:name`) is not bound"));
+
+    stmt.bind_by_name(":name", "Bob")?;
+    assert_eq!(stmt.step_checked()?, State::Done);
+
+    // Bindings survive a reset, but not clearing them.
+    stmt.reset()?;
+    stmt.bind(1, 3)?;
+    assert_eq!(stmt.step_checked()?, State::Done);
+
+    stmt.reset()?;
+    stmt.clear_bindings()?;
+    assert!(stmt.step_checked().is_err());
+    Ok(())
+}
+
+#[test]
+fn statement_typestate() -> sqlite_ll::Result<()> {
+    use sqlite_ll::typestate::{Prepared, Step};
+
+    let c = setup_users(":memory:")?;
+    let prepared = Prepared::new(c.prepare("SELECT name FROM users WHERE id = ?")?)?;
+
+    let done = match prepared.bind(&[Value::Integer(1)])?.step()? {
+        Step::Row(row) => {
+            assert_eq!(row.read::<String>(0)?, "Alice");
+            assert_eq!(row.row().get::<String>(0)?, "Alice");
+
+            match row.step()? {
+                Step::Row(..) => panic!("expected done"),
+                Step::Done(done) => done,
+            }
+        }
+        Step::Done(..) => panic!("expected row"),
+    };
+
+    match done.reset()?.bind(&[Value::Integer(2)])?.step()? {
+        Step::Row(..) => panic!("expected done"),
+        Step::Done(done) => {
+            done.into_statement();
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn statement_owned() -> sqlite_ll::Result<()> {
+    let mut stmt = OwnedStatement::new(setup_users(":memory:")?, "SELECT name FROM users")?;
+
+    stmt.connection()
+        .execute("INSERT INTO users (id, name) VALUES (2, 'Bob')")?;
+
+    let names = stmt
+        .iter::<(String,)>()
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+    assert_eq!(names, [("Alice".to_string(),), ("Bob".to_string(),)]);
+
+    let (c, _) = stmt.into_parts();
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM users", ())?,
+        2
+    );
+    Ok(())
+}
+
+#[test]
+fn statement_outlives_connection() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = Directory::new("sqlite")?;
+    let path = directory.path().join("database.sqlite3");
+
+    let mut stmt = {
+        let c = setup_users(&path)?;
+        c.prepare("SELECT name FROM users")?
+    };
+
+    assert_eq!(stmt.step()?, State::Row);
+    assert_eq!(stmt.read::<String>(0)?, "Alice");
+    assert_eq!(stmt.step()?, State::Done);
+    Ok(())
+}
+
+#[test]
+fn statement_set() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+
+    let mut set = StatementSet::builder()
+        .add("by_id", "SELECT name FROM users WHERE id = ?")
+        .add("count", "SELECT COUNT(*) FROM users")
+        .prepare(&c)?;
+
+    assert_eq!(set.len(), 2);
+    assert!(set.get("missing").is_none());
+
+    let by_id = &mut set["by_id"];
+    by_id.bind(1, 1)?;
+    assert_eq!(by_id.step()?, State::Row);
+    assert_eq!(by_id.read::<String>(0)?, "Alice");
+
+    let e = StatementSet::builder()
+        .add("count", "SELECT COUNT(*) FROM users")
+        .add("broken", "SELECT * FROM missing")
+        .prepare(&c)
+        .err()
+        .expect("expected error");
+    assert_eq!(e.code(), Code::ERROR);
+    assert!(e.to_string().contains("preparing statement `broken`"));
+
+    let e = StatementSet::builder()
+        .add("count", "SELECT 1")
+        .add("count", "SELECT 2")
+        .prepare(&c)
+        .err()
+        .expect("expected error");
+    assert!(e.to_string().contains("more than once"));
+    Ok(())
+}
+
+#[test]
+fn statement_iter() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    let mut s = c.prepare("SELECT value FROM english WHERE value LIKE '%type'")?;
+
+    let values = s
+        .iter::<(String,)>()
+        .map(|row| Ok(row?.0))
+        .take(2)
+        .collect::<sqlite_ll::Result<Vec<_>>>()?;
+
+    assert_eq!(values, ["cerotype", "metatype"]);
+
+    s.reset()?;
+    let mut count = 0;
+
+    for row in &mut s {
+        assert_eq!(row?.len(), 1);
+        count += 1;
+    }
+
+    assert_eq!(count, 6);
+
+    s.reset()?;
+    let rows = s.into_iter().collect::<sqlite_ll::Result<Vec<_>>>()?;
+    assert_eq!(rows.len(), 6);
+    assert_eq!(rows[0], [Value::Text(String::from("cerotype"))]);
+    Ok(())
+}
+
+#[test]
+fn statement_wildcard() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    let s = "SELECT value FROM english WHERE value LIKE '%type'";
+    let mut s = c.prepare(s)?;
+
+    let mut count = 0;
+
+    while let State::Row = s.step()? {
+        count += 1;
+    }
+
+    assert_eq!(count, 6);
+    Ok(())
+}
+
+#[test]
+fn statement_wildcard_with_binding() -> sqlite_ll::Result<()> {
+    let c = setup_english(":memory:")?;
+    let s = "SELECT value FROM english WHERE value LIKE ?";
+    let mut s = c.prepare(s)?;
+    s.bind(1, "%type")?;
+
+    let mut count = 0;
+    while let State::Row = s.step()? {
+        count += 1;
+    }
+    assert_eq!(count, 6);
+    Ok(())
+}
+
+#[test]
+fn test_dropped_connection() -> sqlite_ll::Result<()> {
+    let c = setup_users(":memory:")?;
+    let s = "SELECT id, name, age, photo AS user_photo FROM users";
+    let s = c.prepare(s)?;
+    drop(c);
+
+    let names = s.column_names()?;
+    assert_eq!(names, vec!["id", "name", "age", "user_photo"]);
     assert_eq!("user_photo", s.column_name(3)?);
     Ok(())
 }
@@ -326,11 +2736,20 @@ where
     T: AsRef<Path>,
 {
     let c = Connection::open(path)?;
-    c.execute(
-        "
-        CREATE TABLE users (id INTEGER, name TEXT, age REAL, photo BLOB, email TEXT);
-        INSERT INTO users VALUES (1, 'Alice', 42.69, X'4269', NULL);
-        ",
-    )?;
+
+    Fixture::new()
+        .table(
+            "users",
+            [
+                "id INTEGER",
+                "name TEXT",
+                "age REAL",
+                "photo BLOB",
+                "email TEXT",
+            ],
+        )
+        .row((1, "Alice", 42.69, &[0x42u8, 0x69][..]))
+        .apply(&c)?;
+
     Ok(c)
 }
@@ -0,0 +1,55 @@
+#![cfg(feature = "time")]
+
+use sqlite_ll::{Connection, State};
+use time::macros::{date, datetime, time};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+#[test]
+fn time_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+
+    let date = date!(2023 - 04 - 01);
+    let time = time!(12:30:05.123456789);
+    let primitive = datetime!(2023-04-01 12:30:05.25);
+    let offset = datetime!(2023-04-01 12:30:05.000001 +02:00);
+
+    let mut stmt = c.prepare("SELECT ?, ?, ?, ?, datetime(?3), datetime(?4)")?;
+    stmt.bind_all((date, time, primitive, offset))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.read::<String>(0)?, "2023-04-01");
+    assert_eq!(stmt.read::<String>(1)?, "12:30:05.123456789");
+    assert_eq!(stmt.read::<String>(2)?, "2023-04-01 12:30:05.25");
+    assert_eq!(stmt.read::<String>(3)?, "2023-04-01T12:30:05.000001+02:00");
+
+    assert_eq!(stmt.read::<Date>(0)?, date);
+    assert_eq!(stmt.read::<Time>(1)?, time);
+    assert_eq!(stmt.read::<PrimitiveDateTime>(2)?, primitive);
+    assert_eq!(stmt.read::<OffsetDateTime>(3)?, offset);
+
+    // Values produced by SQLite's date functions.
+    assert_eq!(
+        stmt.read::<PrimitiveDateTime>(4)?,
+        datetime!(2023-04-01 12:30:05)
+    );
+    assert_eq!(
+        stmt.read::<OffsetDateTime>(5)?,
+        datetime!(2023-04-01 10:30:05 UTC)
+    );
+    Ok(())
+}
+
+#[test]
+fn time_invalid() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let mut stmt = c.prepare("SELECT 'nope', '2023-04-01 12:30:05+01:00'")?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert!(stmt.read::<PrimitiveDateTime>(0).is_err());
+    assert!(stmt.read::<Time>(0).is_err());
+    assert_eq!(
+        stmt.read::<OffsetDateTime>(1)?,
+        datetime!(2023-04-01 12:30:05 +01:00)
+    );
+    Ok(())
+}
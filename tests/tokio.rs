@@ -0,0 +1,214 @@
+#![cfg(feature = "tokio")]
+
+use std::ptr;
+
+use sqlite_ll::{ffi, AsyncConnection, Code, Connection, OpenOptions, State};
+
+#[tokio::test]
+async fn async_connection() -> sqlite_ll::Result<()> {
+    let c = AsyncConnection::open(":memory:").await?;
+    c.execute("CREATE TABLE users (name TEXT, age INTEGER)")
+        .await?;
+
+    let mut insert = c.prepare("INSERT INTO users VALUES (?, ?)").await?;
+
+    for (name, age) in [("Alice", 42), ("Bob", 69)] {
+        insert.reset().await?;
+        insert.bind_all((name.to_owned(), age)).await?;
+        assert_eq!(insert.step().await?, State::Done);
+    }
+
+    let mut select = c
+        .prepare("SELECT name, age FROM users ORDER BY age")
+        .await?;
+    assert_eq!(select.step().await?, State::Row);
+    assert_eq!(select.read::<String>(0).await?, "Alice");
+    assert_eq!(
+        select.next::<(String, i64)>().await?,
+        Some((String::from("Bob"), 69))
+    );
+    assert_eq!(select.next::<(String, i64)>().await?, None);
+
+    let count = c
+        .query_value::<i64, _>("SELECT COUNT(*) FROM users", ())
+        .await?;
+    assert_eq!(count, 2);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn async_connection_shared() -> sqlite_ll::Result<()> {
+    let c = AsyncConnection::new(Connection::open(":memory:")?);
+    c.execute("CREATE TABLE numbers (n INTEGER)").await?;
+
+    let tasks = (0..8i64)
+        .map(|n| {
+            let c = c.clone();
+            tokio::spawn(async move {
+                let mut stmt = c.prepare("INSERT INTO numbers VALUES (?)").await?;
+                stmt.bind_all((n,)).await?;
+                stmt.step().await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        assert_eq!(task.await.unwrap()?, State::Done);
+    }
+
+    let sum = c
+        .query_value::<i64, _>("SELECT SUM(n) FROM numbers", ())
+        .await?;
+    assert_eq!(sum, 28);
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_connection_transaction() -> sqlite_ll::Result<()> {
+    let c = AsyncConnection::open(":memory:").await?;
+    c.execute("CREATE TABLE numbers (n INTEGER)").await?;
+
+    c.transaction(|c| c.execute("INSERT INTO numbers VALUES (1)"))
+        .await?;
+
+    let e = c
+        .transaction(|c| {
+            c.execute("INSERT INTO numbers VALUES (2)")?;
+            c.execute("INSERT INTO missing VALUES (3)")
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(e.code(), Code::ERROR);
+
+    let task = tokio::spawn({
+        let c = c.clone();
+
+        async move {
+            c.transaction(|c| -> sqlite_ll::Result<()> {
+                c.execute("INSERT INTO numbers VALUES (4)")?;
+                panic!("boom")
+            })
+            .await
+        }
+    });
+
+    assert!(task.await.unwrap_err().is_panic());
+
+    let values = c
+        .call(|c| {
+            let mut stmt = c.prepare("SELECT n FROM numbers")?;
+            stmt.iter::<(i64,)>().collect::<sqlite_ll::Result<Vec<_>>>()
+        })
+        .await?;
+    assert_eq!(values, [(1,)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_connection_transaction_busy_commit() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = temporary::Directory::new("sqlite")?;
+    let path = directory.path().join("database.sqlite3");
+
+    let c = AsyncConnection::open(&path).await?;
+    c.execute("CREATE TABLE numbers (n INTEGER)").await?;
+
+    // Hold a read transaction, which prevents the transaction from committing.
+    let reader = Connection::open(&path)?;
+    reader.execute("BEGIN; SELECT * FROM numbers")?;
+
+    let e = c
+        .transaction(|c| c.execute("INSERT INTO numbers VALUES (1)"))
+        .await
+        .unwrap_err();
+    assert_eq!(e.code(), Code::BUSY);
+    assert!(c.call(|c| Ok(c.is_autocommit())).await?);
+
+    reader.execute("COMMIT")?;
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(*) FROM numbers", ())
+            .await?,
+        0
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_statement_finalized_on_worker() -> sqlite_ll::Result<()> {
+    let options = OpenOptions::new()
+        .set_create()
+        .set_read_write()
+        .set_no_mutex();
+    let c = AsyncConnection::open_with(options, ":memory:").await?;
+
+    let stmt = c.prepare("SELECT 1").await?;
+    drop(stmt);
+
+    let finalized = c
+        .call(|c| Ok(unsafe { ffi::sqlite3_next_stmt(c.as_raw(), ptr::null_mut()) }.is_null()))
+        .await?;
+    assert!(finalized);
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_connection_open_error() {
+    let e = AsyncConnection::open_with(
+        sqlite_ll::OpenOptions::new().set_read_only(),
+        "/definitely/missing/database.sqlite",
+    )
+    .await
+    .err()
+    .expect("expected error");
+    assert_eq!(e.code(), Code::CANTOPEN);
+}
+
+const FOREVER: &str =
+    "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) SELECT MAX(i) FROM n";
+
+#[tokio::test]
+async fn async_connection_cancel() -> sqlite_ll::Result<()> {
+    let c = AsyncConnection::open(":memory:").await?;
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let _ = tx.send(());
+    });
+
+    let cancel = async move {
+        let _ = rx.await;
+    };
+
+    let e = c
+        .call_until(cancel, |c| c.query_value::<i64, _>(FOREVER, ()))
+        .await
+        .unwrap_err();
+    assert_eq!(e.code(), Code::INTERRUPT);
+
+    // Jobs which complete before cancellation are unaffected.
+    let value = c
+        .call_until(std::future::pending(), |c| {
+            c.query_value::<i64, _>("SELECT 42", ())
+        })
+        .await?;
+    assert_eq!(value, 42);
+
+    let e = c
+        .execute_until(std::future::ready(()), FOREVER)
+        .await
+        .unwrap_err();
+    assert_eq!(e.code(), Code::INTERRUPT);
+
+    let mut stmt = c.prepare(FOREVER).await?;
+    let e = stmt
+        .call_until(std::future::ready(()), |s| s.step())
+        .await
+        .unwrap_err();
+    assert_eq!(e.code(), Code::INTERRUPT);
+
+    // The statement is handed back and can be used again.
+    stmt.reset().await?;
+    assert_eq!(c.query_value::<i64, _>("SELECT 1", ()).await?, 1);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "unicode")]
+
+use sqlite_ll::{Connection, UnicodeFunctions};
+
+#[test]
+fn unicode_functions() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    UnicodeFunctions::new().register(&c)?;
+
+    let text = |sql: &str| c.query_value::<Option<String>, _>(sql, ());
+
+    assert_eq!(text("SELECT upper('straße')")?.as_deref(), Some("STRASSE"));
+    assert_eq!(text("SELECT lower('ΣΊΣΥΦΟΣ')")?.as_deref(), Some("σίσυφος"));
+    assert_eq!(text("SELECT upper(42)")?.as_deref(), Some("42"));
+    assert_eq!(text("SELECT upper(NULL)")?, None);
+
+    assert_eq!(text("SELECT nfc('e\u{301}')")?.as_deref(), Some("\u{e9}"));
+    assert_eq!(text("SELECT nfd('\u{e9}')")?.as_deref(), Some("e\u{301}"));
+    assert_eq!(text("SELECT nfkc('\u{fb01}')")?.as_deref(), Some("fi"));
+    assert_eq!(text("SELECT nfkd('\u{2460}')")?.as_deref(), Some("1"));
+
+    c.execute(
+        "
+        CREATE TABLE words (word TEXT COLLATE NOCASE);
+        INSERT INTO words VALUES ('Öl'), ('apa'), ('öl'), ('Apa'), ('Ärm');
+        ",
+    )?;
+
+    assert_eq!(
+        c.query_value::<i64, _>("SELECT COUNT(DISTINCT word) FROM words", ())?,
+        3
+    );
+    assert!(c.query_value::<bool, _>("SELECT 'ÄRM' = 'ärm' COLLATE NOCASE", ())?);
+    Ok(())
+}
+
+#[test]
+fn unicode_functions_selected() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    UnicodeFunctions::new()
+        .upper(false)
+        .nocase(false)
+        .register(&c)?;
+
+    assert_eq!(
+        c.query_value::<String, _>("SELECT upper('åsa')", ())?,
+        "åSA"
+    );
+    assert!(!c.query_value::<bool, _>("SELECT 'Å' = 'å' COLLATE NOCASE", ())?);
+    assert_eq!(
+        c.query_value::<String, _>("SELECT nfc('a\u{30a}')", ())?,
+        "å"
+    );
+
+    let c = Connection::open(":memory:")?;
+    UnicodeFunctions::new().normalize(false).register(&c)?;
+    assert!(c.execute("SELECT nfc('a')").is_err());
+    Ok(())
+}
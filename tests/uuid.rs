@@ -0,0 +1,30 @@
+#![cfg(feature = "uuid")]
+
+use sqlite_ll::{Connection, State, Type};
+use uuid::fmt::Hyphenated;
+use uuid::Uuid;
+
+#[test]
+fn uuid_round_trip() -> sqlite_ll::Result<()> {
+    let c = Connection::open(":memory:")?;
+    let uuid = Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+
+    let mut stmt = c.prepare("SELECT ?, ?, X'00', 'nope'")?;
+    stmt.bind_all((uuid, uuid.hyphenated()))?;
+    assert_eq!(stmt.step()?, State::Row);
+
+    assert_eq!(stmt.column_type(0), Type::Blob);
+    assert_eq!(stmt.read::<Vec<u8>>(0)?, uuid.as_bytes());
+    assert_eq!(
+        stmt.read::<String>(1)?,
+        "67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+
+    assert_eq!(stmt.read::<Uuid>(0)?, uuid);
+    assert_eq!(stmt.read::<Uuid>(1)?, uuid);
+    assert_eq!(stmt.read::<Hyphenated>(0)?, uuid.hyphenated());
+
+    assert!(stmt.read::<Uuid>(2).is_err());
+    assert!(stmt.read::<Uuid>(3).is_err());
+    Ok(())
+}